@@ -0,0 +1,81 @@
+//! Allocations and time to base64-encode a batch of ~1 KB transactions, comparing the
+//! serialize-then-encode approach with `encode_transaction_into` and a reused buffer.
+//!
+//! Run with `cargo bench --bench encode --features solana`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use aethokit::encode_transaction_into;
+use base64::Engine as _;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const BATCH: usize = 10_000;
+
+fn measure(name: &str, txs: &[Transaction], mut encode: impl FnMut(&Transaction) -> usize) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let started = Instant::now();
+    let mut total = 0;
+    for tx in txs {
+        total += encode(tx);
+    }
+    let elapsed = started.elapsed();
+    black_box(total);
+    println!(
+        "{name:<28} {:>8.2} allocs/tx {:>8} bytes/tx {:>8.2} us/tx",
+        (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64 / txs.len() as f64,
+        (BYTES.load(Ordering::Relaxed) - bytes) / txs.len(),
+        elapsed.as_secs_f64() * 1e6 / txs.len() as f64,
+    );
+}
+
+fn main() {
+    let txs: Vec<Transaction> = (0..BATCH)
+        .map(|_| {
+            let signer = AccountMeta::new(Pubkey::new_unique(), true);
+            let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[7; 1000], vec![signer]);
+            Transaction::new_with_payer(&[ix], Some(&Pubkey::new_unique()))
+        })
+        .collect();
+
+    measure("serialize + encode", &txs, |tx| {
+        let bytes = bincode::serialize(tx).unwrap();
+        base64::engine::general_purpose::STANDARD.encode(bytes).len()
+    });
+    let mut out = String::new();
+    measure("encode_transaction_into", &txs, |tx| {
+        encode_transaction_into(tx, &mut out).unwrap();
+        out.len()
+    });
+}
@@ -1,34 +1,16 @@
-use aethokit::{Aethokit, AethokitConfig, SponsorTxRequest};
+use aethokit::{Aethokit, Priority, SponsoredTxBuilder};
 use solana_sdk::{
     instruction::Instruction,
-    message::Message,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    system_instruction,
-    transaction::Transaction,
 };
-use solana_client::rpc_client::RpcClient;
-use serde::Serialize;
+use solana_system_interface::instruction as system_instruction;
 use std::str::FromStr;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // load your GAS KEY from environment
-    let gas_key = std::env::var("AETHOKIT_GAS_KEY")
-        .expect("set AETHOKIT_GAS_KEY in your environment");
-
-    // initialize with config
-    let aethokit_client = Aethokit::new(AethokitConfig {
-        gas_key,
-        rpc_or_network: Some("devnet".into()), // or None if you want default
-    })?;
-
-    // fetch gas address
-    let sponsor_addr = aethokit_client.get_gas_address().await?;
-    let sponsor_pubkey = Pubkey::from_str(&sponsor_addr)?;
-
-    let rpc_url = "https://api.devnet.solana.com"; // or mainnet(https://api.mainnet-beta.solana.com)
-    let rpc_client = RpcClient::new(rpc_url.to_string());
+    // initialize from AETHOKIT_GAS_KEY (and optionally AETHOKIT_NETWORK, e.g. "devnet")
+    let aethokit_client = Aethokit::from_env()?;
 
     // Sender (payer of funds, but not fees)
     let sender = Keypair::new();
@@ -45,19 +27,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         system_instruction::transfer(&sender.pubkey(), &recipient, lamports);
 
     // Recent blockhash (needed for transaction validity)
-    let blockhash = rpc_client.get_latest_blockhash()?;
+    // (fetched from the network configured above, or the RPC URL if one was given)
+    let blockhash = aethokit_client.get_latest_blockhash().await?;
 
-    // Message (sender pays, fee payer set to gas tank)
-    let message = Message::new(&[instruction], Some(&sponsor_addr));
-
-    let mut tx = Transaction::new_unsigned(message);
-    tx.try_partial_sign(&[&sender], blockhash)?; // sender signs, fee payer not set
-
-    // Serialize the tx (base64)
-    let serialized_tx = base64::encode(tx.serialize());
+    // build the tx with the gas tank as fee payer, sign as sender and sponsor it
+    let hash = aethokit_client
+        .sponsor_instructions(&[instruction], &[&sender], blockhash)
+        .await?;
+    println!("Hash: {}", hash);
 
-    // sponsor the tx and get hash
-    let hash = aethokit_client.sponsor_tx(serialized_tx).await?;
+    // or let the builder fetch the blockhash and add a priority fee
+    let hash = SponsoredTxBuilder::new(&aethokit_client)
+        .transfer(&sender.pubkey(), &recipient, lamports)
+        .memo("order-1")
+        .priority(Priority::High)
+        .build_and_sponsor(&[&sender])
+        .await?;
     println!("Hash: {}", hash);
 
     Ok(())
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{Aethokit, AethokitError};
+
+/// Header carrying the idempotency key of a sponsorship request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// One recipient of an airdrop batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropItem {
+    /// Caller-provided ID, stable across reruns of the batch (e.g. a user ID)
+    pub recipient_id: String,
+    /// Serialized transaction string for this recipient
+    pub transaction: String,
+}
+
+/// What happened to one recipient of [`Aethokit::sponsor_airdrop`].
+#[derive(Debug)]
+pub enum AirdropOutcome {
+    /// Sponsored in this run, with the transaction hash
+    Sent { recipient_id: String, hash: String },
+    /// Already recorded in the manifest by an earlier run; nothing was sent
+    AlreadySent { recipient_id: String, hash: String },
+    /// Sponsorship failed; rerunning the batch retries this recipient
+    Failed {
+        recipient_id: String,
+        error: AethokitError,
+    },
+}
+
+/// Idempotency key for `recipient_id` in `batch_id`, the same on every run.
+pub fn idempotency_key(batch_id: &str, recipient_id: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(batch_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(recipient_id.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    batch_id: String,
+    recipient_id: String,
+    hash: String,
+}
+
+/// Append-only record of the recipients an airdrop has already been sent to, one JSON
+/// line per completed recipient, so a crashed job can be rerun without double-sending.
+#[derive(Debug)]
+pub struct AirdropManifest {
+    path: PathBuf,
+    file: File,
+    completed: HashMap<(String, String), String>,
+}
+
+impl AirdropManifest {
+    /// Open the manifest at `path`, creating it if missing and loading completed entries.
+    ///
+    /// # Errors
+    /// - `Manifest` if the file cannot be opened or contains an invalid entry
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AethokitError> {
+        let path = path.as_ref().to_path_buf();
+        let manifest_error = |message: String| AethokitError::Manifest {
+            path: path.display().to_string(),
+            message,
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| manifest_error(err.to_string()))?;
+        let mut completed = HashMap::new();
+        for line in BufReader::new(&file).lines() {
+            let line = line.map_err(|err| manifest_error(err.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: ManifestEntry =
+                serde_json::from_str(&line).map_err(|err| manifest_error(err.to_string()))?;
+            completed.insert((entry.batch_id, entry.recipient_id), entry.hash);
+        }
+        Ok(Self { path, file, completed })
+    }
+
+    /// Hash of the transaction sent to `recipient_id` in `batch_id`, if any.
+    pub fn completed(&self, batch_id: &str, recipient_id: &str) -> Option<&str> {
+        self.completed
+            .get(&(batch_id.to_string(), recipient_id.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Record `recipient_id` as sent, flushing to disk before returning.
+    fn record(&mut self, batch_id: &str, recipient_id: &str, hash: &str) -> Result<(), AethokitError> {
+        let entry = ManifestEntry {
+            batch_id: batch_id.to_string(),
+            recipient_id: recipient_id.to_string(),
+            hash: hash.to_string(),
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .and_then(|()| self.file.sync_data())
+            .map_err(|err| AethokitError::Manifest {
+                path: self.path.display().to_string(),
+                message: err.to_string(),
+            })?;
+        self.completed
+            .insert((entry.batch_id, entry.recipient_id), entry.hash);
+        Ok(())
+    }
+}
+
+impl Aethokit {
+    /// Sponsor an airdrop batch exactly once per recipient.
+    ///
+    /// Recipients already in `manifest` for `batch_id` are skipped. Each request carries an
+    /// idempotency key derived from `(batch_id, recipient_id)`, so a recipient sent just
+    /// before a crash, but not yet recorded, is not charged twice on rerun. Recipients are
+    /// sent in order and recorded as they complete; a repeated `recipient_id` is only sent once.
+    ///
+    /// # Errors
+    /// - `Manifest` if a completed recipient cannot be recorded; the batch stops there
+    pub async fn sponsor_airdrop(
+        &self,
+        batch_id: &str,
+        items: impl IntoIterator<Item = AirdropItem>,
+        manifest: &mut AirdropManifest,
+    ) -> Result<Vec<AirdropOutcome>, AethokitError> {
+        let mut outcomes = Vec::new();
+        for item in items {
+            if let Some(hash) = manifest.completed(batch_id, &item.recipient_id) {
+                outcomes.push(AirdropOutcome::AlreadySent {
+                    hash: hash.to_string(),
+                    recipient_id: item.recipient_id,
+                });
+                continue;
+            }
+            let key = idempotency_key(batch_id, &item.recipient_id);
+            let result = match self.with_header(IDEMPOTENCY_KEY_HEADER, &key) {
+                Ok(client) => client.sponsor_signature(item.transaction).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(hash) => {
+                    manifest.record(batch_id, &item.recipient_id, &hash)?;
+                    outcomes.push(AirdropOutcome::Sent {
+                        recipient_id: item.recipient_id,
+                        hash,
+                    });
+                }
+                Err(error) => outcomes.push(AirdropOutcome::Failed {
+                    recipient_id: item.recipient_id,
+                    error,
+                }),
+            }
+        }
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Recorded recipients survive reopening the manifest, scoped to their batch.
+    #[test]
+    fn persists_completed_recipients() {
+        let path = std::env::temp_dir().join(format!("aethokit-manifest-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut manifest = AirdropManifest::open(&path).unwrap();
+        manifest.record("drop-1", "alice", "hash-a").unwrap();
+        drop(manifest);
+
+        let manifest = AirdropManifest::open(&path).unwrap();
+        assert_eq!(manifest.completed("drop-1", "alice"), Some("hash-a"));
+        assert_eq!(manifest.completed("drop-2", "alice"), None);
+        assert_eq!(idempotency_key("drop-1", "alice"), idempotency_key("drop-1", "alice"));
+        assert_ne!(idempotency_key("drop-1", "alice"), idempotency_key("drop-1a", "lice"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Recorded recipients are skipped, each request carries its recipient's key, and a
+    /// failed recipient is left out of the manifest so the next run sends it again.
+    #[tokio::test]
+    async fn sends_each_recipient_once() {
+        let path = std::env::temp_dir().join(format!("aethokit-airdrop-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut manifest = AirdropManifest::open(&path).unwrap();
+        manifest.record("drop-1", "alice", "hash-a").unwrap();
+
+        let backend = FakeBackend::new();
+        backend
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "hash-b" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(400, json!({ "error": "bad tx" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "hash-c" })));
+        let client = backend.client().build().unwrap();
+        let item = |recipient_id: &str| AirdropItem {
+            recipient_id: recipient_id.to_string(),
+            transaction: "AQID".to_string(),
+        };
+        let items = || ["alice", "bob", "carol"].map(item);
+
+        let outcomes = client.sponsor_airdrop("drop-1", items(), &mut manifest).await.unwrap();
+        assert!(matches!(&outcomes[0], AirdropOutcome::AlreadySent { hash, .. } if hash == "hash-a"));
+        assert!(matches!(&outcomes[1], AirdropOutcome::Sent { hash, .. } if hash == "hash-b"));
+        assert!(matches!(&outcomes[2], AirdropOutcome::Failed { recipient_id, .. } if recipient_id == "carol"));
+        assert_eq!(manifest.completed("drop-1", "carol"), None);
+        let keys: Vec<_> = backend.requests().into_iter().map(|req| req.idempotency_key).collect();
+        assert_eq!(
+            keys,
+            [Some(idempotency_key("drop-1", "bob")), Some(idempotency_key("drop-1", "carol"))]
+        );
+
+        let outcomes = client.sponsor_airdrop("drop-1", items(), &mut manifest).await.unwrap();
+        assert!(matches!(&outcomes[1], AirdropOutcome::AlreadySent { hash, .. } if hash == "hash-b"));
+        assert!(matches!(&outcomes[2], AirdropOutcome::Sent { hash, .. } if hash == "hash-c"));
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].idempotency_key, Some(idempotency_key("drop-1", "carol")));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+//! Helpers for sponsoring calls to Anchor programs without depending on `anchor-client`.
+//!
+//! Anchor instructions are plain [`Instruction`]s whose data starts with an 8-byte
+//! discriminator derived from the instruction name. With an Anchor-generated client, pass
+//! its instructions (e.g. `program.request().accounts(..).args(..).instructions()?`) to
+//! [`SponsoredTxBuilder::instruction`] or [`Aethokit::sponsor_instructions`]; otherwise
+//! build them with [`instruction`]:
+//!
+//! ```no_run
+//! # use aethokit::{anchor, tx::SponsoredTxBuilder, Aethokit};
+//! # use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::{Keypair, Signer}};
+//! # async fn run(client: &Aethokit, program: Pubkey, user: &Keypair, counter: Pubkey) -> Result<(), aethokit::AethokitError> {
+//! // `increment { amount: u64 }`, borsh-encoded
+//! let args = 5u64.to_le_bytes();
+//! let accounts = vec![AccountMeta::new(counter, false), AccountMeta::new_readonly(user.pubkey(), true)];
+//! let hash = SponsoredTxBuilder::new(client)
+//!     .instruction(anchor::instruction(program, "increment", &args, accounts))
+//!     .build_and_sponsor(&[user])
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SponsoredTxBuilder::instruction`]: crate::tx::SponsoredTxBuilder::instruction
+//! [`Aethokit::sponsor_instructions`]: crate::Aethokit::sponsor_instructions
+
+use sha2::{Digest, Sha256};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+/// Discriminator Anchor prefixes the data of instruction `name` with (`snake_case`, as in
+/// the program's source).
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{name}"));
+    hash[..8].try_into().expect("sha256 is 32 bytes")
+}
+
+/// Instruction calling `name` on the Anchor program `program_id`, with `args` the
+/// borsh-encoded instruction arguments and `accounts` in the order of its `Accounts` struct.
+pub fn instruction(program_id: Pubkey, name: &str, args: &[u8], accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = Vec::with_capacity(8 + args.len());
+    data.extend_from_slice(&discriminator(name));
+    data.extend_from_slice(args);
+    Instruction::new_with_bytes(program_id, &data, accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The data is Anchor's discriminator followed by the arguments.
+    #[test]
+    fn prefixes_discriminator() {
+        assert_eq!(discriminator("initialize"), [175, 175, 109, 31, 13, 152, 155, 237]);
+        let ix = instruction(Pubkey::new_unique(), "initialize", &[1, 2], vec![]);
+        assert_eq!(ix.data, [175, 175, 109, 31, 13, 152, 155, 237, 1, 2]);
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::dedup::DedupCache;
+use crate::{Aethokit, AethokitError, Responded, SponsoredTx};
+
+/// Receives an [`AuditRecord`] for every sponsorship attempt, see
+/// [`AethokitBuilder::audit_sink`](crate::AethokitBuilder::audit_sink).
+///
+/// Called inline before the attempt's result is returned, so keep it quick or hand the
+/// record off to a background writer.
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+/// Writes each record as a line of JSON, e.g. to a file opened in append mode.
+///
+/// Write errors are ignored so an audit outage doesn't fail sponsorships; wrap the writer
+/// to surface them.
+impl<W: Write + Send> AuditSink for Mutex<W> {
+    fn record(&self, record: &AuditRecord) {
+        let mut line = serde_json::to_vec(record).expect("audit records serialize");
+        line.push(b'\n');
+        let mut out = self.lock().unwrap();
+        let _ = out.write_all(&line).and_then(|()| out.flush());
+    }
+}
+
+/// One sponsorship attempt, with one record per gas key tried on failover.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// When the attempt finished
+    pub timestamp: DateTime<Utc>,
+    /// Path of the endpoint relative to the base URL, e.g. `sponsor-tx`
+    pub endpoint: String,
+    /// Hex SHA-256 of the transaction message, `None` if the transaction doesn't decode
+    pub message_hash: Option<String>,
+    pub result: AuditResult,
+    /// Short SHA-256 fingerprint of the gas key used, never the key itself
+    pub key_fingerprint: String,
+}
+
+/// Outcome of a sponsorship attempt in an [`AuditRecord`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum AuditResult {
+    Sponsored {
+        signature: String,
+    },
+    Failed {
+        /// See [`AethokitError::code`]
+        code: Option<&'static str>,
+        error: String,
+    },
+}
+
+/// Audit sink of a client, shareable between clones.
+#[derive(Clone)]
+pub(crate) struct SharedAuditSink(pub(crate) Arc<dyn AuditSink>);
+
+impl fmt::Debug for SharedAuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedAuditSink")
+    }
+}
+
+impl Aethokit {
+    /// Report a sponsorship attempt of `tx` to the audit sink, if there is one.
+    pub(crate) fn audit(
+        &self,
+        endpoint: &str,
+        tx: &str,
+        result: &Result<Responded<SponsoredTx>, AethokitError>,
+    ) {
+        let Some(sink) = &self.audit else {
+            return;
+        };
+        let result = match result {
+            Ok(resp) => AuditResult::Sponsored {
+                signature: resp.value.signature.clone(),
+            },
+            Err(err) => AuditResult::Failed {
+                code: err.code(),
+                error: err.to_string(),
+            },
+        };
+        sink.0.record(&AuditRecord {
+            timestamp: SystemTime::now().into(),
+            endpoint: endpoint.to_string(),
+            message_hash: DedupCache::key(tx).map(hex::encode),
+            result,
+            key_fingerprint: key_fingerprint(&self.gas_key.get()),
+        });
+    }
+}
+
+pub(crate) fn key_fingerprint(gas_key: &str) -> String {
+    let digest = Sha256::digest(gas_key.as_bytes());
+    format!("sha256:{}", &hex::encode(digest)[..12])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    use crate::test_util::{FakeBackend, FakeResponse, FAKE_GAS_KEY};
+
+    /// Each attempt is written as a JSON line with the outcome and without the gas key.
+    #[tokio::test]
+    async fn writes_audit_records() {
+        let backend = FakeBackend::new();
+        backend
+            .on(
+                "POST",
+                "sponsor-tx",
+                FakeResponse::json(402, json!({ "error": "empty" })),
+            )
+            .on(
+                "POST",
+                "sponsor-tx",
+                FakeResponse::json(200, json!({ "hash": "sig" })),
+            );
+        let log = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let client = backend.client().audit_sink(log.clone()).build().unwrap();
+
+        assert!(client.sponsor_tx("tx".to_string()).await.is_err());
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        let log = String::from_utf8(log.lock().unwrap().clone()).unwrap();
+        assert!(!log.contains(FAKE_GAS_KEY));
+        let records: Vec<Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["endpoint"], "sponsor-tx");
+        assert_eq!(records[0]["messageHash"], Value::Null);
+        assert_eq!(records[0]["keyFingerprint"], key_fingerprint(FAKE_GAS_KEY));
+        assert_eq!(records[0]["result"]["code"], "INSUFFICIENT_GAS_BALANCE");
+        assert_eq!(
+            records[1]["result"],
+            json!({ "status": "sponsored", "signature": "sig" })
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use url::Url;
+
+use crate::rt::Instant;
+use crate::{Aethokit, AethokitError};
+
+/// How long a base URL that refused connections is skipped.
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// The primary and fallback base URLs of a client and which are reachable, see
+/// [`AethokitBuilder::fallback_base_urls`](crate::AethokitBuilder::fallback_base_urls).
+#[derive(Debug)]
+pub(crate) struct BaseUrls {
+    /// Primary first, then fallbacks in priority order
+    urls: Vec<Url>,
+    /// Until when each URL is skipped after a connection error
+    down_until: Mutex<Vec<Option<Instant>>>,
+}
+
+impl BaseUrls {
+    pub(crate) fn new(urls: Vec<Url>) -> Self {
+        let down_until = Mutex::new(vec![None; urls.len()]);
+        Self { urls, down_until }
+    }
+
+    /// The highest priority URL not tried yet, preferring ones not cooling down; `None` once
+    /// all were tried.
+    fn pick(&self, tried: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let down_until = self.down_until.lock().unwrap();
+        let untried = || (0..self.urls.len()).filter(|index| !tried.contains(index));
+        untried()
+            .find(|&index| down_until[index].is_none_or(|until| until <= now))
+            .or_else(|| untried().next())
+    }
+
+    fn mark_down(&self, index: usize) {
+        self.down_until.lock().unwrap()[index] = Some(Instant::now() + COOLDOWN);
+    }
+
+    fn mark_up(&self, index: usize) {
+        self.down_until.lock().unwrap()[index] = None;
+    }
+}
+
+impl Aethokit {
+    /// `path` relative to whichever of the client's base URLs `url` is under.
+    pub(crate) fn relative_path(&self, url: &Url) -> String {
+        let bases = self
+            .base_urls
+            .as_ref()
+            .map_or(&[][..], |urls| &urls.urls[..]);
+        std::iter::once(&self.base_url)
+            .chain(bases)
+            .find_map(|base| {
+                url.path()
+                    .strip_prefix(base.path())
+                    .filter(|_| url.origin() == base.origin())
+            })
+            .unwrap_or(url.path())
+            .to_string()
+    }
+
+    /// Run `send` against the preferred base URL, moving on to the next while connections
+    /// are refused. Connection errors mean nothing was sent, so this is safe for any method.
+    ///
+    /// The index of the URL that answered is recorded in the result's
+    /// [`ResponseMeta::base_url_index`](crate::ResponseMeta::base_url_index).
+    pub(crate) async fn with_base_url_failover<T, F, Fut>(
+        &self,
+        url: Url,
+        send: F,
+    ) -> Result<crate::Responded<T>, AethokitError>
+    where
+        F: Fn(Url) -> Fut,
+        Fut: std::future::Future<Output = Result<crate::Responded<T>, AethokitError>>,
+    {
+        let Some(urls) = &self.base_urls else {
+            return send(url).await;
+        };
+        let relative = url
+            .as_str()
+            .strip_prefix(self.base_url.as_str())
+            .ok_or(AethokitError::Internal("request URL is not under the base URL"))?;
+        let mut tried = Vec::new();
+        loop {
+            let index = urls
+                .pick(&tried)
+                .ok_or(AethokitError::Internal("every base URL was tried"))?;
+            tried.push(index);
+            let url = urls.urls[index]
+                .join(relative)
+                .map_err(|_| AethokitError::InvalidUrl(relative.to_string()))?;
+            match send(url).await {
+                Err(err) if is_connect_error(&err) => {
+                    urls.mark_down(index);
+                    if tried.len() == urls.urls.len() {
+                        return Err(err);
+                    }
+                }
+                result => {
+                    urls.mark_up(index);
+                    return result.map(|mut resp| {
+                        resp.meta.base_url_index = index;
+                        resp
+                    });
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn is_connect_error(err: &AethokitError) -> bool {
+    matches!(err.inner(), AethokitError::Http(err) if err.is_connect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requests move to the fallback while the primary refuses connections, and stay there
+    /// until its cooldown is over.
+    #[tokio::test(start_paused = true)]
+    async fn fails_over_to_reachable_base_url() {
+        // nothing listens on port 1, so connections are refused
+        let unreachable = "http://127.0.0.1:1/api/";
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let fallback = format!("http://{}/v1/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 4096]).unwrap();
+            let body = r#"{"gasAddress":"tank"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url(unreachable)
+            .fallback_base_urls([fallback])
+            .build()
+            .unwrap();
+
+        let resp = client.get_gas_address_with_meta().await.unwrap();
+        assert_eq!((resp.value.as_str(), resp.meta.base_url_index), ("tank", 1));
+        let urls = client.base_urls.as_ref().unwrap();
+        assert_eq!(urls.pick(&[]), Some(1));
+        tokio::time::advance(COOLDOWN).await;
+        assert_eq!(urls.pick(&[]), Some(0));
+    }
+}
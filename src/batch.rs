@@ -0,0 +1,139 @@
+use std::fmt;
+
+/// A transaction the planner has assigned to a batch, along with its estimated cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedTx {
+    /// Position of the transaction in the input batch
+    pub index: usize,
+    /// Serialized transaction string
+    pub transaction: String,
+    /// Estimated fee the gas tank would spend, in lamports
+    pub estimated_fee_lamports: u64,
+}
+
+/// Result of [`plan_batch`]: which transactions fit in the tank's balance and which must wait.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchPlan {
+    /// Transactions that can be sponsored with the current balance, in input order
+    pub sponsored: Vec<PlannedTx>,
+    /// Transactions that would exceed the remaining balance, in input order
+    pub deferred: Vec<PlannedTx>,
+    /// Tank balance the plan was computed against, in lamports
+    pub balance_lamports: u64,
+    /// Estimated total spend for the sponsored transactions, in lamports
+    pub estimated_total_lamports: u64,
+}
+
+impl BatchPlan {
+    /// Balance expected to remain in the tank after the sponsored transactions land, or
+    /// zero if the plan has been edited to spend more than the balance.
+    pub fn remaining_lamports(&self) -> u64 {
+        self.balance_lamports.saturating_sub(self.estimated_total_lamports)
+    }
+
+    /// Estimated spend required to also sponsor the deferred transactions.
+    pub fn deferred_lamports(&self) -> u64 {
+        self.deferred
+            .iter()
+            .fold(0, |total, tx| total.saturating_add(tx.estimated_fee_lamports))
+    }
+
+    /// `true` when every transaction in the batch fits in the current balance.
+    pub fn is_fully_funded(&self) -> bool {
+        self.deferred.is_empty()
+    }
+}
+
+impl fmt::Display for BatchPlan {
+    /// One-line summary, e.g. `2 sponsored (8000 lamports), 1 deferred (10000 lamports), 1000 of 9000 lamports left`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sponsored ({} lamports), {} deferred ({} lamports), {} of {} lamports left",
+            self.sponsored.len(),
+            self.estimated_total_lamports,
+            self.deferred.len(),
+            self.deferred_lamports(),
+            self.remaining_lamports(),
+            self.balance_lamports,
+        )
+    }
+}
+
+/// Plan a batch of sponsorships against the current gas tank balance.
+///
+/// Takes `(serialized_tx, estimated_fee_lamports)` pairs and walks them in order,
+/// sponsoring each one whose fee still fits in the remaining balance and deferring
+/// the rest. No requests are made; the returned plan can be reviewed before
+/// any transaction is submitted.
+pub fn plan_batch<I>(txs: I, balance_lamports: u64) -> BatchPlan
+where
+    I: IntoIterator<Item = (String, u64)>,
+{
+    let mut sponsored = Vec::new();
+    let mut deferred = Vec::new();
+    let mut remaining = balance_lamports;
+
+    for (index, (transaction, fee)) in txs.into_iter().enumerate() {
+        let planned = PlannedTx {
+            index,
+            transaction,
+            estimated_fee_lamports: fee,
+        };
+        if fee <= remaining {
+            remaining -= fee;
+            sponsored.push(planned);
+        } else {
+            deferred.push(planned);
+        }
+    }
+
+    BatchPlan {
+        sponsored,
+        deferred,
+        balance_lamports,
+        estimated_total_lamports: balance_lamports - remaining,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transactions that no longer fit are deferred while cheaper later ones are still sponsored.
+    #[test]
+    fn defers_transactions_over_budget() {
+        let txs = vec![
+            ("a".to_string(), 5_000),
+            ("b".to_string(), 10_000),
+            ("c".to_string(), 3_000),
+        ];
+        let plan = plan_batch(txs, 9_000);
+
+        let sponsored: Vec<usize> = plan.sponsored.iter().map(|tx| tx.index).collect();
+        let deferred: Vec<usize> = plan.deferred.iter().map(|tx| tx.index).collect();
+        assert_eq!(sponsored, vec![0, 2]);
+        assert_eq!(deferred, vec![1]);
+        assert_eq!(plan.estimated_total_lamports, 8_000);
+        assert_eq!(plan.remaining_lamports(), 1_000);
+        assert_eq!(plan.deferred_lamports(), 10_000);
+        assert!(!plan.is_fully_funded());
+        assert_eq!(
+            plan.to_string(),
+            "2 sponsored (8000 lamports), 1 deferred (10000 lamports), 1000 of 9000 lamports left"
+        );
+    }
+
+    /// A plan edited to spend more than its balance saturates instead of overflowing.
+    #[test]
+    fn saturates_edited_plans() {
+        let mut plan = plan_batch(vec![("a".to_string(), u64::MAX), ("b".to_string(), 1)], 0);
+        plan.estimated_total_lamports = 5_000;
+        assert_eq!(plan.remaining_lamports(), 0);
+        assert_eq!(plan.deferred_lamports(), u64::MAX);
+        assert_eq!(
+            plan.to_string(),
+            format!("0 sponsored (5000 lamports), 2 deferred ({} lamports), 0 of 0 lamports left", u64::MAX)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::retry::duration_ms;
+use crate::rt::Instant;
+use crate::{Aethokit, AethokitError};
+
+/// Fail requests fast while the backend looks down, see
+/// [`AethokitBuilder::circuit_breaker`](crate::AethokitBuilder::circuit_breaker).
+///
+/// After `failure_threshold` outage failures in a row (connection errors, timeouts and 5xx
+/// answers) the circuit opens and requests fail with `CircuitOpen` without being sent. Once
+/// `open_duration` has passed, up to `half_open_probes` requests are let through; the first
+/// to succeed closes the circuit, a failure opens it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreaker {
+    pub failure_threshold: u32,
+    #[serde(rename = "open_duration_ms", with = "duration_ms")]
+    pub open_duration: Duration,
+    pub half_open_probes: u32,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 1,
+        }
+    }
+}
+
+/// State of a client's circuit breaker, from [`Aethokit::circuit_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent
+    Closed,
+    /// Requests fail with `CircuitOpen`
+    Open,
+    /// Probe requests are sent to find out whether the backend is back
+    HalfOpen,
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probes: u32 },
+}
+
+/// The state of a [`CircuitBreaker`], shared between clones of a client.
+#[derive(Debug)]
+pub(crate) struct Breaker {
+    config: CircuitBreaker,
+    state: Mutex<State>,
+}
+
+/// Permission to send one request, to be settled with [`Breaker::record`].
+///
+/// Dropping it unsettled, e.g. when the request is cancelled, frees its probe slot.
+pub(crate) struct Admission<'a> {
+    breaker: &'a Breaker,
+    probe: bool,
+}
+
+impl Breaker {
+    pub(crate) fn new(config: CircuitBreaker) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Let a request through, or fail with `CircuitOpen`.
+    pub(crate) fn admit(&self) -> Result<Admission<'_>, AethokitError> {
+        let mut state = self.state.lock().unwrap();
+        if let State::Open { until } = *state {
+            let now = Instant::now();
+            if now < until {
+                return Err(AethokitError::CircuitOpen {
+                    retry_after: until - now,
+                });
+            }
+            *state = State::HalfOpen { probes: 0 };
+        }
+        let probe = match &mut *state {
+            State::HalfOpen { probes } if *probes >= self.config.half_open_probes.max(1) => {
+                return Err(AethokitError::CircuitOpen {
+                    retry_after: Duration::ZERO,
+                });
+            }
+            State::HalfOpen { probes } => {
+                *probes += 1;
+                true
+            }
+            _ => false,
+        };
+        Ok(Admission {
+            breaker: self,
+            probe,
+        })
+    }
+
+    /// Settle `admission` with the outcome of its request.
+    pub(crate) fn record<T>(
+        &self,
+        mut admission: Admission<'_>,
+        result: &Result<T, AethokitError>,
+    ) {
+        let probe = std::mem::take(&mut admission.probe);
+        let outage = result.as_ref().err().is_some_and(is_outage);
+        let open = || State::Open {
+            until: Instant::now() + self.config.open_duration,
+        };
+        let mut state = self.state.lock().unwrap();
+        match (&*state, outage) {
+            (State::Closed { failures }, true) => {
+                let failures = failures + 1;
+                *state = if failures >= self.config.failure_threshold {
+                    open()
+                } else {
+                    State::Closed { failures }
+                };
+            }
+            (State::Closed { .. }, false) => *state = State::Closed { failures: 0 },
+            (State::HalfOpen { .. }, true) if probe => *state = open(),
+            (State::HalfOpen { .. }, false) if probe => *state = State::Closed { failures: 0 },
+            // a request admitted before the circuit opened doesn't change it
+            _ => {}
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        match *self.state.lock().unwrap() {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } if Instant::now() < until => CircuitState::Open,
+            State::Open { .. } | State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl Drop for Admission<'_> {
+    fn drop(&mut self) {
+        if let State::HalfOpen { probes } = &mut *self.breaker.state.lock().unwrap() {
+            if self.probe {
+                *probes = probes.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// Whether `err` suggests the backend is down rather than rejecting this request.
+fn is_outage(err: &AethokitError) -> bool {
+    match err.inner() {
+        AethokitError::Http(err) => err.is_connect() || err.is_timeout(),
+        AethokitError::UnexpectedStatus { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
+impl Aethokit {
+    /// State of the client's circuit breaker, `None` without one.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.breaker.as_ref().map(|breaker| breaker.state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Consecutive 5xx answers open the circuit; after the open duration a successful probe
+    /// closes it again.
+    #[tokio::test(start_paused = true)]
+    async fn opens_and_recovers() {
+        let backend = FakeBackend::new();
+        for _ in 0..2 {
+            backend.on("GET", "get-gas-address", FakeResponse::json(503, json!({})));
+        }
+        backend.on(
+            "GET",
+            "get-gas-address",
+            FakeResponse::json(200, json!({ "gasAddress": "tank" })),
+        );
+        let client = backend
+            .client()
+            .circuit_breaker(CircuitBreaker {
+                failure_threshold: 2,
+                ..CircuitBreaker::default()
+            })
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            assert!(client.refresh_gas_address().await.is_err());
+        }
+        assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+        match client.refresh_gas_address().await {
+            Err(AethokitError::CircuitOpen { retry_after }) => {
+                assert!(retry_after > Duration::ZERO)
+            }
+            other => panic!("expected CircuitOpen, got {other:?}"),
+        }
+        assert_eq!(backend.requests().len(), 2);
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+        assert_eq!(client.refresh_gas_address().await.unwrap(), "tank");
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    }
+}
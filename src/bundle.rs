@@ -0,0 +1,89 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{Aethokit, AethokitError, BundleId};
+
+/// Most transactions the backend accepts in one bundle, the Jito block engine's limit.
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
+/// An atomically sponsored bundle, from [`Aethokit::sponsor_bundle`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SponsoredBundle {
+    /// ID of the bundle at the block engine, to look up whether it landed
+    #[serde(rename = "bundleId")]
+    pub bundle_id: BundleId,
+    /// Transaction hash (signature) of each transaction, in bundle order
+    pub signatures: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BundleRequest<'a> {
+    transactions: &'a [String],
+}
+
+impl Aethokit {
+    /// Sponsor `txs` as one bundle that lands in order in a single block, or not at all, e.g.
+    /// a setup transaction and the trade that depends on it.
+    ///
+    /// Each transaction is checked like one passed to [`sponsor_tx`](Self::sponsor_tx)
+    /// before the bundle is sent. Fallback gas keys are not tried, since a bundle that was
+    /// partly paid for can't move to another tank.
+    ///
+    /// # Errors
+    /// - `InvalidBundle` if `txs` is empty or holds more than [`MAX_BUNDLE_SIZE`] transactions
+    /// - `InvalidBundle` if the backend answers with a signature count that doesn't match
+    pub async fn sponsor_bundle(&self, txs: Vec<String>) -> Result<SponsoredBundle, AethokitError> {
+        if txs.is_empty() || txs.len() > MAX_BUNDLE_SIZE {
+            return Err(AethokitError::InvalidBundle(format!(
+                "a bundle holds 1 to {MAX_BUNDLE_SIZE} transactions, got {}",
+                txs.len()
+            )));
+        }
+        for tx in &txs {
+            self.precheck(tx)?;
+            self.enforce_key_policy(tx).await?;
+        }
+        let bundle: SponsoredBundle = self
+            .make_request("sponsor-bundle", Method::POST, Some(&BundleRequest { transactions: &txs }))
+            .await?;
+        if bundle.signatures.len() != txs.len() {
+            return Err(AethokitError::InvalidBundle(format!(
+                "sent {} transactions, got {} signatures back",
+                txs.len(),
+                bundle.signatures.len()
+            )));
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// The bundle is sent in one request and oversized bundles are refused up front.
+    #[tokio::test]
+    async fn sponsors_bundle() {
+        let backend = FakeBackend::new();
+        let body = json!({ "bundleId": "b-1", "signatures": ["sig-a", "sig-b"] });
+        backend.on("POST", "sponsor-bundle", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let bundle = client
+            .sponsor_bundle(vec!["a".to_string(), "b".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(bundle.bundle_id.as_str(), "b-1");
+        assert_eq!(bundle.signatures, ["sig-a", "sig-b"]);
+        assert_eq!(backend.requests()[0].body, Some(json!({ "transactions": ["a", "b"] })));
+
+        let err = client.sponsor_bundle(vec!["a".to_string(); 6]).await.unwrap_err();
+        assert!(matches!(err, AethokitError::InvalidBundle(_)), "{err}");
+        assert!(client.sponsor_bundle(vec!["a".to_string()]).await.is_err());
+        assert_eq!(backend.requests().len(), 2);
+    }
+}
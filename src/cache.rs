@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures_util::future::BoxFuture;
+use sha2::{Digest, Sha256};
+
+use crate::clock::Time;
+use crate::Aethokit;
+
+/// Stores the gas address and key policy between requests, see
+/// [`AethokitBuilder::cache_store`](crate::AethokitBuilder::cache_store).
+///
+/// Implement it over a shared store such as Redis (`GET`, and `SET` with `PX`) so every
+/// replica of a service reuses one lookup instead of each fetching its own. Keys are
+/// namespaced by a hash of the gas key, so one store can serve several keys. A store that
+/// can't be reached should report a miss and drop writes rather than fail the request.
+///
+/// The default is a [`MemoryCache`] per client, shared by its clones.
+pub trait CacheStore: Send + Sync {
+    /// The value stored under `key`, if it hasn't expired.
+    fn get(&self, key: &str) -> BoxFuture<'_, Option<String>>;
+
+    /// Store `value` under `key` for `ttl`.
+    fn set(&self, key: &str, value: String, ttl: Duration) -> BoxFuture<'_, ()>;
+}
+
+/// In-process [`CacheStore`], the default.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    /// Value and expiry of each key
+    entries: Mutex<HashMap<String, (String, Option<Instant>)>>,
+    time: Time,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache expiring entries by the client's clock.
+    pub(crate) fn with_time(time: Time) -> Self {
+        Self {
+            entries: Mutex::default(),
+            time,
+        }
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> BoxFuture<'_, Option<String>> {
+        let now = self.time.now();
+        let mut entries = self.entries.lock().unwrap();
+        let value = match entries.get(key) {
+            Some((_, Some(expires))) if *expires <= now => {
+                entries.remove(key);
+                None
+            }
+            entry => entry.map(|(value, _)| value.clone()),
+        };
+        Box::pin(futures_util::future::ready(value))
+    }
+
+    fn set(&self, key: &str, value: String, ttl: Duration) -> BoxFuture<'_, ()> {
+        // a TTL past the end of time never expires
+        let expires = self.time.now().checked_add(ttl);
+        self.entries.lock().unwrap().insert(key.to_string(), (value, expires));
+        Box::pin(futures_util::future::ready(()))
+    }
+}
+
+/// Cache store of a client, shareable between clones.
+#[derive(Clone)]
+pub(crate) struct SharedCacheStore(pub(crate) Arc<dyn CacheStore>);
+
+impl fmt::Debug for SharedCacheStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedCacheStore")
+    }
+}
+
+impl Aethokit {
+    /// Key of cached `kind` data for the client's gas key, `None` while a key provider hasn't
+    /// supplied the key yet.
+    pub(crate) fn cache_key(&self, kind: &str) -> Option<String> {
+        let gas_key = self.gas_key.get();
+        if gas_key.is_empty() {
+            return None;
+        }
+        let digest = Sha256::digest(gas_key.as_bytes());
+        Some(format!("aethokit:{kind}:{}", &hex::encode(digest)[..32]))
+    }
+
+    pub(crate) async fn cache_get(&self, kind: &str) -> Option<String> {
+        self.cache.0.get(&self.cache_key(kind)?).await
+    }
+
+    pub(crate) async fn cache_set(&self, kind: &str, value: String, ttl: Duration) {
+        if let (Some(key), false) = (self.cache_key(kind), ttl.is_zero()) {
+            self.cache.0.set(&key, value, ttl).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::MockClock;
+
+    /// Entries are served until their TTL runs out on the cache's clock.
+    #[tokio::test]
+    async fn expires_entries() {
+        let clock = Arc::new(MockClock::new());
+        let time = Time {
+            clock: clock.clone(),
+            sleeper: clock.clone(),
+        };
+        let cache = MemoryCache::with_time(time);
+        cache.set("a", "1".to_string(), Duration::from_secs(60)).await;
+        cache.set("b", "2".to_string(), Duration::MAX).await;
+        assert_eq!(cache.get("a").await.as_deref(), Some("1"));
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(cache.get("a").await, None);
+        assert_eq!(cache.get("b").await.as_deref(), Some("2"));
+    }
+}
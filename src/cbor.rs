@@ -0,0 +1,153 @@
+//! CBOR (RFC 8949) request bodies for sponsorships, see [`AethokitBuilder::cbor_requests`](crate::AethokitBuilder::cbor_requests).
+//!
+//! The request is encoded from its JSON form, with the transaction sent as raw bytes instead
+//! of base64 text, which saves about a quarter of the payload.
+
+use std::sync::atomic::Ordering;
+
+use base64::Engine as _;
+use bytes::Bytes;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Aethokit, AethokitError, Responded, SponsorTxRequest, SponsoredTx};
+
+pub(crate) const CONTENT_TYPE: &str = "application/cbor";
+
+/// `request` as CBOR, with its base64 `transaction` field as a byte string. `None` if the
+/// transaction is not valid base64, so the request goes out as JSON and the backend reports
+/// the error as usual.
+pub(crate) fn encode_sponsor_request(request: &impl Serialize) -> Option<Bytes> {
+    let mut value = serde_json::to_value(request).ok()?;
+    let transaction = value.as_object_mut()?.remove("transaction")?;
+    let transaction = base64::engine::general_purpose::STANDARD
+        .decode(transaction.as_str()?)
+        .ok()?;
+    let fields = value.as_object()?;
+
+    let mut out = Vec::with_capacity(transaction.len() + 64);
+    head(&mut out, 5, fields.len() as u64 + 1);
+    text(&mut out, "transaction");
+    head(&mut out, 2, transaction.len() as u64);
+    out.extend_from_slice(&transaction);
+    for (key, field) in fields {
+        text(&mut out, key);
+        encode(&mut out, field);
+    }
+    Some(out.into())
+}
+
+impl Aethokit {
+    /// Send `request` to `path` as CBOR if the client is set to, `None` if it should go
+    /// as JSON instead.
+    pub(crate) async fn send_cbor(
+        &self,
+        path: &str,
+        request: &SponsorTxRequest,
+    ) -> Option<Result<Responded<SponsoredTx>, AethokitError>> {
+        let rejected = self.cbor.as_ref().filter(|flag| !flag.load(Ordering::Relaxed))?;
+        let body = encode_sponsor_request(request)?;
+        match self.send_body(path, Method::POST, Some(body), CONTENT_TYPE).await {
+            Err(err) if err.status() == Some(StatusCode::UNSUPPORTED_MEDIA_TYPE) => {
+                rejected.store(true, Ordering::Relaxed);
+                None
+            }
+            result => Some(result),
+        }
+    }
+}
+
+fn encode(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                head(out, 0, n);
+            } else if let Some(n) = n.as_i64() {
+                // negative integers are encoded as -1 - n
+                head(out, 1, !(n as u64));
+            } else {
+                out.push(0xfb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+            }
+        }
+        Value::String(s) => text(out, s),
+        Value::Array(items) => {
+            head(out, 4, items.len() as u64);
+            items.iter().for_each(|item| encode(out, item));
+        }
+        Value::Object(fields) => {
+            head(out, 5, fields.len() as u64);
+            for (key, field) in fields {
+                text(out, key);
+                encode(out, field);
+            }
+        }
+    }
+}
+
+fn text(out: &mut Vec<u8>, s: &str) {
+    head(out, 3, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Initial byte of major type `major` with argument `value`, in the shortest form.
+fn head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// The transaction becomes a byte string and other fields keep their JSON names and types.
+    #[test]
+    fn encodes_sponsor_request() {
+        let request = json!({ "transaction": "AQID", "skipPreflight": true, "maxRetries": 300 });
+        let encoded = encode_sponsor_request(&request).unwrap();
+        let mut expected = vec![0xa3];
+        expected.extend_from_slice(b"\x6btransaction\x43\x01\x02\x03");
+        expected.extend_from_slice(b"\x6amaxRetries\x19\x01\x2c");
+        expected.extend_from_slice(b"\x6dskipPreflight\xf5");
+        assert_eq!(encoded.as_ref(), expected.as_slice());
+
+        assert!(encode_sponsor_request(&json!({ "transaction": "not base64!" })).is_none());
+    }
+
+    /// A backend without CBOR support gets the request again as JSON, and only JSON after that.
+    #[tokio::test]
+    async fn falls_back_to_json() {
+        let backend = FakeBackend::new();
+        backend.on("POST", "sponsor-tx", FakeResponse::json(415, json!({ "error": "unsupported" })));
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        let client = backend.client().cbor_requests(true).build().unwrap();
+
+        assert_eq!(client.sponsor_tx("AQID".to_string()).await.unwrap(), "sig");
+        assert_eq!(client.sponsor_tx("AQID".to_string()).await.unwrap(), "sig");
+        let bodies: Vec<_> = backend.requests().into_iter().map(|request| request.body).collect();
+        let json = Some(json!({ "transaction": "AQID" }));
+        assert_eq!(bodies, [None, json.clone(), json]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::{Method, StatusCode};
+
+use crate::AethokitError;
+
+/// A finished API request, as seen by a [`Classifier`].
+#[derive(Debug, Clone, Copy)]
+pub struct RequestOutcome<'a> {
+    pub method: &'a Method,
+    /// Path relative to the base URL, without the query string
+    pub endpoint: &'a str,
+    /// Response status, `None` if no response was received
+    pub status: Option<StatusCode>,
+    /// The request's error, `None` if it succeeded
+    pub error: Option<&'a AethokitError>,
+}
+
+/// Maps request outcomes to the `class` label of the client's metrics and `tracing`
+/// events, so failures can be bucketed to match an SRE team's alerting rules.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use aethokit::{Classifier, DefaultClassifier, RequestOutcome};
+///
+/// /// Counts running out of gas separately from other client errors.
+/// struct GasAware;
+///
+/// impl Classifier for GasAware {
+///     fn classify(&self, outcome: &RequestOutcome<'_>) -> Cow<'static, str> {
+///         match outcome.status.map(|status| status.as_u16()) {
+///             Some(402) => "out_of_gas".into(),
+///             _ => DefaultClassifier.classify(outcome),
+///         }
+///     }
+/// }
+/// ```
+pub trait Classifier: Send + Sync {
+    fn classify(&self, outcome: &RequestOutcome<'_>) -> Cow<'static, str>;
+}
+
+/// Classes used unless the builder sets a [`Classifier`]: `success`, `rate_limited`,
+/// `user_error` (other 4xx), `server_error` (5xx), `network` (no response),
+/// `cancelled` (deadline or cancellation), `invalid_response` (unparseable body) and
+/// `sdk_error` for anything else.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultClassifier;
+
+impl Classifier for DefaultClassifier {
+    fn classify(&self, outcome: &RequestOutcome<'_>) -> Cow<'static, str> {
+        let Some(error) = outcome.error else {
+            return "success".into();
+        };
+        let class = match (outcome.status, error) {
+            (Some(StatusCode::TOO_MANY_REQUESTS), _) => "rate_limited",
+            (Some(status), _) if status.is_client_error() => "user_error",
+            (Some(status), _) if status.is_server_error() => "server_error",
+            (_, AethokitError::DeadlineExceeded | AethokitError::Cancelled) => "cancelled",
+            (_, AethokitError::Http(_)) => "network",
+            (_, AethokitError::Serde(_) | AethokitError::UnknownFields(_)) => "invalid_response",
+            _ => "sdk_error",
+        };
+        class.into()
+    }
+}
+
+/// Classifier of a client, shareable between clones.
+#[derive(Clone)]
+pub(crate) struct SharedClassifier(pub(crate) Arc<dyn Classifier>);
+
+impl Default for SharedClassifier {
+    fn default() -> Self {
+        Self(Arc::new(DefaultClassifier))
+    }
+}
+
+impl fmt::Debug for SharedClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClassifier")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Statuses take precedence over the error kind.
+    #[test]
+    fn buckets_outcomes() {
+        let classify = |status: Option<u16>, error: Option<&AethokitError>| {
+            DefaultClassifier.classify(&RequestOutcome {
+                method: &Method::GET,
+                endpoint: "get-gas-address",
+                status: status.map(|s| StatusCode::from_u16(s).unwrap()),
+                error,
+            })
+        };
+        let rejected = AethokitError::MissingGasKey;
+        assert_eq!(classify(Some(200), None), "success");
+        assert_eq!(classify(Some(429), Some(&rejected)), "rate_limited");
+        assert_eq!(classify(Some(401), Some(&rejected)), "user_error");
+        assert_eq!(classify(Some(503), Some(&rejected)), "server_error");
+        assert_eq!(classify(None, Some(&AethokitError::DeadlineExceeded)), "cancelled");
+        assert_eq!(classify(None, Some(&rejected)), "sdk_error");
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::BoxFuture;
+
+use crate::rt;
+
+/// Source of the current time for retries, polling, cache expiry and rate limiting, see
+/// [`AethokitBuilder::clock`](crate::AethokitBuilder::clock).
+///
+/// Deadlines, circuit breaker cooldowns and base URL failover use the runtime's clock.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Waits between retries, polls and rate-limited requests, see
+/// [`AethokitBuilder::sleeper`](crate::AethokitBuilder::sleeper).
+pub trait Sleeper: Send + Sync {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The async runtime's clock and timers, the default.
+///
+/// With the `tokio-runtime` feature this is tokio's clock, so `tokio::time::pause` applies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        rt::now()
+    }
+}
+
+impl Sleeper for SystemClock {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(rt::sleep(duration))
+    }
+}
+
+/// Clock and sleeper of a client, shareable between clones.
+#[derive(Clone)]
+pub(crate) struct Time {
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) sleeper: Arc<dyn Sleeper>,
+}
+
+impl Time {
+    pub(crate) fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    pub(crate) async fn sleep(&self, duration: Duration) {
+        self.sleeper.sleep(duration).await
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            sleeper: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl fmt::Debug for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Time")
+    }
+}
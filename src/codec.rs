@@ -0,0 +1,450 @@
+//! Minimal Solana transaction wire codec, independent of `solana-sdk`.
+//!
+//! Decodes and encodes legacy and v0 transactions so they can be inspected and adjusted
+//! (fee payer, signature slots, size) without the `solana` feature or a matching
+//! `solana-sdk` version.
+
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine as _;
+
+/// Largest serialized transaction the cluster accepts, in bytes.
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// A 32-byte account address.
+pub type Address = [u8; 32];
+/// A 64-byte ed25519 signature; all zeros for a slot that is not signed yet.
+pub type SignatureBytes = [u8; 64];
+
+/// Why a transaction could not be decoded or does not pass a check.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CodecError {
+    #[error("transaction ended unexpectedly")]
+    UnexpectedEnd,
+    #[error("invalid compact length")]
+    InvalidLength,
+    #[error("unsupported message version {0}")]
+    UnsupportedVersion(u8),
+    #[error("{0} unexpected bytes after the transaction")]
+    TrailingBytes(usize),
+    #[error("transaction is {size} bytes, over the {PACKET_DATA_SIZE} byte limit")]
+    TooLarge { size: usize },
+    #[error("message requires {expected} signatures but the transaction has {found} slots")]
+    SignatureCount { expected: usize, found: usize },
+    #[error("account is already in the message")]
+    DuplicateAccount,
+    #[error("invalid base64: {0}")]
+    Base64(String),
+    #[error("invalid base58: {0}")]
+    Base58(String),
+}
+
+/// Base64 alphabet of an encoded transaction, see [`encode_transaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Base64Alphabet {
+    /// `+` and `/`, the format `sponsor_tx` expects
+    #[default]
+    Standard,
+    /// `-` and `_`, for transactions passed in URLs or file names
+    UrlSafe,
+}
+
+/// Encode a serialized transaction as padded base64 in `alphabet`.
+pub fn encode_transaction(bytes: &[u8], alphabet: Base64Alphabet) -> String {
+    match alphabet {
+        Base64Alphabet::Standard => base64::engine::general_purpose::STANDARD.encode(bytes),
+        Base64Alphabet::UrlSafe => base64::engine::general_purpose::URL_SAFE.encode(bytes),
+    }
+}
+
+/// Decode a base64 transaction in either alphabet, with or without padding, into its wire
+/// format, checking that it decodes as a transaction.
+pub fn decode_transaction(encoded: &str) -> Result<Vec<u8>, CodecError> {
+    let encoded = encoded.trim();
+    // strings without `+/-_` read the same in both alphabets
+    let alphabet = if encoded.contains(['-', '_']) {
+        &alphabet::URL_SAFE
+    } else {
+        &alphabet::STANDARD
+    };
+    let config = GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    let bytes = GeneralPurpose::new(alphabet, config)
+        .decode(encoded)
+        .map_err(|err| CodecError::Base64(err.to_string()))?;
+    RawTransaction::decode(&bytes)?;
+    Ok(bytes)
+}
+
+/// Message format version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageVersion {
+    Legacy,
+    V0,
+}
+
+/// Counts of signed and read-only accounts, which come first in the account list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// An instruction referring to accounts by index into the message's account list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Accounts loaded from an address lookup table (v0 messages only).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressTableLookup {
+    pub account_key: Address,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// The signed part of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawMessage {
+    pub version: MessageVersion,
+    pub header: MessageHeader,
+    pub account_keys: Vec<Address>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+    /// Always empty for legacy messages
+    pub address_table_lookups: Vec<AddressTableLookup>,
+}
+
+/// A decoded transaction: signature slots followed by the message they sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawTransaction {
+    pub signatures: Vec<SignatureBytes>,
+    pub message: RawMessage,
+}
+
+impl RawTransaction {
+    /// Decode a transaction from its wire format.
+    pub fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut reader = Reader { bytes };
+        let signatures = reader.vec(|r| r.array())?;
+        let message = RawMessage::read(&mut reader)?;
+        if !reader.bytes.is_empty() {
+            return Err(CodecError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(Self { signatures, message })
+    }
+
+    /// Decode a base64-encoded transaction, as accepted by `sponsor_tx`.
+    pub fn from_base64(encoded: &str) -> Result<Self, CodecError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|err| CodecError::Base64(err.to_string()))?;
+        Self::decode(&bytes)
+    }
+
+    /// Encode the transaction into its wire format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_len(&mut out, self.signatures.len());
+        for signature in &self.signatures {
+            out.extend_from_slice(signature);
+        }
+        out.extend(self.message.encode());
+        out
+    }
+
+    /// Encode the transaction as base64, as accepted by `sponsor_tx`.
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.encode())
+    }
+
+    /// The account paying the fee, the first account of the message.
+    pub fn fee_payer(&self) -> Option<&Address> {
+        self.message.account_keys.first()
+    }
+
+    /// Make `fee_payer` pay the fee in place of the current fee payer.
+    ///
+    /// This changes the message, so every signature slot is cleared and the transaction
+    /// must be signed again.
+    ///
+    /// # Errors
+    /// - `DuplicateAccount` if `fee_payer` is already another account of the message
+    pub fn set_fee_payer(&mut self, fee_payer: Address) -> Result<(), CodecError> {
+        let keys = &mut self.message.account_keys;
+        if keys.iter().skip(1).any(|key| *key == fee_payer) {
+            return Err(CodecError::DuplicateAccount);
+        }
+        match keys.first_mut() {
+            Some(first) => *first = fee_payer,
+            None => return Err(CodecError::UnexpectedEnd),
+        }
+        self.signatures = vec![[0; 64]; usize::from(self.message.header.num_required_signatures)];
+        Ok(())
+    }
+
+    /// Indexes of signature slots that are not signed yet.
+    pub fn unsigned_slots(&self) -> Vec<usize> {
+        self.signatures
+            .iter()
+            .enumerate()
+            .filter(|(_, signature)| signature.iter().all(|&b| b == 0))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Check the transaction fits in a packet and has a slot for each required signature.
+    pub fn check(&self) -> Result<(), CodecError> {
+        let expected = usize::from(self.message.header.num_required_signatures);
+        if self.signatures.len() != expected {
+            return Err(CodecError::SignatureCount {
+                expected,
+                found: self.signatures.len(),
+            });
+        }
+        let size = self.encode().len();
+        if size > PACKET_DATA_SIZE {
+            return Err(CodecError::TooLarge { size });
+        }
+        Ok(())
+    }
+}
+
+/// Version prefix bit set on versioned messages.
+const VERSION_PREFIX: u8 = 0x80;
+
+impl RawMessage {
+    fn read(reader: &mut Reader<'_>) -> Result<Self, CodecError> {
+        let first = reader.byte()?;
+        let (version, num_required_signatures) = if first & VERSION_PREFIX != 0 {
+            match first & !VERSION_PREFIX {
+                0 => (MessageVersion::V0, reader.byte()?),
+                version => return Err(CodecError::UnsupportedVersion(version)),
+            }
+        } else {
+            (MessageVersion::Legacy, first)
+        };
+        let header = MessageHeader {
+            num_required_signatures,
+            num_readonly_signed_accounts: reader.byte()?,
+            num_readonly_unsigned_accounts: reader.byte()?,
+        };
+        let account_keys = reader.vec(|r| r.array())?;
+        let recent_blockhash = reader.array()?;
+        let instructions = reader.vec(|r| {
+            Ok(CompiledInstruction {
+                program_id_index: r.byte()?,
+                accounts: r.vec(|r| r.byte())?,
+                data: r.vec(|r| r.byte())?,
+            })
+        })?;
+        let address_table_lookups = match version {
+            MessageVersion::Legacy => Vec::new(),
+            MessageVersion::V0 => reader.vec(|r| {
+                Ok(AddressTableLookup {
+                    account_key: r.array()?,
+                    writable_indexes: r.vec(|r| r.byte())?,
+                    readonly_indexes: r.vec(|r| r.byte())?,
+                })
+            })?,
+        };
+        Ok(Self {
+            version,
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+        })
+    }
+
+    /// Encode the message, i.e. the bytes the signatures are over.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        if self.version == MessageVersion::V0 {
+            out.push(VERSION_PREFIX);
+        }
+        out.push(self.header.num_required_signatures);
+        out.push(self.header.num_readonly_signed_accounts);
+        out.push(self.header.num_readonly_unsigned_accounts);
+        write_len(&mut out, self.account_keys.len());
+        for key in &self.account_keys {
+            out.extend_from_slice(key);
+        }
+        out.extend_from_slice(&self.recent_blockhash);
+        write_len(&mut out, self.instructions.len());
+        for instruction in &self.instructions {
+            out.push(instruction.program_id_index);
+            write_bytes(&mut out, &instruction.accounts);
+            write_bytes(&mut out, &instruction.data);
+        }
+        if self.version == MessageVersion::V0 {
+            write_len(&mut out, self.address_table_lookups.len());
+            for lookup in &self.address_table_lookups {
+                out.extend_from_slice(&lookup.account_key);
+                write_bytes(&mut out, &lookup.writable_indexes);
+                write_bytes(&mut out, &lookup.readonly_indexes);
+            }
+        }
+        out
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, CodecError> {
+        let (&first, rest) = self.bytes.split_first().ok_or(CodecError::UnexpectedEnd)?;
+        self.bytes = rest;
+        Ok(first)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], CodecError> {
+        if self.bytes.len() < N {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().expect("split at N"))
+    }
+
+    /// Compact-u16 length: 7 bits per byte, least significant first, at most 3 bytes.
+    fn len(&mut self) -> Result<usize, CodecError> {
+        let mut len = 0usize;
+        for i in 0..3 {
+            let byte = self.byte()?;
+            len |= usize::from(byte & 0x7f) << (7 * i);
+            if byte & 0x80 == 0 {
+                return if len > usize::from(u16::MAX) {
+                    Err(CodecError::InvalidLength)
+                } else {
+                    Ok(len)
+                };
+            }
+        }
+        Err(CodecError::InvalidLength)
+    }
+
+    fn vec<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T, CodecError>) -> Result<Vec<T>, CodecError> {
+        let len = self.len()?;
+        // every item takes at least a byte, so a larger length cannot be valid
+        if len > self.bytes.len() {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        (0..len).map(|_| item(self)).collect()
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    let mut len = len;
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_len(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v0_transaction() -> RawTransaction {
+        RawTransaction {
+            signatures: vec![[0; 64], [7; 64]],
+            message: RawMessage {
+                version: MessageVersion::V0,
+                header: MessageHeader {
+                    num_required_signatures: 2,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: vec![[1; 32], [2; 32], [3; 32]],
+                recent_blockhash: [9; 32],
+                instructions: vec![CompiledInstruction {
+                    program_id_index: 2,
+                    accounts: vec![0, 1],
+                    data: vec![0xaa; 200],
+                }],
+                address_table_lookups: vec![AddressTableLookup {
+                    account_key: [4; 32],
+                    writable_indexes: vec![0],
+                    readonly_indexes: vec![1, 2],
+                }],
+            },
+        }
+    }
+
+    /// Both alphabets decode, padded or not, and what isn't a transaction is rejected.
+    #[test]
+    fn decodes_either_alphabet() {
+        let mut tx = v0_transaction();
+        // signature bytes that encode to `+/` in the standard alphabet
+        tx.signatures[0] = [0xfb; 64];
+        let bytes = tx.encode();
+        let standard = encode_transaction(&bytes, Base64Alphabet::Standard);
+        let url_safe = encode_transaction(&bytes, Base64Alphabet::UrlSafe);
+        assert!(standard.contains(['+', '/']) && url_safe.contains(['-', '_']));
+        assert_eq!(decode_transaction(&standard).unwrap(), bytes);
+        assert_eq!(decode_transaction(url_safe.trim_end_matches('=')).unwrap(), bytes);
+        assert!(matches!(decode_transaction("not base64!"), Err(CodecError::Base64(_))));
+        assert_eq!(decode_transaction("AAAA"), Err(CodecError::UnexpectedEnd));
+    }
+
+    /// v0 transactions round-trip, and rewriting the fee payer clears every signature.
+    #[test]
+    fn round_trips_and_rewrites_fee_payer() {
+        let tx = v0_transaction();
+        let bytes = tx.encode();
+        assert_eq!(RawTransaction::decode(&bytes).unwrap(), tx);
+        assert_eq!(RawTransaction::from_base64(&tx.to_base64()).unwrap(), tx);
+        assert_eq!(tx.unsigned_slots(), vec![0]);
+        tx.check().unwrap();
+
+        let mut rewritten = tx.clone();
+        assert_eq!(rewritten.set_fee_payer([2; 32]), Err(CodecError::DuplicateAccount));
+        rewritten.set_fee_payer([5; 32]).unwrap();
+        assert_eq!(rewritten.fee_payer(), Some(&[5; 32]));
+        assert_eq!(rewritten.unsigned_slots(), vec![0, 1]);
+
+        assert_eq!(RawTransaction::decode(&bytes[..bytes.len() - 1]), Err(CodecError::UnexpectedEnd));
+        let mut long = bytes.clone();
+        long.push(0);
+        assert_eq!(RawTransaction::decode(&long), Err(CodecError::TrailingBytes(1)));
+    }
+
+    /// Encoding matches `solana-sdk`'s for legacy transactions.
+    #[cfg(feature = "solana")]
+    #[test]
+    fn matches_solana_sdk_encoding() {
+        use solana_sdk::hash::Hash;
+        use solana_sdk::pubkey::Pubkey;
+        use solana_sdk::transaction::Transaction;
+
+        let payer = Pubkey::new_unique();
+        let from = Pubkey::new_unique();
+        let instruction = solana_system_interface::instruction::transfer(&from, &Pubkey::new_unique(), 1_000);
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+        tx.message.recent_blockhash = Hash::new_unique();
+
+        let bytes = bincode::serialize(&tx).unwrap();
+        let raw = RawTransaction::decode(&bytes).unwrap();
+        assert_eq!(raw.message.version, MessageVersion::Legacy);
+        assert_eq!(raw.fee_payer(), Some(&payer.to_bytes()));
+        assert_eq!(raw.signatures.len(), 2);
+        assert_eq!(raw.encode(), bytes);
+    }
+}
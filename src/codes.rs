@@ -0,0 +1,240 @@
+//! Stable identifiers for API errors, returned by [`AethokitError::code`].
+//!
+//! Match on these instead of on messages or bare status codes:
+//!
+//! ```
+//! # fn handle(err: aethokit::AethokitError) {
+//! use aethokit::codes;
+//!
+//! match err.code() {
+//!     Some(codes::INSUFFICIENT_GAS_BALANCE) => { /* top up the tank */ }
+//!     Some(codes::RATE_LIMITED) => { /* back off */ }
+//!     _ => { /* report */ }
+//! }
+//! # }
+//! ```
+//!
+//! [`AethokitError::error_code`] returns the same codes as an [`ErrorCode`], keeping codes
+//! this SDK doesn't know yet, e.g. to look up a translated message for each.
+
+use std::fmt;
+
+use reqwest::StatusCode;
+
+use crate::AethokitError;
+
+/// The request body or transaction was rejected as malformed (400).
+pub const INVALID_REQUEST: &str = "INVALID_REQUEST";
+/// The gas key is missing, invalid or revoked (401, 403).
+pub const INVALID_GAS_KEY: &str = "INVALID_GAS_KEY";
+/// The gas tank cannot pay for the transaction (402).
+pub const INSUFFICIENT_GAS_BALANCE: &str = "INSUFFICIENT_GAS_BALANCE";
+/// The requested resource, e.g. a transaction hash, is unknown (404).
+pub const NOT_FOUND: &str = "NOT_FOUND";
+/// The transaction's recent blockhash expired before it could be sponsored, so it must be
+/// rebuilt with a fresh one and signed again.
+pub const BLOCKHASH_EXPIRED: &str = "BLOCKHASH_EXPIRED";
+/// The quota reservation charged for the sponsorship has no budget left.
+pub const RESERVATION_EXHAUSTED: &str = "RESERVATION_EXHAUSTED";
+/// The quota reservation charged for the sponsorship expired or was released.
+pub const RESERVATION_EXPIRED: &str = "RESERVATION_EXPIRED";
+/// Too many requests for the gas key (429).
+pub const RATE_LIMITED: &str = "RATE_LIMITED";
+/// The backend failed to handle the request (5xx).
+pub const SERVER_ERROR: &str = "SERVER_ERROR";
+/// An account the transaction spends from, other than the gas tank, lacks the funds.
+pub const INSUFFICIENT_FUNDS: &str = "INSUFFICIENT_FUNDS";
+/// The transaction doesn't decode or failed the backend's checks.
+pub const INVALID_TX: &str = "INVALID_TX";
+/// The gas key was suspended by its owner or the backend, e.g. for abuse.
+pub const KEY_SUSPENDED: &str = "KEY_SUSPENDED";
+/// The gas key belongs to another network than the request.
+pub const NETWORK_MISMATCH: &str = "NETWORK_MISMATCH";
+
+/// Every code in this module.
+pub const ALL: &[&str] = &[
+    INVALID_REQUEST,
+    INVALID_GAS_KEY,
+    INSUFFICIENT_GAS_BALANCE,
+    NOT_FOUND,
+    BLOCKHASH_EXPIRED,
+    RESERVATION_EXHAUSTED,
+    RESERVATION_EXPIRED,
+    RATE_LIMITED,
+    SERVER_ERROR,
+    INSUFFICIENT_FUNDS,
+    INVALID_TX,
+    KEY_SUSPENDED,
+    NETWORK_MISMATCH,
+];
+
+/// An API error code, see [`AethokitError::error_code`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    InvalidRequest,
+    InvalidGasKey,
+    InsufficientGasBalance,
+    NotFound,
+    BlockhashExpired,
+    ReservationExhausted,
+    ReservationExpired,
+    RateLimited,
+    ServerError,
+    InsufficientFunds,
+    InvalidTx,
+    KeySuspended,
+    NetworkMismatch,
+    /// A code this version of the SDK doesn't know, as the backend sent it
+    Unknown(String),
+}
+
+impl ErrorCode {
+    /// The code as the backend sends it, e.g. `INVALID_TX`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::InvalidRequest => INVALID_REQUEST,
+            ErrorCode::InvalidGasKey => INVALID_GAS_KEY,
+            ErrorCode::InsufficientGasBalance => INSUFFICIENT_GAS_BALANCE,
+            ErrorCode::NotFound => NOT_FOUND,
+            ErrorCode::BlockhashExpired => BLOCKHASH_EXPIRED,
+            ErrorCode::ReservationExhausted => RESERVATION_EXHAUSTED,
+            ErrorCode::ReservationExpired => RESERVATION_EXPIRED,
+            ErrorCode::RateLimited => RATE_LIMITED,
+            ErrorCode::ServerError => SERVER_ERROR,
+            ErrorCode::InsufficientFunds => INSUFFICIENT_FUNDS,
+            ErrorCode::InvalidTx => INVALID_TX,
+            ErrorCode::KeySuspended => KEY_SUSPENDED,
+            ErrorCode::NetworkMismatch => NETWORK_MISMATCH,
+            ErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ErrorCode {
+    /// Known codes are matched ignoring case; anything else is kept as `Unknown`.
+    fn from(code: &str) -> Self {
+        let known = [
+            ErrorCode::InvalidRequest,
+            ErrorCode::InvalidGasKey,
+            ErrorCode::InsufficientGasBalance,
+            ErrorCode::NotFound,
+            ErrorCode::BlockhashExpired,
+            ErrorCode::ReservationExhausted,
+            ErrorCode::ReservationExpired,
+            ErrorCode::RateLimited,
+            ErrorCode::ServerError,
+            ErrorCode::InsufficientFunds,
+            ErrorCode::InvalidTx,
+            ErrorCode::KeySuspended,
+            ErrorCode::NetworkMismatch,
+        ];
+        known
+            .into_iter()
+            .find(|known| known.as_str().eq_ignore_ascii_case(code))
+            .unwrap_or_else(|| ErrorCode::Unknown(code.to_string()))
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The `code` of an error body, as sent.
+fn sent_code(body: &serde_json::Value) -> Option<&str> {
+    body["code"].as_str().or_else(|| body["error"]["code"].as_str())
+}
+
+/// Code of a rejected request: the `code` of the error body when it is a known one,
+/// otherwise the code implied by the status.
+pub(crate) fn for_response(status: StatusCode, body: &str) -> Option<&'static str> {
+    let body: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+    if let Some(code) = sent_code(&body).and_then(|sent| ALL.iter().find(|code| code.eq_ignore_ascii_case(sent))) {
+        return Some(code);
+    }
+    // the RPC node's preflight error, passed through by backends that don't send a code
+    if body["error"]["message"]
+        .as_str()
+        .or_else(|| body["message"].as_str())
+        .is_some_and(|message| message.to_ascii_lowercase().contains("blockhash not found"))
+    {
+        return Some(BLOCKHASH_EXPIRED);
+    }
+    match status.as_u16() {
+        400 | 422 => Some(INVALID_REQUEST),
+        401 | 403 => Some(INVALID_GAS_KEY),
+        402 => Some(INSUFFICIENT_GAS_BALANCE),
+        404 => Some(NOT_FOUND),
+        429 => Some(RATE_LIMITED),
+        500..=599 => Some(SERVER_ERROR),
+        _ => None,
+    }
+}
+
+impl AethokitError {
+    /// Stable identifier of the API error behind this one, if the server rejected the
+    /// request; see [`codes`](crate::codes).
+    pub fn code(&self) -> Option<&'static str> {
+        match self.inner() {
+            AethokitError::UnexpectedStatus { status, body, .. } => for_response(*status, body),
+            AethokitError::RateLimited { .. } => Some(RATE_LIMITED),
+            AethokitError::BlockhashExpired => Some(BLOCKHASH_EXPIRED),
+            AethokitError::ReservationExhausted { .. } => Some(RESERVATION_EXHAUSTED),
+            AethokitError::ReservationExpired { .. } => Some(RESERVATION_EXPIRED),
+            _ => None,
+        }
+    }
+
+    /// [`code`](Self::code) as an [`ErrorCode`], except that a code the SDK doesn't know
+    /// is returned as `Unknown` instead of the one implied by the status.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        if let AethokitError::UnexpectedStatus { body, .. } = self.inner() {
+            let body: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+            if let Some(sent) = sent_code(&body) {
+                return Some(ErrorCode::from(sent));
+            }
+        }
+        self.code().map(ErrorCode::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A known code in the body wins over the status; unknown ones fall back to it.
+    #[test]
+    fn maps_responses_to_codes() {
+        let code = |status: u16, body: &str| for_response(StatusCode::from_u16(status).unwrap(), body);
+        assert_eq!(code(400, r#"{"code":"insufficient_gas_balance"}"#), Some(INSUFFICIENT_GAS_BALANCE));
+        assert_eq!(code(400, r#"{"error":{"code":"INVALID_GAS_KEY"}}"#), Some(INVALID_GAS_KEY));
+        assert_eq!(code(402, r#"{"code":"SOMETHING_NEW"}"#), Some(INSUFFICIENT_GAS_BALANCE));
+        assert_eq!(code(503, "upstream down"), Some(SERVER_ERROR));
+        let preflight = r#"{"message":"Transaction simulation failed: Blockhash not found"}"#;
+        assert_eq!(code(400, preflight), Some(BLOCKHASH_EXPIRED));
+        assert_eq!(code(302, ""), None);
+    }
+
+    /// Unknown codes are kept as sent; known ones map to their variant.
+    #[test]
+    fn maps_errors_to_error_codes() {
+        let rejected = |status: u16, body: &str| AethokitError::UnexpectedStatus {
+            status: StatusCode::from_u16(status).unwrap(),
+            method: reqwest::Method::POST,
+            path: "sponsor-tx".to_string(),
+            body: body.to_string(),
+            json: None,
+            request_id: None,
+        };
+        assert_eq!(rejected(403, r#"{"code":"key_suspended"}"#).error_code(), Some(ErrorCode::KeySuspended));
+        assert_eq!(
+            rejected(400, r#"{"error":{"code":"TX_TOO_OLD"}}"#).error_code(),
+            Some(ErrorCode::Unknown("TX_TOO_OLD".to_string()))
+        );
+        assert_eq!(rejected(429, "").error_code(), Some(ErrorCode::RateLimited));
+        assert_eq!(ErrorCode::from("NETWORK_MISMATCH").to_string(), NETWORK_MISMATCH);
+        assert!(ALL.iter().all(|code| !matches!(ErrorCode::from(*code), ErrorCode::Unknown(_))));
+    }
+}
@@ -0,0 +1,283 @@
+use std::sync::Mutex;
+
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::{Aethokit, AethokitError};
+
+/// Response schema version the SDK's models were written against, as `major.minor`.
+pub const API_SCHEMA_VERSION: &str = "1.0";
+
+/// Header the server announces its response schema version in.
+pub const API_VERSION_HEADER: &str = "x-aethokit-api-version";
+
+/// Header every request names the SDK release in, e.g. `0.4.0`.
+pub const SDK_VERSION_HEADER: &str = "x-sdk-version";
+
+/// Header every request asks for responses in [`API_SCHEMA_VERSION`] with, so the server can
+/// keep serving older SDKs during a migration.
+pub const ACCEPT_VERSION_HEADER: &str = "accept-version";
+
+/// Fields the previous response schema (0.x) named differently, as `(endpoint, old, new)`.
+const PREVIOUS_SCHEMA_RENAMES: &[(&str, &str, &str)] = &[
+    ("sponsor-tx", "signature", "hash"),
+    ("get-sponsored-tx", "signature", "hash"),
+    ("get-sponsored-txs", "signature", "hash"),
+    ("get-gas-address", "address", "gasAddress"),
+];
+
+/// How the server's schema version relates to [`API_SCHEMA_VERSION`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Same major and minor version
+    Compatible,
+    /// Same major version with a different minor: fields may have been added or become
+    /// optional, but nothing the SDK relies on was removed
+    MinorDrift,
+    /// Different major version: responses may not parse
+    Incompatible,
+    /// No response carried a version header yet, or it could not be parsed
+    Unknown,
+}
+
+/// The server's schema version compared with the one the SDK was built against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    pub sdk_version: &'static str,
+    pub server_version: Option<String>,
+    pub compatibility: Compatibility,
+}
+
+impl CompatibilityReport {
+    fn new(server_version: Option<String>) -> Self {
+        let compatibility = match server_version.as_deref().map(parse) {
+            None | Some(None) => Compatibility::Unknown,
+            Some(Some(server)) => {
+                let sdk = parse(API_SCHEMA_VERSION).expect("valid schema version");
+                if server == sdk {
+                    Compatibility::Compatible
+                } else if server.0 == sdk.0 {
+                    Compatibility::MinorDrift
+                } else {
+                    Compatibility::Incompatible
+                }
+            }
+        };
+        Self {
+            sdk_version: API_SCHEMA_VERSION,
+            server_version,
+            compatibility,
+        }
+    }
+
+    /// The server's major version differs, so responses are likely to fail to parse.
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility == Compatibility::Incompatible
+    }
+}
+
+/// `major.minor`, or a bare `major` as `major.0`.
+fn parse(version: &str) -> Option<(u32, u32)> {
+    let version = version.trim().trim_start_matches('v');
+    let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Rewrite a successful response body of `endpoint` from the previous schema into the
+/// current one, if the server announced an older major version or none at all, as
+/// self-hosted backends from before the version header do.
+///
+/// Fields are renamed at the top level and in records of top-level arrays, unless the body
+/// already has the new name. Bodies that aren't JSON objects are returned as they are.
+pub(crate) fn upgrade_response(endpoint: &str, headers: &HeaderMap, text: String) -> String {
+    let sdk_major = parse(API_SCHEMA_VERSION).expect("valid schema version").0;
+    let announced = headers.get(API_VERSION_HEADER).map(|value| value.to_str().ok().and_then(parse));
+    match announced {
+        None => {}
+        Some(Some((major, _))) if major < sdk_major => {}
+        Some(_) => return text,
+    }
+    let mut renames = PREVIOUS_SCHEMA_RENAMES
+        .iter()
+        .filter(|(path, ..)| *path == endpoint)
+        .peekable();
+    if renames.peek().is_none() {
+        return text;
+    }
+    let Ok(Value::Object(mut body)) = serde_json::from_str::<Value>(&text) else {
+        return text;
+    };
+    for (_, old, new) in renames {
+        rename(&mut body, old, new);
+        for records in body.values_mut().filter_map(Value::as_array_mut) {
+            for record in records.iter_mut().filter_map(Value::as_object_mut) {
+                rename(record, old, new);
+            }
+        }
+    }
+    serde_json::to_string(&body).unwrap_or(text)
+}
+
+fn rename(object: &mut serde_json::Map<String, Value>, old: &str, new: &str) {
+    if !object.contains_key(new) {
+        if let Some(value) = object.remove(old) {
+            object.insert(new.to_string(), value);
+        }
+    }
+}
+
+/// Latest schema version announced by the server, shared between clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct ServerVersion {
+    seen: Mutex<Option<String>>,
+}
+
+impl ServerVersion {
+    /// Record the version header of a response, warning the first time it is incompatible.
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        let Some(version) = headers
+            .get(API_VERSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return;
+        };
+        let mut seen = self.seen.lock().unwrap();
+        if seen.as_deref() != Some(version) {
+            *seen = Some(version.to_string());
+            #[cfg(feature = "tracing")]
+            if CompatibilityReport::new(Some(version.to_string())).is_breaking() {
+                tracing::warn!(
+                    target: "aethokit",
+                    server_version = version,
+                    sdk_version = API_SCHEMA_VERSION,
+                    "the Aethokit API schema version is incompatible with this SDK; upgrade the SDK"
+                );
+            }
+        }
+    }
+
+    fn report(&self) -> CompatibilityReport {
+        CompatibilityReport::new(self.seen.lock().unwrap().clone())
+    }
+}
+
+impl Aethokit {
+    /// Compatibility with the schema version the server announced in its latest response
+    /// to this client or its clones, without making a request.
+    pub fn compatibility(&self) -> CompatibilityReport {
+        self.server_version.report()
+    }
+
+    /// [`ping`](Self::ping) the server and report its schema version, for a startup check.
+    ///
+    /// # Errors
+    /// - Any error from [`ping`](Self::ping)
+    pub async fn check_compatibility(&self) -> Result<CompatibilityReport, AethokitError> {
+        self.ping().await?;
+        Ok(self.compatibility())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use futures_util::future::BoxFuture;
+    use reqwest::{Request, Response};
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+    use crate::{Middleware, Next};
+
+    struct ExpectVersionHeaders;
+
+    impl Middleware for ExpectVersionHeaders {
+        fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+            assert_eq!(request.headers()[SDK_VERSION_HEADER], env!("CARGO_PKG_VERSION"));
+            assert_eq!(request.headers()[ACCEPT_VERSION_HEADER], API_SCHEMA_VERSION);
+            next.run(request)
+        }
+    }
+
+    /// Requests carry the SDK's versions; only a major version change is breaking, and the
+    /// latest announced version wins.
+    #[tokio::test]
+    async fn reports_server_schema_version() {
+        assert_eq!(
+            CompatibilityReport::new(Some("1.0".into())).compatibility,
+            Compatibility::Compatible
+        );
+        assert_eq!(
+            CompatibilityReport::new(Some("v1.3".into())).compatibility,
+            Compatibility::MinorDrift
+        );
+        assert_eq!(
+            CompatibilityReport::new(Some("2".into())).compatibility,
+            Compatibility::Incompatible
+        );
+        assert_eq!(
+            CompatibilityReport::new(Some("beta".into())).compatibility,
+            Compatibility::Unknown
+        );
+
+        let backend = FakeBackend::new();
+        let client = backend
+            .client()
+            .middleware(Arc::new(ExpectVersionHeaders))
+            .build()
+            .unwrap();
+        assert_eq!(client.compatibility().compatibility, Compatibility::Unknown);
+
+        let body = json!({ "gasAddress": "tank" });
+        backend.on(
+            "GET",
+            "get-gas-address",
+            FakeResponse::json(200, body).header(API_VERSION_HEADER, "2.0"),
+        );
+        backend.on(
+            "GET",
+            "health",
+            FakeResponse::json(200, json!({})).header(API_VERSION_HEADER, "1.1"),
+        );
+        client.refresh_gas_address().await.unwrap();
+        assert!(client.compatibility().is_breaking());
+        let report = client.check_compatibility().await.unwrap();
+        assert_eq!(report.server_version.as_deref(), Some("1.1"));
+        assert_eq!(report.compatibility, Compatibility::MinorDrift);
+    }
+
+    /// Responses of a 0.x server, or one that doesn't announce a version, are read with the
+    /// previous field names; a current server's bodies are left alone.
+    #[tokio::test]
+    async fn reads_previous_schema() {
+        let backend = FakeBackend::new();
+        let page = json!({
+            "transactions": [{
+                "signature": "sig",
+                "feePaid": 5000,
+                "timestamp": "2024-05-01T12:00:00Z",
+                "status": "confirmed",
+            }],
+        });
+        backend
+            .on(
+                "POST",
+                "sponsor-tx",
+                FakeResponse::json(200, json!({ "signature": "sig" })).header(API_VERSION_HEADER, "0.9"),
+            )
+            .on("GET", "get-sponsored-txs", FakeResponse::json(200, page))
+            .on(
+                "GET",
+                "get-gas-address",
+                FakeResponse::json(200, json!({ "address": "tank" })).header(API_VERSION_HEADER, "1.0"),
+            );
+        let client = backend.client().build().unwrap();
+
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        let page = client.list_sponsored_txs(Default::default()).await.unwrap();
+        assert_eq!(page.records[0].hash, "sig");
+        assert!(client.refresh_gas_address().await.is_err());
+    }
+}
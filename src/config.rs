@@ -0,0 +1,252 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::retry::duration_ms;
+use crate::{AethokitConfig, AethokitError, CircuitBreaker, RetryPolicy};
+
+/// HTTP timeouts applied to every request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Timeouts {
+    /// Total time allowed for a request, from connecting to reading the body
+    #[serde(rename = "request_ms", default, with = "option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub request: Option<Duration>,
+    /// Time allowed to establish the connection
+    #[serde(rename = "connect_ms", default, with = "option_duration_ms", skip_serializing_if = "Option::is_none")]
+    pub connect: Option<Duration>,
+}
+
+/// Deployment a client targets, see [`AethokitBuilder::environment`](crate::AethokitBuilder::environment).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    /// The sandbox API on devnet; mainnet networks are rejected
+    Sandbox,
+    /// The production API on mainnet; devnet and testnet are rejected
+    Production,
+}
+
+impl Environment {
+    /// Base URL of the environment's API deployment.
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Environment::Sandbox => SANDBOX_BASE_URL,
+            Environment::Production => crate::DEFAULT_BASE_URL,
+        }
+    }
+
+    /// Network used when no `rpc_or_network` is set.
+    pub fn default_network(self) -> &'static str {
+        match self {
+            Environment::Sandbox => "devnet",
+            Environment::Production => "mainnet",
+        }
+    }
+
+    /// Whether `rpc_or_network` may be used in this environment. Custom RPC URLs that don't
+    /// name a cluster in their host are allowed in both.
+    pub(crate) fn allows(self, rpc_or_network: &str) -> bool {
+        let rpc_or_network = rpc_or_network.to_ascii_lowercase();
+        let host = url::Url::parse(&rpc_or_network).ok().and_then(|url| url.host_str().map(str::to_string));
+        let names = |clusters: &[&str]| match &host {
+            Some(host) => clusters.iter().any(|cluster| host.contains(cluster)),
+            None => clusters.contains(&rpc_or_network.as_str()),
+        };
+        match self {
+            Environment::Sandbox => !names(&["mainnet", "mainnet-beta"]),
+            Environment::Production => !names(&["devnet", "testnet"]),
+        }
+    }
+}
+
+const SANDBOX_BASE_URL: &str = "https://aethokit-sandbox.onrender.com/api/";
+
+impl AethokitConfig {
+    /// Load a configuration file, as TOML if the extension is `.toml` and JSON otherwise.
+    ///
+    /// ```toml
+    /// gas_key = "..."
+    /// network = "devnet"            # RPC URL or network name
+    /// base_url = "https://aethokit.onrender.com/api/"
+    ///
+    /// [retry]
+    /// max_retries = 3
+    /// initial_backoff_ms = 200
+    /// max_backoff_ms = 5000
+    /// post_retries = "idempotent"   # or "pre_transmission", "never"
+    ///
+    /// [timeouts]
+    /// request_ms = 30000
+    /// connect_ms = 5000
+    /// ```
+    ///
+    /// # Errors
+    /// - `Config` if the file cannot be read or does not match the schema
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AethokitError> {
+        load(path.as_ref())
+    }
+}
+
+/// The transport policy of a client, as one struct that can be shipped as a config file
+/// and applied with [`AethokitBuilder::transport_profile`](crate::AethokitBuilder::transport_profile).
+///
+/// Fields left unset keep whatever the builder already has, so a profile can be layered
+/// over code defaults. Request hedging is not supported by the SDK, so there is no setting
+/// for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransportProfile {
+    #[serde(default)]
+    pub timeouts: Timeouts,
+    /// Retry policy for idempotent requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// Client-side limit on requests per second, at least one; see
+    /// [`AethokitBuilder::rate_limit`](crate::AethokitBuilder::rate_limit)
+    #[serde(rename = "rate_limit_per_second", default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u32>,
+    /// Circuit breaker for backend outages; see
+    /// [`AethokitBuilder::circuit_breaker`](crate::AethokitBuilder::circuit_breaker)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl TransportProfile {
+    /// Load a profile file, as TOML if the extension is `.toml` and JSON otherwise.
+    ///
+    /// ```toml
+    /// rate_limit_per_second = 20
+    ///
+    /// [retry]
+    /// max_retries = 2
+    /// initial_backoff_ms = 100
+    /// max_backoff_ms = 2000
+    ///
+    /// [timeouts]
+    /// request_ms = 10000
+    ///
+    /// [circuit_breaker]
+    /// failure_threshold = 5
+    /// open_duration_ms = 30000
+    /// half_open_probes = 1
+    /// ```
+    ///
+    /// # Errors
+    /// - `Config` if the file cannot be read or does not match the schema
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AethokitError> {
+        load(path.as_ref())
+    }
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> Result<T, AethokitError> {
+    let config_error = |message: String| AethokitError::Config {
+        path: path.display().to_string(),
+        message,
+    };
+    let contents = std::fs::read_to_string(path).map_err(|err| config_error(err.to_string()))?;
+    let is_toml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    if is_toml {
+        toml::from_str(&contents).map_err(|err| config_error(err.to_string()))
+    } else {
+        serde_json::from_str(&contents).map_err(|err| config_error(err.to_string()))
+    }
+}
+
+mod option_duration_ms {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => super::duration_ms::serialize(duration, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Option::<u64>::deserialize(deserializer).map(|ms| ms.map(Duration::from_millis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// TOML and JSON files share one schema; `network` is accepted for `rpc_or_network`.
+    #[test]
+    fn loads_toml_and_json() {
+        let dir = std::env::temp_dir();
+        let toml_path = dir.join(format!("aethokit-config-{}.toml", std::process::id()));
+        std::fs::write(
+            &toml_path,
+            "gas_key = \"key\"\nnetwork = \"devnet\"\n\n[retry]\nmax_retries = 2\ninitial_backoff_ms = 100\nmax_backoff_ms = 1000\n\n[timeouts]\nrequest_ms = 30000\n",
+        )
+        .unwrap();
+        let config = AethokitConfig::from_file(&toml_path).unwrap();
+        std::fs::remove_file(&toml_path).unwrap();
+        assert_eq!(config.rpc_or_network.as_deref(), Some("devnet"));
+        assert_eq!(config.retry.unwrap().max_retries, 2);
+        assert_eq!(config.timeouts.request, Some(Duration::from_secs(30)));
+        assert_eq!(config.timeouts.connect, None);
+
+        let json_path = dir.join(format!("aethokit-config-{}.json", std::process::id()));
+        std::fs::write(&json_path, r#"{"gas_key": "key", "rpcOrNetwork": "mainnet"}"#).unwrap();
+        let config = AethokitConfig::from_file(&json_path).unwrap();
+        std::fs::remove_file(&json_path).unwrap();
+        assert_eq!(config.rpc_or_network.as_deref(), Some("mainnet"));
+        assert!(config.retry.is_none());
+    }
+
+    /// Unknown keys in a profile are rejected so typos don't silently fall back to defaults.
+    #[test]
+    fn loads_transport_profile() {
+        let profile: TransportProfile =
+            toml::from_str("rate_limit_per_second = 20\n\n[timeouts]\nconnect_ms = 500\n").unwrap();
+        assert_eq!(profile.rate_limit, Some(20));
+        assert_eq!(profile.timeouts.connect, Some(Duration::from_millis(500)));
+        assert!(profile.retry.is_none());
+        assert!(profile.circuit_breaker.is_none());
+
+        let breaker = CircuitBreaker { failure_threshold: 3, open_duration: Duration::from_secs(10), half_open_probes: 2 };
+        let profile = TransportProfile { circuit_breaker: Some(breaker), ..profile };
+        let text = toml::to_string(&profile).unwrap();
+        assert!(text.contains("open_duration_ms = 10000"));
+        assert_eq!(toml::from_str::<TransportProfile>(&text).unwrap(), profile);
+        let client = crate::Aethokit::builder().gas_key("key").transport_profile(profile).build().unwrap();
+        assert_eq!(client.circuit_state(), Some(crate::CircuitState::Closed));
+
+        assert!(toml::from_str::<TransportProfile>("rate_limit = 20\n").is_err());
+        assert!(toml::from_str::<TransportProfile>("[circuit_breaker]\nfailure_threshold = 3\nopen_ms = 10\nhalf_open_probes = 1\n").is_err());
+    }
+
+    /// Environments pick their deployment and network, and refuse networks of the other one.
+    #[test]
+    fn environment_presets() {
+        let build = |environment, rpc: Option<&str>| {
+            let builder = crate::Aethokit::builder().gas_key("key").environment(environment);
+            match rpc {
+                Some(rpc) => builder.rpc_or_network(rpc).build(),
+                None => builder.build(),
+            }
+        };
+        let sandbox = build(Environment::Sandbox, None).unwrap();
+        assert_eq!(sandbox.base_url.as_str(), SANDBOX_BASE_URL);
+        assert_eq!(sandbox.rpc_or_network.as_deref(), Some("devnet"));
+        let production = build(Environment::Production, None).unwrap();
+        assert_eq!(production.base_url.as_str(), crate::DEFAULT_BASE_URL);
+        assert_eq!(production.rpc_or_network.as_deref(), Some("mainnet"));
+
+        for (environment, rpc) in [
+            (Environment::Sandbox, "mainnet-beta"),
+            (Environment::Sandbox, "https://api.mainnet-beta.solana.com"),
+            (Environment::Production, "devnet"),
+            (Environment::Production, "https://api.testnet.solana.com"),
+        ] {
+            let err = build(environment, Some(rpc)).unwrap_err();
+            assert!(matches!(err, AethokitError::EnvironmentMismatch { .. }), "{rpc}: {err}");
+        }
+        assert!(build(Environment::Production, Some("https://rpc.example.com")).is_ok());
+    }
+}
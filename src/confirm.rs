@@ -0,0 +1,347 @@
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Serialize;
+
+use crate::{Aethokit, AethokitError, HistoryStatus, TxRecord};
+
+/// How settled a sponsored transaction must be before [`Aethokit::sponsor_and_confirm`] returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitmentLevel {
+    /// Landed in a slot, which may still be skipped
+    Processed,
+    /// Voted on by a supermajority of the cluster
+    #[default]
+    Confirmed,
+    /// Rooted, can no longer be rolled back
+    Finalized,
+}
+
+impl CommitmentLevel {
+    fn is_reached_by(self, record: &TxRecord) -> bool {
+        match self {
+            CommitmentLevel::Processed => record.slot.is_some(),
+            CommitmentLevel::Confirmed => {
+                matches!(record.status, HistoryStatus::Confirmed | HistoryStatus::Finalized)
+            }
+            CommitmentLevel::Finalized => record.status == HistoryStatus::Finalized,
+        }
+    }
+}
+
+/// A sponsored transaction that reached the requested commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmedSponsorship {
+    /// Transaction signature, as returned by `sponsor_tx`
+    pub signature: String,
+    pub slot: u64,
+    /// Fee paid by the gas tank, in lamports
+    pub fee: u64,
+}
+
+/// A commitment level a sponsored transaction reached, with its slot, yielded by
+/// [`Aethokit::confirm_tx_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationProgress {
+    Processed(u64),
+    Confirmed(u64),
+    Finalized(u64),
+}
+
+impl ConfirmationProgress {
+    fn new(level: CommitmentLevel, slot: u64) -> Self {
+        match level {
+            CommitmentLevel::Processed => ConfirmationProgress::Processed(slot),
+            CommitmentLevel::Confirmed => ConfirmationProgress::Confirmed(slot),
+            CommitmentLevel::Finalized => ConfirmationProgress::Finalized(slot),
+        }
+    }
+}
+
+/// Polling state of [`Aethokit::confirm_tx_progress`].
+struct Progress {
+    /// Levels not reported yet, lowest first
+    remaining: Vec<CommitmentLevel>,
+    record: Option<TxRecord>,
+    /// Wait before the next poll, `None` before the first
+    interval: Option<Duration>,
+}
+
+/// Options for [`Aethokit::confirm_all`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmOptions {
+    /// Signatures polled at the same time
+    pub concurrency: usize,
+    /// How long to wait for each signature, from when its polling starts
+    pub timeout: Duration,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 16,
+            timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+const CONFIRM_POLL_START: Duration = Duration::from_millis(500);
+const CONFIRM_POLL_MAX: Duration = Duration::from_secs(2);
+
+impl Aethokit {
+    /// Submit a transaction for sponsorship and wait until it reaches `commitment`,
+    /// polling the history API for up to `timeout` (including the submission).
+    ///
+    /// # Errors
+    /// - `TxFailed` if the transaction landed but failed
+    /// - `NotConfirmed` if `commitment` was not reached within `timeout`; the transaction
+    ///   may still land, so keep tracking it by the returned hash
+    pub async fn sponsor_and_confirm(
+        &self,
+        tx: String,
+        commitment: CommitmentLevel,
+        timeout: Duration,
+    ) -> Result<ConfirmedSponsorship, AethokitError> {
+        let deadline = self.time.now() + timeout;
+        let hash = self.sponsor_signature(tx).await?;
+        self.confirm_until(hash, commitment, deadline).await
+    }
+
+    /// Wait until an already sponsored transaction reaches `commitment`, polling the history
+    /// API for up to `timeout`.
+    ///
+    /// # Errors
+    /// - `TxFailed` if the transaction landed but failed
+    /// - `NotConfirmed` if `commitment` was not reached within `timeout`
+    pub async fn confirm_tx(
+        &self,
+        signature: &str,
+        commitment: CommitmentLevel,
+        timeout: Duration,
+    ) -> Result<ConfirmedSponsorship, AethokitError> {
+        self.confirm_until(signature.to_string(), commitment, self.time.now() + timeout)
+            .await
+    }
+
+    /// [`confirm_tx`](Self::confirm_tx), yielding each commitment level as the transaction
+    /// reaches it, up to `commitment`, so a UI can show progress the way wallets do.
+    ///
+    /// Every level up to `commitment` is yielded once and in order, even if a single poll
+    /// shows several reached. The stream ends after `commitment`, or with an error.
+    ///
+    /// # Errors
+    /// - `TxFailed` if the transaction landed but failed
+    /// - `NotConfirmed` if `commitment` was not reached within `timeout`
+    pub fn confirm_tx_progress<'a>(
+        &'a self,
+        signature: &'a str,
+        commitment: CommitmentLevel,
+        timeout: Duration,
+    ) -> impl Stream<Item = Result<ConfirmationProgress, AethokitError>> + 'a {
+        let deadline = self.time.now() + timeout;
+        let remaining = [
+            CommitmentLevel::Processed,
+            CommitmentLevel::Confirmed,
+            CommitmentLevel::Finalized,
+        ]
+        .into_iter()
+        .filter(|level| *level <= commitment)
+        .collect();
+        let state = Progress {
+            remaining,
+            record: None,
+            interval: None,
+        };
+        stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+            loop {
+                if let (Some(record), Some(&level)) = (&state.record, state.remaining.first()) {
+                    if level.is_reached_by(record) {
+                        let progress = ConfirmationProgress::new(level, record.slot.unwrap_or_default());
+                        state.remaining.remove(0);
+                        let next = (!state.remaining.is_empty()).then_some(state);
+                        return Some((Ok(progress), next));
+                    }
+                }
+                let interval = match state.interval {
+                    Some(interval) if self.time.now() + interval > deadline => {
+                        let err = AethokitError::NotConfirmed {
+                            hash: signature.to_string(),
+                            status: state.record.map(|record| record.status),
+                        };
+                        return Some((Err(err), None));
+                    }
+                    Some(interval) => {
+                        if let Err(err) = self.scoped_sleep(interval).await {
+                            return Some((Err(err), None));
+                        }
+                        (interval * 2).min(CONFIRM_POLL_MAX)
+                    }
+                    None => CONFIRM_POLL_START,
+                };
+                state.interval = Some(interval);
+                match self.lookup_sponsored_tx(signature).await {
+                    Ok(Some(record)) if record.status == HistoryStatus::Failed => {
+                        let err = AethokitError::TxFailed {
+                            hash: signature.to_string(),
+                        };
+                        return Some((Err(err), None));
+                    }
+                    Ok(record) => state.record = record,
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        })
+    }
+
+    /// [`confirm_tx`](Self::confirm_tx) for many signatures at once, e.g. after a batch
+    /// mint, with at most `options.concurrency` polled at a time.
+    ///
+    /// Yields each signature with its outcome as it completes, so in completion order, not
+    /// input order. A failure of one signature doesn't stop the others.
+    ///
+    /// # Panics
+    /// If `options.concurrency` is zero.
+    pub fn confirm_all<'a, I, S>(
+        &'a self,
+        signatures: I,
+        commitment: CommitmentLevel,
+        options: ConfirmOptions,
+    ) -> impl Stream<Item = (String, Result<ConfirmedSponsorship, AethokitError>)> + 'a
+    where
+        I: IntoIterator<Item = S>,
+        I::IntoIter: 'a,
+        S: Into<String> + 'a,
+    {
+        assert!(options.concurrency > 0, "concurrency must be at least 1");
+        stream::iter(signatures)
+            .map(move |signature| async move {
+                let signature = signature.into();
+                let result = self.confirm_tx(&signature, commitment, options.timeout).await;
+                (signature, result)
+            })
+            .buffer_unordered(options.concurrency)
+    }
+
+    async fn confirm_until(
+        &self,
+        hash: String,
+        commitment: CommitmentLevel,
+        deadline: Instant,
+    ) -> Result<ConfirmedSponsorship, AethokitError> {
+        let mut interval = CONFIRM_POLL_START;
+        loop {
+            let record = self.lookup_sponsored_tx(&hash).await?;
+            match record {
+                Some(record) if record.status == HistoryStatus::Failed => {
+                    return Err(AethokitError::TxFailed { hash });
+                }
+                Some(record) if commitment.is_reached_by(&record) => {
+                    return Ok(ConfirmedSponsorship {
+                        signature: record.hash,
+                        slot: record.slot.unwrap_or_default(),
+                        fee: record.fee_paid,
+                    });
+                }
+                _ => {}
+            }
+            if self.time.now() + interval > deadline {
+                return Err(AethokitError::NotConfirmed {
+                    hash,
+                    status: record.map(|record| record.status),
+                });
+            }
+            self.scoped_sleep(interval).await?;
+            interval = (interval * 2).min(CONFIRM_POLL_MAX);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Each level accepts the statuses at or beyond it; `Processed` only needs a slot.
+    #[test]
+    fn checks_commitment() {
+        let record = |status, slot| TxRecord {
+            hash: "hash".to_string(),
+            slot,
+            fee_paid: 5_000,
+            timestamp: "2024-05-01T12:00:00Z".parse().unwrap(),
+            status,
+            fee_breakdown: None,
+        };
+        assert!(!CommitmentLevel::Processed.is_reached_by(&record(HistoryStatus::Pending, None)));
+        assert!(CommitmentLevel::Processed.is_reached_by(&record(HistoryStatus::Pending, Some(1))));
+        assert!(CommitmentLevel::Confirmed.is_reached_by(&record(HistoryStatus::Finalized, Some(1))));
+        assert!(!CommitmentLevel::Finalized.is_reached_by(&record(HistoryStatus::Confirmed, Some(1))));
+    }
+
+    /// Every signature gets its own outcome, failures included.
+    #[tokio::test(start_paused = true)]
+    async fn confirms_all_signatures() {
+        let backend = FakeBackend::new();
+        for (hash, status) in [("a", "confirmed"), ("b", "failed")] {
+            let record = json!({
+                "hash": hash,
+                "slot": 1,
+                "feePaid": 5000,
+                "timestamp": "2024-05-01T12:00:00Z",
+                "status": status,
+            });
+            backend.on("GET", "get-sponsored-tx", FakeResponse::json(200, record));
+        }
+        let client = backend.client().build().unwrap();
+
+        // one at a time, so the canned records are served in order
+        let options = ConfirmOptions {
+            concurrency: 1,
+            ..ConfirmOptions::default()
+        };
+        let outcomes: Vec<_> = client
+            .confirm_all(["a", "b"], CommitmentLevel::Confirmed, options)
+            .collect()
+            .await;
+        assert_eq!(outcomes[0].0, "a");
+        assert_eq!(outcomes[0].1.as_ref().unwrap().slot, 1);
+        assert!(matches!(&outcomes[1].1, Err(AethokitError::TxFailed { hash }) if hash == "b"));
+    }
+
+    /// Levels are reported once each and in order, including ones reached in one poll.
+    #[tokio::test(start_paused = true)]
+    async fn streams_confirmation_progress() {
+        let backend = FakeBackend::new();
+        for (slot, status) in [(None, "pending"), (Some(7), "confirmed"), (Some(7), "finalized")] {
+            let record = json!({
+                "hash": "a",
+                "slot": slot,
+                "feePaid": 5000,
+                "timestamp": "2024-05-01T12:00:00Z",
+                "status": status,
+            });
+            backend.on("GET", "get-sponsored-tx", FakeResponse::json(200, record));
+        }
+        let client = backend.client().build().unwrap();
+
+        let progress: Vec<_> = client
+            .confirm_tx_progress("a", CommitmentLevel::Finalized, Duration::from_secs(30))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            progress,
+            [
+                ConfirmationProgress::Processed(7),
+                ConfirmationProgress::Confirmed(7),
+                ConfirmationProgress::Finalized(7),
+            ]
+        );
+        assert_eq!(backend.requests().len(), 3);
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::clock::Time;
+use crate::codec::RawTransaction;
+use crate::{Responded, SponsoredTx};
+
+type MessageHash = [u8; 32];
+
+/// Recent sponsorships by message hash, see
+/// [`AethokitBuilder::dedup_cache`](crate::AethokitBuilder::dedup_cache).
+#[derive(Debug)]
+pub(crate) struct DedupCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<HashMap<MessageHash, (Responded<SponsoredTx>, Instant)>>,
+    time: Time,
+}
+
+impl DedupCache {
+    pub(crate) fn new(capacity: usize, ttl: Duration, time: Time) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            entries: Mutex::default(),
+            time,
+        }
+    }
+
+    /// Hash of the message of `tx`, `None` if it doesn't decode.
+    pub(crate) fn key(tx: &str) -> Option<MessageHash> {
+        let tx = RawTransaction::from_base64(tx).ok()?;
+        Some(Sha256::digest(tx.message.encode()).into())
+    }
+
+    /// The sponsorship of the same message within the TTL, if any.
+    pub(crate) fn get(&self, key: &MessageHash) -> Option<Responded<SponsoredTx>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((resp, at)) if self.time.now().duration_since(*at) < self.ttl => Some(resp.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: MessageHash, resp: &Responded<SponsoredTx>) {
+        let mut entries = self.entries.lock().unwrap();
+        let now = self.time.now();
+        entries.retain(|_, (_, at)| now.duration_since(*at) < self.ttl);
+        if entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (_, at))| *at)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (resp.clone(), now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::codec::{CompiledInstruction, MessageHeader, MessageVersion, RawMessage};
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    fn tx(signature: u8, blockhash: u8) -> String {
+        RawTransaction {
+            signatures: vec![[signature; 64]],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: vec![[1; 32], [2; 32]],
+                recent_blockhash: [blockhash; 32],
+                instructions: vec![CompiledInstruction {
+                    program_id_index: 1,
+                    accounts: vec![0],
+                    data: vec![],
+                }],
+                address_table_lookups: vec![],
+            },
+        }
+        .to_base64()
+    }
+
+    /// Resubmitting the same message within the TTL returns the first signature without a
+    /// request; another message, or the same after the TTL, is sent.
+    #[tokio::test(start_paused = true)]
+    async fn short_circuits_resubmissions() {
+        let backend = FakeBackend::new();
+        backend.on(
+            "POST",
+            "sponsor-tx",
+            FakeResponse::json(200, json!({ "hash": "first" })),
+        );
+        backend.on(
+            "POST",
+            "sponsor-tx",
+            FakeResponse::json(200, json!({ "hash": "second" })),
+        );
+        let client = backend
+            .client()
+            .dedup_cache(100, Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        assert_eq!(client.sponsor_tx(tx(0, 9)).await.unwrap(), "first");
+        // signature slots don't matter, only the message
+        assert_eq!(client.sponsor_tx(tx(7, 9)).await.unwrap(), "first");
+        assert_eq!(backend.requests().len(), 1);
+        assert_eq!(client.sponsor_tx(tx(0, 8)).await.unwrap(), "second");
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(client.sponsor_tx(tx(0, 9)).await.unwrap(), "second");
+        assert_eq!(backend.requests().len(), 3);
+    }
+}
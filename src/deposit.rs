@@ -0,0 +1,107 @@
+use reqwest::Method;
+use serde::Deserialize;
+use url::form_urlencoded;
+
+use crate::{Aethokit, AethokitError};
+
+const SOL_DECIMALS: u8 = 9;
+
+/// How to fund the gas tank, from [`Aethokit::get_deposit_info`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DepositInfo {
+    #[serde(rename = "gasAddress")]
+    pub gas_address: String,
+    /// Smallest deposit credited to the tank, in lamports
+    #[serde(rename = "minTopUp", deserialize_with = "crate::lenient::u64")]
+    pub min_top_up_lamports: u64,
+    /// Tokens the tank accepts besides SOL
+    #[serde(rename = "acceptedTokens", default)]
+    pub accepted_tokens: Vec<AcceptedToken>,
+}
+
+/// An SPL token the gas tank can be topped up with.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AcceptedToken {
+    pub symbol: String,
+    pub mint: String,
+    pub decimals: u8,
+}
+
+impl DepositInfo {
+    /// Solana Pay transfer request URL for topping up the tank, to render as a link or QR code.
+    ///
+    /// `amount` is in lamports, or in the smallest unit of `token` if given; without it the
+    /// wallet asks the user.
+    pub fn solana_pay_url(&self, amount: Option<u64>, token: Option<&AcceptedToken>) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        if let Some(amount) = amount {
+            let decimals = token.map_or(SOL_DECIMALS, |token| token.decimals);
+            query.append_pair("amount", &decimal(amount, decimals));
+        }
+        if let Some(token) = token {
+            query.append_pair("spl-token", &token.mint);
+        }
+        query.append_pair("label", "Aethokit gas tank");
+        format!("solana:{}?{}", self.gas_address, query.finish())
+    }
+}
+
+/// `amount` base units as a decimal without trailing zeros, e.g. `1500000000` with 9 decimals
+/// as `1.5`.
+fn decimal(amount: u64, decimals: u8) -> String {
+    let scale = 10u64.pow(u32::from(decimals));
+    let (whole, fraction) = (amount / scale, amount % scale);
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{fraction:0width$}", width = usize::from(decimals));
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+impl Aethokit {
+    /// Gas address, minimum top-up and accepted tokens of the gas tank, for "fund your gas
+    /// tank" flows; see [`DepositInfo::solana_pay_url`] for a ready-made payment link.
+    pub async fn get_deposit_info(&self) -> Result<DepositInfo, AethokitError> {
+        self.make_request::<(), _>("deposit-info", Method::GET, None)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Amounts are written in whole units of SOL or of the chosen token.
+    #[tokio::test]
+    async fn builds_solana_pay_urls() {
+        let backend = FakeBackend::new();
+        let body = json!({
+            "gasAddress": "Gas111",
+            "minTopUp": 10_000_000,
+            "acceptedTokens": [{ "symbol": "USDC", "mint": "EPjF", "decimals": 6 }],
+        });
+        backend.on("GET", "deposit-info", FakeResponse::json(200, body));
+        let info = backend
+            .client()
+            .build()
+            .unwrap()
+            .get_deposit_info()
+            .await
+            .unwrap();
+        assert_eq!(info.min_top_up_lamports, 10_000_000);
+
+        assert_eq!(
+            info.solana_pay_url(Some(1_500_000_000), None),
+            "solana:Gas111?amount=1.5&label=Aethokit+gas+tank"
+        );
+        assert_eq!(
+            info.solana_pay_url(Some(25_000_000), info.accepted_tokens.first()),
+            "solana:Gas111?amount=25&spl-token=EPjF&label=Aethokit+gas+tank"
+        );
+        assert_eq!(decimal(5, 9), "0.000000005");
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use reqwest::header::HeaderMap;
+
+use crate::Aethokit;
+
+/// Warn, once per process, that the string-based `api` is deprecated in favour of `replacement`.
+///
+/// Only emits anything with the `deprecation-warnings` feature, which logs through `tracing`
+/// so call sites can be found from existing logs before the next major version removes them.
+pub(crate) fn warn_once(api: &'static str, replacement: &'static str) {
+    if !first_use(api) {
+        return;
+    }
+    #[cfg(test)]
+    WARNED.lock().unwrap().push(api);
+    #[cfg(feature = "deprecation-warnings")]
+    tracing::warn!(api, replacement, "`{api}` is deprecated, use `{replacement}` instead");
+    #[cfg(not(feature = "deprecation-warnings"))]
+    let _ = replacement;
+}
+
+/// APIs [`warn_once`] has warned about, in order, so tests can see what was reported.
+#[cfg(test)]
+static WARNED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+fn first_use(api: &'static str) -> bool {
+    static SEEN: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    SEEN.get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(api)
+}
+
+/// An endpoint the server flagged as deprecated with `Deprecation`/`Sunset` response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerDeprecation {
+    /// Path of the endpoint relative to the base URL, without the query string
+    pub endpoint: String,
+    /// `Deprecation` header as sent: a date, or `true`/`@<unix time>` depending on the server
+    pub deprecation: Option<String>,
+    /// `Sunset` header as sent, the HTTP date the endpoint stops working
+    pub sunset: Option<String>,
+}
+
+/// Callback run the first time each endpoint is reported deprecated.
+pub(crate) type DeprecationCallback = Arc<dyn Fn(&ServerDeprecation) + Send + Sync>;
+
+/// Server-announced deprecations seen by a client, shared between its clones.
+pub(crate) struct Deprecations {
+    seen: Mutex<BTreeMap<String, ServerDeprecation>>,
+    callback: Option<DeprecationCallback>,
+}
+
+impl Deprecations {
+    pub(crate) fn new(callback: Option<DeprecationCallback>) -> Self {
+        Self {
+            seen: Mutex::default(),
+            callback,
+        }
+    }
+
+    /// Record the deprecation headers of a response from `endpoint`, if any.
+    pub(crate) fn observe(&self, endpoint: &str, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let (deprecation, sunset) = (header("deprecation"), header("sunset"));
+        if deprecation.is_none() && sunset.is_none() {
+            return;
+        }
+        let notice = ServerDeprecation {
+            endpoint: endpoint.split('?').next().unwrap_or(endpoint).to_string(),
+            deprecation,
+            sunset,
+        };
+        let first = {
+            let mut seen = self.seen.lock().unwrap();
+            let first = !seen.contains_key(&notice.endpoint);
+            seen.insert(notice.endpoint.clone(), notice.clone());
+            first
+        };
+        if !first {
+            return;
+        }
+        #[cfg(feature = "deprecation-warnings")]
+        tracing::warn!(
+            endpoint = %notice.endpoint,
+            sunset = notice.sunset.as_deref(),
+            "the Aethokit API reports endpoint `{}` as deprecated",
+            notice.endpoint
+        );
+        if let Some(callback) = &self.callback {
+            callback(&notice);
+        }
+    }
+
+    fn pending(&self) -> Vec<ServerDeprecation> {
+        self.seen.lock().unwrap().values().cloned().collect()
+    }
+}
+
+impl fmt::Debug for Deprecations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deprecations")
+            .field("seen", &self.seen)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Aethokit {
+    /// Endpoints the server has flagged as deprecated in responses to this client and its
+    /// clones, with the latest headers seen for each, ordered by endpoint.
+    pub fn pending_deprecations(&self) -> Vec<ServerDeprecation> {
+        self.deprecations.pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Each API is reported only on its first use.
+    #[test]
+    fn reports_each_api_once() {
+        assert!(first_use("tests::legacy_a"));
+        assert!(!first_use("tests::legacy_a"));
+        assert!(first_use("tests::legacy_b"));
+    }
+
+    /// The legacy string-returning sponsorship methods warn on their first call only.
+    #[tokio::test]
+    async fn legacy_sponsor_tx_warns_once() {
+        use crate::test_util::{FakeBackend, FakeResponse};
+        use serde_json::json;
+
+        let backend = FakeBackend::new();
+        for _ in 0..3 {
+            backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "signature": "sig" })));
+        }
+        let client = backend.client().build().unwrap();
+        client.sponsor_tx("tx".to_string()).await.unwrap();
+        client.sponsor_tx("tx".to_string()).await.unwrap();
+        client.sponsor_tx_with_meta("tx".to_string()).await.unwrap();
+
+        let warned = WARNED.lock().unwrap();
+        let count = |api| warned.iter().filter(|&&seen| seen == api).count();
+        assert_eq!(count("Aethokit::sponsor_tx"), 1);
+        assert_eq!(count("Aethokit::sponsor_tx_with_meta"), 1);
+    }
+
+    /// Deprecated endpoints are recorded without their query and reported to the callback once.
+    #[test]
+    fn records_server_deprecations() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let deprecations = Deprecations::new(Some(Arc::new(move |_: &ServerDeprecation| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })));
+        let mut headers = HeaderMap::new();
+        deprecations.observe("get-sponsored-tx?hash=a", &headers);
+        assert!(deprecations.pending().is_empty());
+
+        headers.insert("sunset", "Wed, 01 Jul 2026 00:00:00 GMT".parse().unwrap());
+        deprecations.observe("get-sponsored-tx?hash=a", &headers);
+        deprecations.observe("get-sponsored-tx?hash=b", &headers);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let pending = deprecations.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].endpoint, "get-sponsored-tx");
+        assert_eq!(pending[0].deprecation, None);
+    }
+}
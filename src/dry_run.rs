@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::codec::{Address, RawMessage, RawTransaction};
+use crate::policy::COMPUTE_BUDGET_PROGRAM_ID;
+use crate::{Aethokit, AethokitError, Responded, ResponseMeta, SponsoredTx};
+
+/// Start of the signature of every sponsorship made by a client in dry-run mode, see
+/// [`AethokitBuilder::dry_run`](crate::AethokitBuilder::dry_run).
+pub const DRY_RUN_SIGNATURE_PREFIX: &str = "dry-run-";
+
+/// Compute budget `SetComputeUnitLimit` instruction index.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+/// Compute budget `SetComputeUnitPrice` instruction index.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// A value the server changed, before and after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn of(before: T, after: T) -> Option<Self> {
+        (before != after).then_some(Self { before, after })
+    }
+}
+
+/// An instruction with its program and accounts resolved to addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionSummary {
+    pub program_id: Address,
+    /// Accounts in order; indexes into address lookup tables are left out
+    pub accounts: Vec<Address>,
+    pub data: Vec<u8>,
+}
+
+/// What the server would change in a transaction before sponsoring it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxDiff {
+    pub fee_payer: Option<Change<Option<Address>>>,
+    pub compute_unit_limit: Option<Change<Option<u32>>>,
+    /// Priority fee, in micro-lamports per compute unit
+    pub compute_unit_price: Option<Change<Option<u64>>>,
+    pub recent_blockhash: Option<Change<[u8; 32]>>,
+    /// Instructions in the final transaction but not in the one sent, compute budget ones included
+    pub added_instructions: Vec<InstructionSummary>,
+    /// Instructions in the one sent but not in the final transaction
+    pub removed_instructions: Vec<InstructionSummary>,
+}
+
+impl TxDiff {
+    /// Compare the transaction `sent` with the one the `server` would submit.
+    pub fn between(sent: &RawTransaction, server: &RawTransaction) -> Self {
+        let (before, after) = (summarize(&sent.message), summarize(&server.message));
+        let missing_from = |all: &[InstructionSummary], other: &[InstructionSummary]| {
+            let mut other = other.to_vec();
+            all.iter()
+                .filter(|ix| match other.iter().position(|o| o == *ix) {
+                    Some(i) => {
+                        other.swap_remove(i);
+                        false
+                    }
+                    None => true,
+                })
+                .cloned()
+                .collect()
+        };
+        Self {
+            fee_payer: Change::of(sent.fee_payer().copied(), server.fee_payer().copied()),
+            compute_unit_limit: Change::of(compute_unit_limit(&before), compute_unit_limit(&after)),
+            compute_unit_price: Change::of(compute_unit_price(&before), compute_unit_price(&after)),
+            recent_blockhash: Change::of(sent.message.recent_blockhash, server.message.recent_blockhash),
+            added_instructions: missing_from(&after, &before),
+            removed_instructions: missing_from(&before, &after),
+        }
+    }
+
+    /// `true` when the server would send the transaction unchanged.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn summarize(message: &RawMessage) -> Vec<InstructionSummary> {
+    let key = |index: &u8| message.account_keys.get(usize::from(*index)).copied();
+    message
+        .instructions
+        .iter()
+        .map(|ix| InstructionSummary {
+            program_id: key(&ix.program_id_index).unwrap_or_default(),
+            accounts: ix.accounts.iter().filter_map(key).collect(),
+            data: ix.data.clone(),
+        })
+        .collect()
+}
+
+fn compute_budget_arg<const N: usize>(instructions: &[InstructionSummary], discriminant: u8) -> Option<[u8; N]> {
+    instructions
+        .iter()
+        .filter(|ix| ix.program_id == COMPUTE_BUDGET_PROGRAM_ID)
+        .find_map(|ix| match ix.data.split_first() {
+            Some((&d, arg)) if d == discriminant => arg.try_into().ok(),
+            _ => None,
+        })
+}
+
+fn compute_unit_limit(instructions: &[InstructionSummary]) -> Option<u32> {
+    compute_budget_arg(instructions, SET_COMPUTE_UNIT_LIMIT).map(u32::from_le_bytes)
+}
+
+fn compute_unit_price(instructions: &[InstructionSummary]) -> Option<u64> {
+    compute_budget_arg(instructions, SET_COMPUTE_UNIT_PRICE).map(u64::from_le_bytes)
+}
+
+/// Result of [`Aethokit::dry_run_sponsor_tx`].
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    /// The transaction the server would submit
+    pub transaction: RawTransaction,
+    pub diff: TxDiff,
+}
+
+#[derive(Debug, Serialize)]
+struct DryRunRequest<'a> {
+    transaction: &'a str,
+    #[serde(rename = "rpcOrNetwork", skip_serializing_if = "Option::is_none")]
+    rpc_or_network: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DryRunResponse {
+    transaction: String,
+}
+
+impl Aethokit {
+    /// Ask the server for the transaction it would submit for `tx` (fee payer filled in,
+    /// compute budget and priority fee chosen) without submitting it, diffed against `tx`.
+    ///
+    /// Meant for CI checks that catch surprising server-side changes. Nothing is charged
+    /// to the gas tank.
+    ///
+    /// # Errors
+    /// - `Codec` if `tx` or the server's transaction is not a valid transaction
+    pub async fn dry_run_sponsor_tx(&self, tx: &str) -> Result<DryRun, AethokitError> {
+        let sent = RawTransaction::from_base64(tx)?;
+        let body = DryRunRequest {
+            transaction: tx.trim(),
+            rpc_or_network: self.rpc_or_network.as_deref(),
+        };
+        let resp: DryRunResponse = self
+            .make_request("sponsor-tx/dry-run", Method::POST, Some(&body))
+            .await?;
+        let transaction = RawTransaction::from_base64(&resp.transaction)?;
+        Ok(DryRun {
+            diff: TxDiff::between(&sent, &transaction),
+            transaction,
+        })
+    }
+
+    /// The synthetic result of sponsoring `tx` in dry-run mode, after the same checks as a
+    /// real sponsorship and, if enabled, the server's dry run.
+    pub(crate) async fn dry_run_sponsorship(&self, tx: &str) -> Result<Responded<SponsoredTx>, AethokitError> {
+        let sent = RawTransaction::from_base64(tx)?;
+        let signed_transaction = match self.dry_run_simulation {
+            true => Some(self.dry_run_sponsor_tx(tx).await?.transaction.to_base64()),
+            false => None,
+        };
+        let hash = Sha256::digest(sent.message.encode());
+        Ok(Responded {
+            value: SponsoredTx {
+                signature: format!("{DRY_RUN_SIGNATURE_PREFIX}{}", &hex::encode(hash)[..32]),
+                slot: None,
+                fee_lamports: None,
+                sponsored_at: None,
+                signed_transaction,
+                fee_breakdown: None,
+                dry_run: true,
+            },
+            meta: ResponseMeta::new(StatusCode::OK, &HeaderMap::new(), Duration::ZERO),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::codec::{CompiledInstruction, MessageHeader, MessageVersion};
+
+    /// Sponsorships in dry-run mode are checked but never sent.
+    #[tokio::test]
+    async fn skips_broadcast_in_dry_run_mode() {
+        use crate::test_util::FakeBackend;
+
+        let backend = FakeBackend::new();
+        let client = backend.client().dry_run(true).build().unwrap();
+        let tx = RawTransaction {
+            signatures: vec![[0; 64]],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 0,
+                },
+                account_keys: vec![[1; 32]],
+                recent_blockhash: [0; 32],
+                instructions: vec![],
+                address_table_lookups: vec![],
+            },
+        };
+
+        let sponsored = client.sponsor_tx_detailed(tx.to_base64()).await.unwrap();
+        assert!(sponsored.dry_run);
+        assert!(sponsored.signature.starts_with(DRY_RUN_SIGNATURE_PREFIX));
+        assert!(client.sponsor_tx("not a transaction".to_string()).await.is_err());
+        assert!(backend.requests().is_empty());
+    }
+
+    /// Fee payer, priority fee and added compute budget instructions are reported.
+    #[test]
+    fn diffs_server_changes() {
+        let transfer = CompiledInstruction {
+            program_id_index: 2,
+            accounts: vec![0, 1],
+            data: vec![2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
+        };
+        let sent = RawTransaction {
+            signatures: vec![[0; 64]],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: vec![[1; 32], [2; 32], [0; 32]],
+                recent_blockhash: [9; 32],
+                instructions: vec![transfer.clone()],
+                address_table_lookups: Vec::new(),
+            },
+        };
+        assert!(TxDiff::between(&sent, &sent).is_empty());
+
+        let mut server = sent.clone();
+        server.message.account_keys = vec![[8; 32], [1; 32], [2; 32], [0; 32], COMPUTE_BUDGET_PROGRAM_ID];
+        let mut price = vec![SET_COMPUTE_UNIT_PRICE];
+        price.extend(5_000u64.to_le_bytes());
+        server.message.instructions = vec![
+            CompiledInstruction {
+                program_id_index: 4,
+                accounts: Vec::new(),
+                data: price,
+            },
+            CompiledInstruction {
+                program_id_index: 3,
+                accounts: vec![1, 2],
+                data: transfer.data,
+            },
+        ];
+
+        let diff = TxDiff::between(&sent, &server);
+        assert_eq!(
+            diff.fee_payer,
+            Some(Change {
+                before: Some([1; 32]),
+                after: Some([8; 32])
+            })
+        );
+        assert_eq!(diff.compute_unit_price.map(|change| change.after), Some(Some(5_000)));
+        assert_eq!(diff.compute_unit_limit, None);
+        assert_eq!(diff.recent_blockhash, None);
+        assert_eq!(diff.added_instructions.len(), 1);
+        assert_eq!(diff.added_instructions[0].program_id, COMPUTE_BUDGET_PROGRAM_ID);
+        assert!(diff.removed_instructions.is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+use base64::Engine as _;
+
+use crate::codec::{CodecError, RawTransaction};
+use crate::{Aethokit, AethokitError};
+
+/// Text encoding of a serialized transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TxEncoding {
+    /// Standard base64, the format [`Aethokit::sponsor_tx`] expects
+    #[default]
+    Base64,
+    /// Base58, as handed out by many wallet adapters
+    Base58,
+}
+
+impl TxEncoding {
+    /// Decode `tx` into the raw transaction bytes.
+    pub fn decode(self, tx: &str) -> Result<Vec<u8>, AethokitError> {
+        let tx = tx.trim();
+        let bytes = match self {
+            TxEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(tx)
+                .map_err(|err| CodecError::Base64(err.to_string()))?,
+            TxEncoding::Base58 => bs58::decode(tx)
+                .into_vec()
+                .map_err(|err| CodecError::Base58(err.to_string()))?,
+        };
+        Ok(bytes)
+    }
+}
+
+impl Aethokit {
+    /// Submit a transaction encoded as `encoding` for sponsorship. Returns the transaction hash.
+    ///
+    /// # Errors
+    /// - `Codec` if `tx` is not valid in `encoding` or does not decode as a transaction
+    pub async fn sponsor_encoded_tx(&self, tx: &str, encoding: TxEncoding) -> Result<String, AethokitError> {
+        match encoding {
+            TxEncoding::Base64 => {
+                RawTransaction::decode(&encoding.decode(tx)?)?;
+                self.sponsor_signature(tx.trim().to_string()).await
+            }
+            TxEncoding::Base58 => self.sponsor_tx_bytes(&encoding.decode(tx)?).await,
+        }
+    }
+
+    /// Submit a serialized transaction for sponsorship. Returns the transaction hash.
+    ///
+    /// # Errors
+    /// - `Codec` if `tx` does not decode as a transaction
+    pub async fn sponsor_tx_bytes(&self, tx: &[u8]) -> Result<String, AethokitError> {
+        RawTransaction::decode(tx)?;
+        self.sponsor_signature(base64::engine::general_purpose::STANDARD.encode(tx))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base58 and base64 inputs decode to the same bytes.
+    #[test]
+    fn decodes_encodings() {
+        let bytes = vec![1, 0, 2, 255, 42];
+        let base58 = bs58::encode(&bytes).into_string();
+        let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        assert_eq!(TxEncoding::Base58.decode(&base58).unwrap(), bytes);
+        assert_eq!(TxEncoding::Base64.decode(&format!(" {base64}\n")).unwrap(), bytes);
+        assert!(matches!(
+            TxEncoding::Base58.decode("0OIl"),
+            Err(AethokitError::Codec(CodecError::Base58(_)))
+        ));
+    }
+}
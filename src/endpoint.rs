@@ -0,0 +1,131 @@
+//! Request paths built from templates and typed query parameters.
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde::ser::Error as _;
+use serde::Serialize;
+use serde_json::Value;
+use url::form_urlencoded;
+
+use crate::AethokitError;
+
+/// Characters escaped in a path segment: everything but unreserved characters and the
+/// sub-delimiters allowed in segments.
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// `template` with each `{name}` replaced by the percent-encoded value of `name` in
+/// `params`, followed by the fields of `query` as a query string.
+///
+/// `query` must serialize to a struct or map, or to `()` for none. Fields that are `None`
+/// are left out, sequences repeat the key, and values use their serde representation, so
+/// enums and timestamps come out as in JSON bodies.
+///
+/// # Errors
+/// - `Internal` if the template has an unclosed `{` or a name missing from `params`
+pub(crate) fn endpoint(
+    template: &str,
+    params: &[(&str, &str)],
+    query: &impl Serialize,
+) -> Result<String, AethokitError> {
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start
+            + rest[start..]
+                .find('}')
+                .ok_or(AethokitError::Internal("unclosed `{` in path template"))?;
+        let name = &rest[start + 1..end];
+        let value = params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| *value)
+            .ok_or(AethokitError::Internal("missing value for a path template parameter"))?;
+        path.push_str(&rest[..start]);
+        path.extend(utf8_percent_encode(value, SEGMENT));
+        rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+
+    let fields = match serde_json::to_value(query)? {
+        Value::Null => return Ok(path),
+        Value::Object(fields) => fields,
+        _ => {
+            return Err(
+                serde_json::Error::custom("query parameters must be a struct or map").into(),
+            )
+        }
+    };
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in &fields {
+        let values = match value {
+            Value::Array(values) => values.as_slice(),
+            value => std::slice::from_ref(value),
+        };
+        for value in values {
+            match value {
+                Value::Null => {}
+                Value::String(value) => {
+                    serializer.append_pair(key, value);
+                }
+                Value::Bool(_) | Value::Number(_) => {
+                    serializer.append_pair(key, &value.to_string());
+                }
+                Value::Array(_) | Value::Object(_) => {
+                    return Err(serde_json::Error::custom(format!(
+                        "query parameter `{key}` is nested"
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+    let query = serializer.finish();
+    if !query.is_empty() {
+        path.push('?');
+        path.push_str(&query);
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    /// Path values are escaped as segments, and unset query fields are left out.
+    #[test]
+    fn builds_endpoints() {
+        let query = json!({ "limit": 10, "cursor": null, "status": ["a b", "c&d"] });
+        assert_eq!(
+            endpoint("tx/{signature}/status", &[("signature", "5x/y?z")], &query).unwrap(),
+            "tx/5x%2Fy%3Fz/status?limit=10&status=a+b&status=c%26d"
+        );
+        assert_eq!(endpoint("health", &[], &()).unwrap(), "health");
+        assert!(endpoint("health", &[], &json!({ "nested": { "a": 1 } })).is_err());
+    }
+
+    /// Broken templates surface as errors instead of panicking in a request handler.
+    #[test]
+    fn rejects_broken_templates() {
+        for template in ["tx/{signature", "tx/{signature}"] {
+            let err = endpoint(template, &[], &()).unwrap_err();
+            assert!(matches!(err, AethokitError::Internal(_)), "{template}: {err}");
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::Method;
+use serde::Deserialize;
+
+use crate::webhooks::{TxConfirmed, TxFailed};
+use crate::{Aethokit, AethokitError, ResponseMeta};
+
+/// A real-time notification from the sponsorship event stream.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type")]
+pub enum SponsorEvent {
+    #[serde(rename = "tx.confirmed")]
+    TxConfirmed(TxConfirmed),
+    #[serde(rename = "tx.failed")]
+    TxFailed(TxFailed),
+    #[serde(rename = "balance.changed")]
+    BalanceChanged(BalanceChanged),
+    /// An event type this version of the SDK does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// The gas tank balance changed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BalanceChanged {
+    #[serde(rename = "gasAddress")]
+    pub gas_address: String,
+    /// New balance, in lamports
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub balance: u64,
+    /// Time the event was emitted
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Aethokit {
+    /// Subscribe to sponsorship events for the gas tank over server-sent events.
+    ///
+    /// The stream ends when the server closes the connection; callers that need a
+    /// permanent subscription should resubscribe.
+    ///
+    /// A deadline set with [`with_deadline`](Aethokit::with_deadline) bounds opening the
+    /// subscription, not how long the stream stays open.
+    ///
+    /// # Errors
+    /// - `RateLimited` if the server answers 429, with its retry-after
+    /// - `UnexpectedStatus` if the server rejects the subscription otherwise
+    pub async fn subscribe_events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<SponsorEvent, AethokitError>>, AethokitError> {
+        let url = self.join_url("events")?;
+        let connect = async {
+            self.load_gas_key().await?;
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+            let req = self.build_request(
+                self.request(Method::GET, url)
+                    .header("accept", "text/event-stream"),
+            )?;
+            self.middleware.send(&*self.transport.0, req).await
+        };
+        let started = self.time.now();
+        let res = self.within_scope(connect).await?;
+        let status = res.status();
+        let meta = ResponseMeta::new(status, res.headers(), self.time.now() - started);
+        self.observe_response("events", res.headers(), &meta);
+        if !status.is_success() {
+            let body = res.text().await?;
+            return Err(AethokitError::from_status(status, Method::GET, "events", body, meta));
+        }
+
+        let events = res
+            .bytes_stream()
+            .scan(SseDecoder::default(), |decoder, chunk| {
+                let items = match chunk {
+                    Ok(bytes) => decoder
+                        .push(&bytes)
+                        .into_iter()
+                        .map(|data| serde_json::from_str(&data).map_err(AethokitError::from))
+                        .collect(),
+                    Err(err) => vec![Err(AethokitError::from(err))],
+                };
+                futures_util::future::ready(Some(stream::iter(items)))
+            })
+            .flatten();
+        let spend = self.spend.clone();
+        let events = events.inspect(move |event| {
+            if let Ok(SponsorEvent::BalanceChanged(ev)) = event {
+                spend.observe_balance(ev.balance);
+            }
+        });
+        #[cfg(feature = "otel")]
+        let events = {
+            let otel = self.otel.clone();
+            events.inspect(move |event| {
+                if let Ok(SponsorEvent::BalanceChanged(ev)) = event {
+                    otel.record_balance(&ev.gas_address, ev.balance);
+                }
+            })
+        };
+        Ok(events)
+    }
+}
+
+/// Incremental decoder for the `text/event-stream` format, yielding each event's data.
+#[derive(Debug, Default)]
+struct SseDecoder {
+    buf: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(chunk);
+        let mut complete = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    complete.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+            // `event:`, `id:`, `retry:` and `:` comment lines carry nothing we need.
+        }
+        complete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Events split across chunks are reassembled and keep-alive comments are ignored.
+    #[test]
+    fn decodes_chunked_events() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b": keep-alive\n\nevent: balance\ndata: {\"type\":").is_empty());
+        let events = decoder.push(
+            b"\"balance.changed\",\"gasAddress\":\"Gas111\",\"balance\":42,\"timestamp\":\"2024-05-01T12:00:00Z\"}\r\n\r\n",
+        );
+        assert_eq!(events.len(), 1);
+
+        let event: SponsorEvent = serde_json::from_str(&events[0]).unwrap();
+        match event {
+            SponsorEvent::BalanceChanged(ev) => assert_eq!(ev.balance, 42),
+            other => panic!("expected BalanceChanged, got {other:?}"),
+        }
+    }
+
+    /// A rate-limited subscription reports the server's retry-after like other endpoints.
+    #[tokio::test]
+    async fn rate_limited_subscription() {
+        use std::time::Duration;
+
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let backend = FakeBackend::new();
+        let limited = FakeResponse::json(429, json!({ "error": "slow down" })).header("retry-after", "7");
+        backend.on("GET", "events", limited);
+        let client = backend.client().build().unwrap();
+
+        match client.subscribe_events().await.map(drop) {
+            Err(AethokitError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(7)))
+            }
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+        assert_eq!(client.rate_limit_info().unwrap().retry_after, Some(Duration::from_secs(7)));
+    }
+}
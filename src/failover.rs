@@ -0,0 +1,160 @@
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+use crate::{Aethokit, AethokitError, Responded};
+
+/// The primary gas key, shared by a client's clones so a rotation reaches all of them.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedGasKey(Arc<RwLock<Arc<str>>>);
+
+impl SharedGasKey {
+    pub(crate) fn new(gas_key: String) -> Self {
+        Self(Arc::new(RwLock::new(gas_key.into())))
+    }
+
+    pub(crate) fn get(&self) -> Arc<str> {
+        self.0.read().unwrap().clone()
+    }
+}
+
+impl Aethokit {
+    /// Replace the primary gas key of the client and its clones, e.g. after fetching a
+    /// rotated key from a secrets manager.
+    ///
+    /// Requests already sent finish with the old key; every request or retry sent after the
+    /// call uses the new one. Clients for other keys, from [`Tenants`](crate::Tenants),
+    /// [`ClientPool`](crate::ClientPool) or fallback keys, keep theirs. The cached gas address
+    /// is dropped if the key changed, in case the new key belongs to another tank.
+    ///
+    /// # Errors
+    /// - `MissingGasKey` if `gas_key` is empty
+    pub fn set_gas_key(&self, gas_key: impl Into<String>) -> Result<(), AethokitError> {
+        let gas_key = gas_key.into();
+        if gas_key.trim().is_empty() {
+            return Err(AethokitError::MissingGasKey);
+        }
+        let mut current = self.gas_key.0.write().unwrap();
+        if **current != *gas_key {
+            // cache entries are keyed by the gas key, so the old key's aren't read again
+            *current = gas_key.into();
+        }
+        Ok(())
+    }
+
+    /// Number of gas keys the client can sponsor with: the primary key plus any fallbacks.
+    pub fn gas_key_count(&self) -> usize {
+        1 + self.fallback_keys.len()
+    }
+
+    /// The client for gas key `index` (0 is the primary key), without fallbacks of its own.
+    fn for_gas_key(&self, index: usize) -> Aethokit {
+        let mut client = match index {
+            0 => self.clone(),
+            _ => self.with_gas_key(self.fallback_keys[index - 1].clone()),
+        };
+        client.fallback_keys = Arc::default();
+        client
+    }
+
+    /// A copy of the client using `gas_key`, whose gas address and policy are cached apart.
+    pub(crate) fn with_gas_key(&self, gas_key: String) -> Aethokit {
+        let mut client = self.clone();
+        client.gas_key = SharedGasKey::new(gas_key);
+        client.key_source = None;
+        // the gas address differs per tank, so only share lookups within one key
+        client.gas_address_flight = Default::default();
+        client
+    }
+
+    /// Run `call` with each gas key in priority order until one is not out of quota.
+    ///
+    /// The index of the key that succeeded is recorded in the result's
+    /// [`ResponseMeta::gas_key_index`](crate::ResponseMeta::gas_key_index).
+    pub(crate) async fn with_failover<T, F, Fut>(&self, call: F) -> Result<Responded<T>, AethokitError>
+    where
+        F: Fn(Aethokit) -> Fut,
+        Fut: Future<Output = Result<Responded<T>, AethokitError>>,
+    {
+        let last = self.gas_key_count() - 1;
+        for index in 0.. {
+            match call(self.for_gas_key(index)).await {
+                Ok(mut resp) => {
+                    resp.meta.gas_key_index = index;
+                    return Ok(resp);
+                }
+                Err(err) if index < last && err.is_quota_exceeded() => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(AethokitError::Internal("no gas key was tried"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use reqwest::header::HeaderMap;
+    use reqwest::{Method, StatusCode};
+
+    use crate::ResponseMeta;
+
+    /// Keys are tried in order while they are out of quota, and the last error is returned.
+    #[tokio::test]
+    async fn falls_back_to_next_key() {
+        let client = Aethokit::builder()
+            .gas_key("primary")
+            .fallback_gas_keys(["second", "third"])
+            .build()
+            .unwrap();
+        let depleted = || {
+            AethokitError::unexpected_status(
+                StatusCode::PAYMENT_REQUIRED,
+                Method::POST,
+                "sponsor-tx",
+                "gas tank depleted".to_string(),
+                None,
+            )
+        };
+
+        let resp = client
+            .with_failover(|client| async move {
+                match &*client.gas_key.get() {
+                    "second" => Ok(Responded {
+                        value: "second".to_string(),
+                        meta: ResponseMeta::new(StatusCode::OK, &HeaderMap::new(), Duration::ZERO),
+                    }),
+                    _ => Err(depleted()),
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(resp.value, "second");
+        assert_eq!(resp.meta.gas_key_index, 1);
+
+        let err = client
+            .with_failover(|_| async { Err::<Responded<()>, _>(depleted()) })
+            .await
+            .unwrap_err();
+        assert!(err.is_quota_exceeded());
+    }
+
+    /// A rotated key reaches every clone, but not clients made for other keys.
+    #[tokio::test]
+    async fn rotates_gas_key() {
+        let backend = crate::test_util::FakeBackend::new();
+        let body = serde_json::json!({ "hash": "sig" });
+        backend.on("POST", "sponsor-tx", crate::test_util::FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+        let clone = client.clone();
+        let tenant = client.with_gas_key("tenant".to_string());
+
+        client.set_gas_key("rotated").unwrap();
+        clone.sponsor_tx("tx".to_string()).await.unwrap();
+        assert_eq!(backend.requests()[0].gas_key.as_deref(), Some("rotated"));
+        assert_eq!(&*tenant.gas_key.get(), "tenant");
+        assert!(matches!(client.set_gas_key(" "), Err(AethokitError::MissingGasKey)));
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{Aethokit, AethokitError};
+
+/// Lamports the gas tank would spend to sponsor a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct FeeEstimate {
+    /// Signature fee, 5000 lamports per signature on mainnet
+    #[serde(rename = "baseFee", deserialize_with = "crate::lenient::u64")]
+    pub base_fee_lamports: u64,
+    /// Compute-unit price times the compute-unit limit
+    #[serde(rename = "priorityFee", default, deserialize_with = "crate::lenient::u64")]
+    pub priority_fee_lamports: u64,
+}
+
+impl FeeEstimate {
+    pub fn total_lamports(&self) -> u64 {
+        self.base_fee_lamports.saturating_add(self.priority_fee_lamports)
+    }
+}
+
+impl fmt::Display for FeeEstimate {
+    /// One-line summary, e.g. `6200 lamports (base 5000, priority 1200)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} lamports (base {}, priority {})",
+            self.total_lamports(),
+            self.base_fee_lamports,
+            self.priority_fee_lamports
+        )
+    }
+}
+
+/// What the gas tank paid for a sponsored transaction, in lamports, when the backend reports
+/// it; see [`SponsoredTx::fee_breakdown`](crate::SponsoredTx::fee_breakdown) and
+/// [`TxRecord::fee_breakdown`](crate::TxRecord::fee_breakdown).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Signature fee
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub base_fee: u64,
+    /// Compute-unit price times the compute units consumed
+    #[serde(default, deserialize_with = "crate::lenient::u64")]
+    pub priority_fee: u64,
+    /// Rent the gas tank deposited for accounts the transaction created
+    #[serde(default, deserialize_with = "crate::lenient::u64")]
+    pub rent: u64,
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct EstimateFeeRequest<'a> {
+    transaction: &'a str,
+    #[serde(rename = "rpcOrNetwork", skip_serializing_if = "Option::is_none")]
+    rpc_or_network: Option<&'a str>,
+}
+
+impl Aethokit {
+    /// Estimate what sponsoring `tx` would cost the gas tank, without submitting it, so
+    /// callers can decide whether to sponsor or have the user pay.
+    ///
+    /// The estimate reflects the compute budget the server would set, so it can be higher
+    /// than the fee implied by `tx` alone.
+    pub async fn estimate_fee(&self, tx: &str) -> Result<FeeEstimate, AethokitError> {
+        let body = EstimateFeeRequest {
+            transaction: tx.trim(),
+            rpc_or_network: self.rpc_or_network.as_deref(),
+        };
+        self.make_request("estimate-fee", Method::POST, Some(&body)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::FeeEstimate;
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Estimates render as the total followed by its breakdown.
+    #[test]
+    fn displays_fee_estimate() {
+        let estimate = FeeEstimate {
+            base_fee_lamports: 5000,
+            priority_fee_lamports: 1200,
+        };
+        assert_eq!(estimate.to_string(), "6200 lamports (base 5000, priority 1200)");
+    }
+
+    /// The transaction is posted as given and the total adds both fees.
+    #[tokio::test]
+    async fn estimates_fee() {
+        let backend = FakeBackend::new();
+        let body = json!({ "baseFee": 5000, "priorityFee": 1200 });
+        backend.on("POST", "estimate-fee", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let estimate = client.estimate_fee(" AQID ").await.unwrap();
+        assert_eq!(estimate.total_lamports(), 6200);
+        assert_eq!(backend.requests()[0].body, Some(json!({ "transaction": "AQID" })));
+    }
+
+    /// Sponsorships carry the backend's fee breakdown when it sends one.
+    #[tokio::test]
+    async fn reads_fee_breakdown() {
+        let backend = FakeBackend::new();
+        let breakdown = json!({ "baseFee": 5000, "priorityFee": "1200", "rent": 2039280, "total": 2045480 });
+        backend.on(
+            "POST",
+            "sponsor-tx",
+            FakeResponse::json(200, json!({ "hash": "sig", "feeBreakdown": breakdown })),
+        );
+        let client = backend.client().build().unwrap();
+
+        let sponsored = client.sponsor_tx_detailed("tx".to_string()).await.unwrap();
+        let expected = super::FeeBreakdown { base_fee: 5000, priority_fee: 1200, rent: 2_039_280, total: 2_045_480 };
+        assert_eq!(sponsored.fee_breakdown, Some(expected));
+    }
+}
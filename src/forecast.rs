@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::clock::Time;
+use crate::Aethokit;
+
+/// Most recent sponsorships kept for forecasting.
+const SPEND_LOG_CAPACITY: usize = 4096;
+
+/// Callback of [`AethokitBuilder::depletion_warning`](crate::AethokitBuilder::depletion_warning)
+/// and the forecast it watches.
+#[derive(Clone)]
+pub(crate) struct DepletionWarning {
+    pub(crate) threshold: Duration,
+    pub(crate) window: Duration,
+    pub(crate) callback: Arc<dyn Fn(DepletionForecast) + Send + Sync>,
+}
+
+impl fmt::Debug for DepletionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DepletionWarning")
+            .field("threshold", &self.threshold)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Fees of recent sponsorships made through a client, shared between its clones.
+#[derive(Debug, Default)]
+pub(crate) struct SpendLog {
+    time: Time,
+    warning: Option<DepletionWarning>,
+    state: Mutex<SpendState>,
+}
+
+#[derive(Debug, Default)]
+struct SpendState {
+    spends: VecDeque<(Instant, u64)>,
+    /// Last balance read from the tank, less the fees recorded since
+    balance: Option<u64>,
+    /// Set while the forecast is within the warning threshold, so it fires once per drop
+    warned: bool,
+}
+
+impl SpendLog {
+    pub(crate) fn new(time: Time, warning: Option<DepletionWarning>) -> Self {
+        Self {
+            time,
+            warning,
+            state: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn record(&self, fee_lamports: u64) {
+        let now = self.time.now();
+        let mut state = self.state.lock().unwrap();
+        if state.spends.len() == SPEND_LOG_CAPACITY {
+            state.spends.pop_front();
+        }
+        state.spends.push_back((now, fee_lamports));
+        state.balance = state.balance.map(|balance| balance.saturating_sub(fee_lamports));
+        let (Some(warning), Some(balance)) = (&self.warning, state.balance) else {
+            return;
+        };
+        let forecast = forecast(&state.spends, balance, warning.window, now);
+        let below = forecast.depletes_within(warning.threshold);
+        let fire = below && !state.warned;
+        state.warned = below;
+        drop(state);
+        if fire {
+            (warning.callback)(forecast);
+        }
+    }
+
+    /// Note the tank's current balance, e.g. from an RPC read or a balance event.
+    pub(crate) fn observe_balance(&self, balance_lamports: u64) {
+        self.state.lock().unwrap().balance = Some(balance_lamports);
+    }
+
+    fn forecast(&self, balance_lamports: u64, window: Duration) -> DepletionForecast {
+        forecast(&self.state.lock().unwrap().spends, balance_lamports, window, self.time.now())
+    }
+}
+
+fn forecast(
+    spends: &VecDeque<(Instant, u64)>,
+    balance_lamports: u64,
+    window: Duration,
+    now: Instant,
+) -> DepletionForecast {
+    let spent: u64 = spends
+        .iter()
+        .filter(|(at, _)| now.saturating_duration_since(*at) <= window)
+        .map(|(_, fee)| fee)
+        .sum();
+    let burn = if window.is_zero() {
+        0.0
+    } else {
+        spent as f64 / window.as_secs_f64()
+    };
+    DepletionForecast {
+        balance_lamports,
+        burn_lamports_per_sec: burn,
+        depletes_in: (burn > 0.0).then(|| Duration::from_secs_f64(balance_lamports as f64 / burn)),
+    }
+}
+
+/// When the gas tank runs dry at the current burn rate, from [`Aethokit::forecast_depletion`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepletionForecast {
+    pub balance_lamports: u64,
+    /// Average spend over the forecast window
+    pub burn_lamports_per_sec: f64,
+    /// Time until the balance reaches zero, `None` if nothing was spent in the window
+    pub depletes_in: Option<Duration>,
+}
+
+impl DepletionForecast {
+    /// `true` if the tank is forecast to run dry within `threshold`, e.g. to raise a warning.
+    pub fn depletes_within(&self, threshold: Duration) -> bool {
+        self.depletes_in.is_some_and(|left| left <= threshold)
+    }
+}
+
+impl Aethokit {
+    /// Forecast when a tank holding `balance_lamports` runs dry, at the rate this client
+    /// and its clones spent over the last `window`.
+    ///
+    /// Only counts sponsorships whose fee the backend reported (see
+    /// [`SponsoredTx::fee_lamports`](crate::SponsoredTx::fee_lamports)), from the last
+    /// few thousand made. To be warned when a sponsorship brings the forecast close, see
+    /// [`AethokitBuilder::depletion_warning`](crate::AethokitBuilder::depletion_warning).
+    pub fn forecast_depletion(&self, balance_lamports: u64, window: Duration) -> DepletionForecast {
+        self.spend.forecast(balance_lamports, window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{FakeBackend, FakeResponse, MockClock};
+
+    /// The burn rate covers only spends within the window.
+    #[test]
+    fn forecasts_from_recent_spend() {
+        let clock = Arc::new(MockClock::new());
+        let time = Time {
+            clock: clock.clone(),
+            sleeper: clock.clone(),
+        };
+        let log = SpendLog::new(time, None);
+        log.record(1_000);
+        log.record(2_000);
+        clock.advance(Duration::from_secs(10));
+        let forecast = log.forecast(30_000, Duration::from_secs(60));
+        assert_eq!(forecast.burn_lamports_per_sec, 50.0);
+        assert_eq!(forecast.depletes_in, Some(Duration::from_secs(600)));
+        assert!(forecast.depletes_within(Duration::from_secs(600)));
+        assert!(!forecast.depletes_within(Duration::from_secs(599)));
+
+        let idle = log.forecast(30_000, Duration::from_secs(5));
+        assert_eq!(idle.depletes_in, None);
+    }
+
+    /// The warning fires once when a sponsorship brings the forecast from the last seen
+    /// balance within the threshold, and not again while it stays there.
+    #[tokio::test]
+    async fn warns_when_forecast_drops_below_threshold() {
+        let backend = FakeBackend::new();
+        let event = json!({
+            "type": "balance.changed",
+            "gasAddress": "Gas111",
+            "balance": 100_000,
+            "timestamp": "2024-05-01T12:00:00Z",
+        });
+        backend
+            .on("GET", "events", FakeResponse::text(200, format!("data: {event}\n\n")))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig", "feeLamports": 30_000 })));
+        let clock = Arc::new(MockClock::new());
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let seen = warnings.clone();
+        let client = backend
+            .client()
+            .clock(clock.clone())
+            .sleeper(clock.clone())
+            .depletion_warning(Duration::from_secs(10), Duration::from_secs(10), move |forecast| {
+                seen.lock().unwrap().push(forecast)
+            })
+            .build()
+            .unwrap();
+
+        // nothing fires before a balance is known
+        client.sponsor_tx_detailed("AQID".to_string()).await.unwrap();
+        clock.advance(Duration::from_secs(11));
+        futures_util::StreamExt::collect::<Vec<_>>(client.subscribe_events().await.unwrap()).await;
+
+        // 30k over 10s burns 3k/s so 70k lasts 23s, then 40k at 6k/s lasts 7s
+        client.sponsor_tx_detailed("AQID".to_string()).await.unwrap();
+        assert!(warnings.lock().unwrap().is_empty());
+        client.sponsor_tx_detailed("AQID".to_string()).await.unwrap();
+        client.sponsor_tx_detailed("AQID".to_string()).await.unwrap();
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].balance_lamports, 40_000);
+        assert_eq!(warnings[0].burn_lamports_per_sec, 6_000.0);
+    }
+}
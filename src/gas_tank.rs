@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+#[cfg(feature = "solana")]
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{Aethokit, AethokitError, BalanceChanged, ListParams, SponsorEvent, TankId, TxPage};
+
+/// Gas addresses fetched at a time by [`Aethokit::get_gas_addresses`].
+const GAS_ADDRESS_CONCURRENCY: usize = 16;
+/// Cache entry kind of the tank's identity, see [`CacheStore`](crate::CacheStore).
+const TANK_INFO_CACHE: &str = "tank-info";
+
+/// Identity of the gas tank behind a gas key, from [`Aethokit::get_tank_info`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TankInfo {
+    pub id: TankId,
+    /// Name given to the tank in the dashboard
+    pub name: String,
+    /// Network the tank sponsors on, e.g. `mainnet-beta`
+    pub network: String,
+}
+
+/// Handle grouping the operations on the client's gas tank, from [`Aethokit::gas_tank`].
+///
+/// The tank's address is cached on the client for the builder's
+/// [`gas_address_ttl`](crate::AethokitBuilder::gas_address_ttl), shared by every handle and
+/// clone of the client; [`refresh`](Self::refresh) fetches it again.
+#[derive(Debug, Clone)]
+pub struct GasTank {
+    client: Aethokit,
+}
+
+impl Aethokit {
+    /// Handle on the gas tank of the client's gas key.
+    pub fn gas_tank(&self) -> GasTank {
+        GasTank {
+            client: self.clone(),
+        }
+    }
+
+    /// Gas addresses of many gas keys, e.g. every customer tank of a platform at startup,
+    /// fetched 16 at a time with this client's settings.
+    ///
+    /// Addresses already in the client's [`CacheStore`](crate::CacheStore) aren't fetched
+    /// again, and the fetched ones are cached for later clients of the same keys. Each key
+    /// gets its own result, so one revoked key doesn't hide the others.
+    pub async fn get_gas_addresses<I, S>(&self, gas_keys: I) -> HashMap<String, Result<String, AethokitError>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        stream::iter(gas_keys)
+            .map(|gas_key| async move {
+                let gas_key = gas_key.into();
+                let result = match gas_key.trim() {
+                    "" => Err(AethokitError::MissingGasKey),
+                    key => self.with_gas_key(key.to_string()).get_gas_address().await,
+                };
+                (gas_key, result)
+            })
+            .buffer_unordered(GAS_ADDRESS_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Short fingerprint of the gas key in use, `sha256:` and 12 hex digits, the same for
+    /// every client of the key. Safe to log and to label metrics with, unlike the key.
+    pub fn key_fingerprint(&self) -> String {
+        crate::audit::key_fingerprint(&self.gas_key.get())
+    }
+
+    /// ID, name and network of the gas key's tank, to tell in logs and metrics which tank
+    /// served a request. Cached like the gas address, see
+    /// [`gas_address_ttl`](crate::AethokitBuilder::gas_address_ttl).
+    pub async fn get_tank_info(&self) -> Result<TankInfo, AethokitError> {
+        let cached = self.cache_get(TANK_INFO_CACHE).await;
+        if let Some(info) = cached.and_then(|info| serde_json::from_str(&info).ok()) {
+            return Ok(info);
+        }
+        let info: TankInfo = self
+            .make_request::<(), _>("get-tank-info", Method::GET, None)
+            .await?;
+        self.cache_set(TANK_INFO_CACHE, serde_json::to_string(&info)?, self.gas_address_ttl)
+            .await;
+        Ok(info)
+    }
+
+    /// ID of the gas key's tank, see [`get_tank_info`](Self::get_tank_info).
+    pub async fn tank_id(&self) -> Result<TankId, AethokitError> {
+        Ok(self.get_tank_info().await?.id)
+    }
+
+    /// Shorthand for [`GasTank::watch_balance`] on [`gas_tank`](Self::gas_tank).
+    #[cfg(feature = "solana")]
+    pub fn watch_gas_balance(
+        &self,
+        interval: Duration,
+        threshold_lamports: u64,
+    ) -> impl Stream<Item = Result<u64, AethokitError>> {
+        self.gas_tank().watch_balance(interval, threshold_lamports)
+    }
+}
+
+impl GasTank {
+    /// Address of the tank, the fee payer of its sponsored transactions.
+    pub async fn address(&self) -> Result<String, AethokitError> {
+        self.client.get_gas_address().await
+    }
+
+    /// ID, name and network of the tank, see [`Aethokit::get_tank_info`].
+    pub async fn info(&self) -> Result<TankInfo, AethokitError> {
+        self.client.get_tank_info().await
+    }
+
+    /// Fetch the tank's address again, replacing the cached one.
+    pub async fn refresh(&self) -> Result<String, AethokitError> {
+        self.client.refresh_gas_address().await
+    }
+
+    /// Balance of the tank, in lamports, read from the client's Solana RPC endpoint.
+    #[cfg(feature = "solana")]
+    pub async fn balance(&self) -> Result<u64, AethokitError> {
+        let address = self.address().await?;
+        let result = self
+            .client
+            .rpc_call("getBalance", serde_json::json!([address]))
+            .await?;
+        let balance = result["value"]
+            .as_u64()
+            .ok_or_else(|| AethokitError::Rpc("response is missing a balance".to_string()))?;
+        self.client.spend.observe_balance(balance);
+        Ok(balance)
+    }
+
+    /// Poll the tank's [`balance`](Self::balance) every `interval` and yield it each time it
+    /// drops below `threshold_lamports`, e.g. to trigger an automatic top-up.
+    ///
+    /// Yields once per drop: the balance must climb back to the threshold before it is
+    /// reported again. Failed polls are yielded as errors and polling goes on; the stream
+    /// ends when the client's deadline passes or its cancellation token fires.
+    #[cfg(feature = "solana")]
+    pub fn watch_balance(
+        &self,
+        interval: Duration,
+        threshold_lamports: u64,
+    ) -> impl Stream<Item = Result<u64, AethokitError>> {
+        let state = (self.clone(), false, true);
+        futures_util::stream::unfold(state, move |(tank, mut below, mut first)| async move {
+            loop {
+                if !first {
+                    tank.client.scoped_sleep(interval).await.ok()?;
+                }
+                first = false;
+                match tank.balance().await {
+                    Err(AethokitError::Cancelled | AethokitError::DeadlineExceeded) => return None,
+                    Err(err) => return Some((Err(err), (tank, below, first))),
+                    Ok(balance) if crossed_below(&mut below, balance, threshold_lamports) => {
+                        return Some((Ok(balance), (tank, below, first)));
+                    }
+                    Ok(_) => {}
+                }
+            }
+        })
+    }
+
+    /// Fetch one page of transactions sponsored by the tank.
+    pub async fn history(&self, params: ListParams) -> Result<TxPage, AethokitError> {
+        self.client.list_sponsored_txs(params).await
+    }
+
+    /// Subscribe to the tank's balance changes, e.g. to alert when it runs low.
+    ///
+    /// Other sponsorship events are skipped; see [`Aethokit::subscribe_events`] for them.
+    pub async fn alerts(
+        &self,
+    ) -> Result<impl Stream<Item = Result<BalanceChanged, AethokitError>>, AethokitError> {
+        let events = self.client.subscribe_events().await?;
+        Ok(events.filter_map(|event| {
+            futures_util::future::ready(match event {
+                Ok(SponsorEvent::BalanceChanged(change)) => Some(Ok(change)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+        }))
+    }
+}
+
+/// Track whether the balance is below `threshold`, returning `true` when it just dropped below.
+#[cfg_attr(not(feature = "solana"), allow(dead_code))]
+fn crossed_below(below: &mut bool, balance: u64, threshold: u64) -> bool {
+    let was_below = std::mem::replace(below, balance < threshold);
+    *below && !was_below
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// The handle shares the client's gas address cache.
+    #[tokio::test]
+    async fn shares_address_cache() {
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url("http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let tank = client.gas_tank();
+        client.cache_set(crate::GAS_ADDRESS_CACHE, "tank".to_string(), Duration::from_secs(60)).await;
+        assert_eq!(tank.address().await.unwrap(), "tank");
+        assert!(tank.refresh().await.is_err());
+    }
+
+    /// The tank's identity is fetched once, and the fingerprint doesn't reveal the key.
+    #[tokio::test]
+    async fn identifies_tank() {
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse, FAKE_GAS_KEY};
+
+        let backend = FakeBackend::new();
+        let body = json!({ "id": "tank_1", "name": "checkout", "network": "devnet" });
+        backend.on("GET", "get-tank-info", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        assert_eq!(client.tank_id().await.unwrap().as_str(), "tank_1");
+        let info = client.gas_tank().info().await.unwrap();
+        assert_eq!((info.name.as_str(), info.network.as_str()), ("checkout", "devnet"));
+        assert_eq!(backend.requests().len(), 1);
+        let fingerprint = client.key_fingerprint();
+        assert!(fingerprint.starts_with("sha256:") && !fingerprint.contains(FAKE_GAS_KEY));
+        assert_eq!(fingerprint, client.clone().key_fingerprint());
+    }
+
+    /// Every key gets a result, and addresses fetched once are served from the cache.
+    #[tokio::test]
+    async fn looks_up_many_gas_addresses() {
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let backend = FakeBackend::new();
+        backend.on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "tank" })));
+        let client = backend.client().build().unwrap();
+
+        let addresses = client.get_gas_addresses(["a", "b", " "]).await;
+        assert_eq!(addresses.len(), 3);
+        assert_eq!(addresses["a"].as_deref().unwrap(), "tank");
+        assert_eq!(addresses["b"].as_deref().unwrap(), "tank");
+        assert!(matches!(addresses[" "], Err(AethokitError::MissingGasKey)));
+        client.get_gas_addresses(["b", "a"]).await;
+        assert_eq!(backend.requests().len(), 2);
+    }
+
+    /// A low balance is reported once per drop below the threshold.
+    #[test]
+    fn reports_each_drop_once() {
+        let mut below = false;
+        let drops: Vec<bool> = [100, 40, 30, 60, 49]
+            .into_iter()
+            .map(|balance| crossed_below(&mut below, balance, 50))
+            .collect();
+        assert_eq!(drops, vec![false, true, false, false, true]);
+    }
+}
@@ -0,0 +1,87 @@
+//! A process-wide client for scripts and small bots that don't want to pass an [`Aethokit`]
+//! through every function.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), aethokit::AethokitError> {
+//! aethokit::global::init(aethokit::AethokitConfig {
+//!     gas_key: std::env::var("AETHOKIT_GAS_KEY").unwrap_or_default(),
+//!     ..Default::default()
+//! })?;
+//! let address = aethokit::global::get_gas_address().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Libraries and services should build their own client instead, so tests and tenants can
+//! use different settings.
+
+use std::sync::OnceLock;
+
+use crate::{Aethokit, AethokitConfig, AethokitError};
+
+static CLIENT: OnceLock<Aethokit> = OnceLock::new();
+
+/// Initialize the global client from `config`.
+///
+/// # Errors
+/// - everything [`Aethokit::new`] fails with
+/// - `GlobalClient` if the global client is already initialized
+pub fn init(config: AethokitConfig) -> Result<&'static Aethokit, AethokitError> {
+    init_with(Aethokit::new(config)?)
+}
+
+/// Make `aethokit` the global client, e.g. one configured with [`Aethokit::builder`].
+///
+/// # Errors
+/// - `GlobalClient` if the global client is already initialized
+pub fn init_with(aethokit: Aethokit) -> Result<&'static Aethokit, AethokitError> {
+    CLIENT
+        .set(aethokit)
+        .map_err(|_| AethokitError::GlobalClient("already initialized"))?;
+    client()
+}
+
+/// The global client.
+///
+/// # Errors
+/// - `GlobalClient` if [`init`] hasn't been called
+pub fn client() -> Result<&'static Aethokit, AethokitError> {
+    CLIENT
+        .get()
+        .ok_or(AethokitError::GlobalClient("not initialized, call `aethokit::global::init` first"))
+}
+
+/// [`Aethokit::sponsor_tx`] on the global client.
+pub async fn sponsor_tx(tx: String) -> Result<String, AethokitError> {
+    client()?.sponsor_signature(tx).await
+}
+
+/// [`Aethokit::get_gas_address`] on the global client.
+pub async fn get_gas_address() -> Result<String, AethokitError> {
+    client()?.get_gas_address().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Calls fail until the client is initialized, which only works once.
+    #[tokio::test]
+    async fn initializes_once() {
+        assert!(matches!(get_gas_address().await, Err(AethokitError::GlobalClient(_))));
+        let backend = FakeBackend::new();
+        backend
+            .on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "tank" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        init_with(backend.client().build().unwrap()).unwrap();
+
+        assert_eq!(get_gas_address().await.unwrap(), "tank");
+        assert_eq!(sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        let again = backend.client().build().unwrap();
+        assert!(matches!(init_with(again), Err(AethokitError::GlobalClient(_))));
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use reqwest::{Method, StatusCode};
+
+use crate::{Aethokit, AethokitError, ResponseMeta};
+
+impl Aethokit {
+    /// Open a pooled connection to the API with a `HEAD` request to the base URL, so the next
+    /// request skips the TCP and TLS handshakes; see
+    /// [`AethokitBuilder::eager_connect`](crate::AethokitBuilder::eager_connect).
+    ///
+    /// The gas key is not sent and any answer counts, only reaching the server matters.
+    /// Worth repeating after the idle timeout on services that sit idle for long.
+    ///
+    /// # Errors
+    /// - `Http` if the API cannot be reached
+    pub async fn warm_up(&self) -> Result<(), AethokitError> {
+        let request = reqwest::Request::new(Method::HEAD, self.base_url.clone());
+        self.within_scope(async { self.transport.0.execute(request).await.map(drop) })
+            .await
+    }
+
+    /// Check the backend is reachable, returning the round-trip time.
+    ///
+    /// Hits the `health` endpoint, which does not check the gas key;
+    /// see [`validate_key`](Self::validate_key) for that.
+    ///
+    /// # Errors
+    /// - `Http` if the backend cannot be reached
+    /// - `RateLimited` if it answers 429, with its retry-after
+    /// - `UnexpectedStatus` if it answers with another non-success status
+    pub async fn ping(&self) -> Result<Duration, AethokitError> {
+        let url = self.join_url("health")?;
+        let check = async {
+            self.load_gas_key().await?;
+            let started = Instant::now();
+            let req = self.build_request(self.request(Method::GET, url))?;
+            let res = self.middleware.send(&*self.transport.0, req).await?;
+            let status = res.status();
+            let meta = ResponseMeta::new(status, res.headers(), started.elapsed());
+            self.observe_response("health", res.headers(), &meta);
+            if !status.is_success() {
+                let body = res.text().await?;
+                return Err(AethokitError::from_status(status, Method::GET, "health", body, meta));
+            }
+            Ok(started.elapsed())
+        };
+        self.within_scope(check).await
+    }
+
+    /// Check the gas key is valid and active, so a service can fail fast at startup.
+    ///
+    /// Returns `false` when the backend rejects the key (401 or 403). Fetches the gas
+    /// address, refreshing the cached one.
+    ///
+    /// # Errors
+    /// - Any other error from [`get_gas_address`](Self::get_gas_address), e.g. the backend
+    ///   being unreachable
+    pub async fn validate_key(&self) -> Result<bool, AethokitError> {
+        match self.refresh_gas_address().await {
+            Ok(_) => Ok(true),
+            Err(err) if matches!(err.status(), Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures_util::future::BoxFuture;
+    use reqwest::{Request, Response};
+
+    use crate::{Aethokit, AethokitError, Middleware, Next};
+
+    /// The warm-up's connection is reused by the first request.
+    #[tokio::test]
+    async fn reuses_warmed_up_connection() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/api/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut request_lines = Vec::new();
+            let responses = [String::new(), r#"{"gasAddress":"tank"}"#.to_string()];
+            for body in responses {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                request_lines.push(line.trim_end().to_string());
+                while line != "\r\n" {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                }
+                let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{body}", body.len());
+                writer.write_all(response.as_bytes()).unwrap();
+            }
+            request_lines
+        });
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url(base_url)
+            .eager_connect(true)
+            .connect()
+            .await
+            .unwrap();
+
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        let lines = server.join().unwrap();
+        assert_eq!(lines, ["HEAD /api/ HTTP/1.1", "GET /api/get-gas-address HTTP/1.1"]);
+    }
+
+    /// A rejected key is reported as invalid rather than as an error.
+    #[tokio::test]
+    async fn reports_revoked_key() {
+        struct Revoked;
+        impl Middleware for Revoked {
+            fn handle<'a>(&'a self, _request: Request, _next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+                let response = http::Response::builder().status(401).body("revoked").unwrap();
+                Box::pin(async { Ok(Response::from(response)) })
+            }
+        }
+
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .middleware(Arc::new(Revoked))
+            .build()
+            .unwrap();
+        assert!(!client.validate_key().await.unwrap());
+        assert!(client.ping().await.unwrap_err().is_client_error());
+    }
+}
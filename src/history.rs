@@ -0,0 +1,405 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::endpoint::endpoint;
+use crate::{Aethokit, AethokitError, FeeBreakdown, ResponseMeta};
+
+/// Lifecycle state of a sponsored transaction as recorded in the history API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryStatus {
+    Pending,
+    Confirmed,
+    Finalized,
+    Failed,
+}
+
+impl HistoryStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HistoryStatus::Pending => "pending",
+            HistoryStatus::Confirmed => "confirmed",
+            HistoryStatus::Finalized => "finalized",
+            HistoryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for HistoryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Filters and paging options for [`Aethokit::list_sponsored_txs`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListParams {
+    /// Cursor returned as `next_cursor` by the previous page
+    pub cursor: Option<String>,
+    /// Maximum number of records per page
+    pub limit: Option<u32>,
+    /// Only return transactions in this state
+    pub status: Option<HistoryStatus>,
+    /// Only return transactions sponsored at or after this time
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// A single sponsored transaction from the history API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxRecord {
+    /// Transaction hash (signature)
+    pub hash: String,
+    /// Slot the transaction landed in, if it has landed
+    #[serde(default, deserialize_with = "crate::lenient::option_u64")]
+    pub slot: Option<u64>,
+    /// Fee paid by the gas tank, in lamports
+    #[serde(rename = "feePaid", deserialize_with = "crate::lenient::u64")]
+    pub fee_paid: u64,
+    /// Time the transaction was sponsored
+    pub timestamp: DateTime<Utc>,
+    pub status: HistoryStatus,
+    /// What the fee was spent on, if the backend reports it
+    #[serde(default, rename = "feeBreakdown", skip_serializing_if = "Option::is_none")]
+    pub fee_breakdown: Option<FeeBreakdown>,
+}
+
+impl fmt::Display for TxRecord {
+    /// One-line summary, e.g. `5Kd3...9xQ confirmed, fee 5000 lamports, slot 1234 at 2024-05-01T12:00:00Z`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}, fee {} lamports", self.hash, self.status, self.fee_paid)?;
+        if let Some(slot) = self.slot {
+            write!(f, ", slot {slot}")?;
+        }
+        write!(f, " at {}", self.timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+/// One page of sponsorship history.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxPage {
+    #[serde(rename = "transactions")]
+    pub records: Vec<TxRecord>,
+    /// Cursor for the next page, `None` on the last page
+    #[serde(rename = "nextCursor", default)]
+    pub next_cursor: Option<String>,
+}
+
+/// How long a history lookup may wait for a recently submitted transaction to be indexed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Consistency {
+    /// Return whatever the history API has right now.
+    #[default]
+    Eventual,
+    /// Keep looking up a transaction that is not indexed yet, for up to `max_wait`.
+    ///
+    /// Use this right after sponsoring, when the history API may briefly lag behind submission.
+    ReadYourWrites { max_wait: Duration },
+}
+
+const VISIBILITY_POLL_START: Duration = Duration::from_millis(250);
+const VISIBILITY_POLL_MAX: Duration = Duration::from_secs(2);
+/// Longest line of an NDJSON export kept in memory; no record comes close.
+const MAX_NDJSON_LINE: usize = 1 << 20;
+
+impl Aethokit {
+    /// Look up a single sponsored transaction by hash. Returns `None` if the history API
+    /// doesn't know it (yet).
+    pub async fn get_sponsored_tx(
+        &self,
+        hash: &str,
+        consistency: Consistency,
+    ) -> Result<Option<TxRecord>, AethokitError> {
+        let deadline = match consistency {
+            Consistency::Eventual => None,
+            Consistency::ReadYourWrites { max_wait } => Some(self.time.now() + max_wait),
+        };
+        let mut interval = VISIBILITY_POLL_START;
+        loop {
+            if let Some(record) = self.lookup_sponsored_tx(hash).await? {
+                return Ok(Some(record));
+            }
+            match deadline {
+                Some(deadline) if self.time.now() + interval <= deadline => {
+                    self.scoped_sleep(interval).await?;
+                    interval = (interval * 2).min(VISIBILITY_POLL_MAX);
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    pub(crate) async fn lookup_sponsored_tx(&self, hash: &str) -> Result<Option<TxRecord>, AethokitError> {
+        let path = endpoint("get-sponsored-tx", &[], &json!({ "hash": hash }))?;
+        match self.make_request::<(), TxRecord>(&path, Method::GET, None).await {
+            Ok(record) => Ok(Some(record)),
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetch one page of transactions sponsored by the gas tank.
+    pub async fn list_sponsored_txs(&self, params: ListParams) -> Result<TxPage, AethokitError> {
+        let path = endpoint("get-sponsored-txs", &[], &params)?;
+        self.make_request::<(), TxPage>(&path, Method::GET, None)
+            .await
+    }
+
+    /// Stream every sponsored transaction matching `params`, following `next_cursor` across pages.
+    ///
+    /// Pages are fetched lazily as the stream is polled. The stream ends after the last page,
+    /// or yields the error and stops if a page request fails.
+    pub fn paginate_sponsored_txs(
+        &self,
+        params: ListParams,
+    ) -> impl Stream<Item = Result<TxRecord, AethokitError>> {
+        let start = Some((self.clone(), params));
+        stream::try_unfold(start, |state| async move {
+            let Some((client, params)) = state else {
+                return Ok::<_, AethokitError>(None);
+            };
+            let page = client.list_sponsored_txs(params.clone()).await?;
+            let next = page.next_cursor.map(|cursor| {
+                let params = ListParams {
+                    cursor: Some(cursor),
+                    ..params
+                };
+                (client, params)
+            });
+            let records = stream::iter(page.records.into_iter().map(Ok));
+            Ok(Some((records, next)))
+        })
+        .try_flatten()
+    }
+
+    /// Stream every sponsored transaction matching `params` from the backend's NDJSON export,
+    /// for exports too large to page through. `cursor` and `limit` are ignored.
+    ///
+    /// Records are parsed line by line as they arrive, so memory stays bounded however long
+    /// the export is. The stream yields a parse error and carries on with the next line,
+    /// and stops after a transport error.
+    ///
+    /// # Errors
+    /// - `RateLimited` if the server answers 429, with its retry-after
+    /// - `UnexpectedStatus` if the server rejects the export otherwise
+    pub async fn list_sponsored_txs_stream(
+        &self,
+        params: ListParams,
+    ) -> Result<impl Stream<Item = Result<TxRecord, AethokitError>>, AethokitError> {
+        let params = ListParams { cursor: None, limit: None, ..params };
+        let path = endpoint("export-sponsored-txs", &[], &params)?;
+        let url = self.join_url(&path)?;
+        let connect = async {
+            self.load_gas_key().await?;
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+            let req = self.build_request(self.request(Method::GET, url).header("accept", "application/x-ndjson"))?;
+            self.middleware.send(&*self.transport.0, req).await
+        };
+        let started = self.time.now();
+        let res = self.within_scope(connect).await?;
+        let status = res.status();
+        let meta = ResponseMeta::new(status, res.headers(), self.time.now() - started);
+        self.observe_response(&path, res.headers(), &meta);
+        if !status.is_success() {
+            let body = res.text().await?;
+            return Err(AethokitError::from_status(status, Method::GET, &path, body, meta));
+        }
+
+        let body = res.bytes_stream().boxed();
+        let records = stream::unfold(Some((body, NdjsonDecoder::default())), |state| async move {
+            let (mut body, mut decoder) = state?;
+            let (lines, next) = match body.next().await {
+                Some(Ok(chunk)) => (decoder.push(&chunk), Some((body, decoder))),
+                Some(Err(err)) => (vec![Err(AethokitError::from(err))], None),
+                None => (decoder.finish().into_iter().collect(), None),
+            };
+            Some((stream::iter(lines), next))
+        })
+        .flatten()
+        .map(|line| serde_json::from_str(&line?).map_err(AethokitError::from));
+        Ok(records)
+    }
+}
+
+/// Splits an NDJSON body into lines, keeping only the incomplete last one between chunks.
+#[derive(Debug, Default)]
+struct NdjsonDecoder {
+    buf: Vec<u8>,
+}
+
+impl NdjsonDecoder {
+    fn push(&mut self, chunk: &[u8]) -> Vec<Result<String, AethokitError>> {
+        self.buf.extend_from_slice(chunk);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            lines.extend(Self::line(&line));
+        }
+        if self.buf.len() > MAX_NDJSON_LINE {
+            self.buf.clear();
+            let err = <serde_json::Error as serde::de::Error>::custom("NDJSON export line over 1 MiB");
+            lines.push(Err(err.into()));
+        }
+        lines
+    }
+
+    /// The last line, if the body doesn't end with a newline.
+    fn finish(&mut self) -> Option<Result<String, AethokitError>> {
+        Self::line(&std::mem::take(&mut self.buf))
+    }
+
+    fn line(line: &[u8]) -> Option<Result<String, AethokitError>> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        (!line.is_empty()).then(|| Ok(line.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use serde_json::Value;
+
+    use crate::test_util::{FakeBackend, FakeResponse, MockClock};
+
+    fn record_json(hash: &str) -> Value {
+        json!({ "hash": hash, "feePaid": 5000, "timestamp": "2024-05-01T12:00:00Z", "status": "pending" })
+    }
+
+    /// Read-your-writes keeps polling, backing off, until the transaction is indexed.
+    #[tokio::test]
+    async fn waits_for_sponsored_tx_to_be_indexed() {
+        let backend = FakeBackend::new();
+        let missing = || FakeResponse::json(404, json!({ "error": "not found" }));
+        backend
+            .on("GET", "get-sponsored-tx", missing())
+            .on("GET", "get-sponsored-tx", missing())
+            .on("GET", "get-sponsored-tx", FakeResponse::json(200, record_json("sig")));
+        let clock = Arc::new(MockClock::new());
+        let client = backend.client().clock(clock.clone()).sleeper(clock.clone()).build().unwrap();
+
+        let consistency = Consistency::ReadYourWrites { max_wait: Duration::from_secs(10) };
+        let record = client.get_sponsored_tx("sig", consistency).await.unwrap();
+        assert_eq!(record.map(|record| record.hash).as_deref(), Some("sig"));
+        assert_eq!(backend.requests().len(), 3);
+        assert_eq!(clock.elapsed(), Duration::from_millis(750));
+    }
+
+    /// A transaction still missing at the deadline comes back as `None`, and the default
+    /// consistency doesn't wait at all.
+    #[tokio::test]
+    async fn gives_up_on_sponsored_tx_at_deadline() {
+        let backend = FakeBackend::new();
+        backend.on("GET", "get-sponsored-tx", FakeResponse::json(404, json!({ "error": "not found" })));
+        let clock = Arc::new(MockClock::new());
+        let client = backend.client().clock(clock.clone()).sleeper(clock.clone()).build().unwrap();
+
+        assert_eq!(client.get_sponsored_tx("sig", Consistency::default()).await.unwrap(), None);
+        assert_eq!((backend.requests().len(), clock.elapsed()), (1, Duration::ZERO));
+
+        let consistency = Consistency::ReadYourWrites { max_wait: Duration::from_secs(3) };
+        assert_eq!(client.get_sponsored_tx("sig", consistency).await.unwrap(), None);
+        assert!(clock.elapsed() <= Duration::from_secs(3));
+        // 250ms, 500ms, 1s, then the next 2s poll would pass the deadline
+        assert_eq!(backend.requests().len(), 5);
+    }
+
+    /// A rate-limited export reports the server's retry-after like other endpoints.
+    #[tokio::test]
+    async fn rate_limited_export() {
+        let backend = FakeBackend::new();
+        let limited = FakeResponse::json(429, json!({ "error": "slow down" })).header("retry-after", "3");
+        backend.on("GET", "export-sponsored-txs", limited);
+        let client = backend.client().build().unwrap();
+
+        match client.list_sponsored_txs_stream(ListParams::default()).await.map(drop) {
+            Err(AethokitError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(3)))
+            }
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+    }
+
+    /// Records are read line by line, across chunk boundaries and without a final newline.
+    #[tokio::test]
+    async fn streams_ndjson_export() {
+        let record = |hash: &str| {
+            format!(r#"{{"hash":"{hash}","feePaid":5000,"timestamp":"2024-05-01T12:00:00Z","status":"confirmed"}}"#)
+        };
+        let mut decoder = NdjsonDecoder::default();
+        let line = record("a");
+        let (head, tail) = line.split_at(10);
+        assert!(decoder.push(head.as_bytes()).is_empty());
+        assert_eq!(decoder.push(format!("{tail}\n\n").as_bytes()).len(), 1);
+
+        let backend = FakeBackend::new();
+        let body = format!("{}\n{}\nnot json\n{}", record("a"), record("b"), record("c"));
+        backend.on("GET", "export-sponsored-txs", FakeResponse::text(200, body));
+        let client = backend.client().build().unwrap();
+        let params = ListParams { status: Some(HistoryStatus::Confirmed), ..Default::default() };
+
+        let records: Vec<_> = client.list_sponsored_txs_stream(params).await.unwrap().collect().await;
+        let hashes: Vec<_> = records.iter().map(|r| r.as_ref().ok().map(|r| r.hash.as_str())).collect();
+        assert_eq!(hashes, [Some("a"), Some("b"), None, Some("c")]);
+        assert_eq!(backend.requests()[0].path, "export-sponsored-txs?status=confirmed");
+    }
+
+    /// Only the filters that are set end up in the query string, URL-encoded.
+    #[test]
+    fn encodes_list_params() {
+        let params = ListParams {
+            cursor: Some("abc/def".to_string()),
+            limit: Some(50),
+            status: Some(HistoryStatus::Failed),
+            since: None,
+        };
+        let path = |params: &ListParams| endpoint("txs", &[], params).unwrap();
+        assert_eq!(path(&params), "txs?cursor=abc%2Fdef&limit=50&status=failed");
+        assert_eq!(path(&ListParams::default()), "txs");
+        let since = ListParams {
+            since: Some("2024-05-01T14:00:00+02:00".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(path(&since), "txs?since=2024-05-01T12%3A00%3A00Z");
+    }
+
+    /// Timestamps and amounts survive a serde round trip unchanged.
+    #[test]
+    fn round_trips_tx_record() {
+        let json = r#"{"hash":"5Kd3","slot":1234,"feePaid":5000,"timestamp":"2024-05-01T12:00:00.250Z","status":"finalized"}"#;
+        let record: TxRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.timestamp.timestamp_millis(), 1_714_564_800_250);
+        assert_eq!(serde_json::to_string(&record).unwrap(), json);
+        assert!(serde_json::from_str::<TxRecord>(&json.replace("2024-05-01T12:00:00.250Z", "yesterday")).is_err());
+    }
+
+    /// Records render as a one-line summary, omitting the slot until the transaction lands.
+    #[test]
+    fn displays_tx_record() {
+        let mut record = TxRecord {
+            hash: "5Kd3".to_string(),
+            slot: Some(1234),
+            fee_paid: 5_000,
+            timestamp: "2024-05-01T12:00:00Z".parse().unwrap(),
+            status: HistoryStatus::Confirmed,
+            fee_breakdown: None,
+        };
+        assert_eq!(
+            record.to_string(),
+            "5Kd3 confirmed, fee 5000 lamports, slot 1234 at 2024-05-01T12:00:00Z"
+        );
+        record.slot = None;
+        record.status = HistoryStatus::Pending;
+        assert_eq!(record.to_string(), "5Kd3 pending, fee 5000 lamports at 2024-05-01T12:00:00Z");
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+/// An API request about to be sent, passed to [`AethokitBuilder::on_request`](crate::AethokitBuilder::on_request).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestInfo {
+    pub method: Method,
+    pub url: Url,
+    /// 1 for the first attempt, counting up with each retry
+    pub attempt: u32,
+}
+
+/// The outcome of an API request, passed to [`AethokitBuilder::on_response`](crate::AethokitBuilder::on_response).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseInfo {
+    pub method: Method,
+    pub url: Url,
+    pub attempt: u32,
+    /// `None` if no response was received, e.g. on a connection error or timeout
+    pub status: Option<StatusCode>,
+    /// Time from sending the request to reading the whole response or failing
+    pub latency: Duration,
+}
+
+type RequestHook = Arc<dyn Fn(&RequestInfo) + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&ResponseInfo) + Send + Sync>;
+
+/// Request and response hooks set on the builder, shared by the clients it builds.
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    pub(crate) on_request: Option<RequestHook>,
+    pub(crate) on_response: Option<ResponseHook>,
+}
+
+impl Hooks {
+    pub(crate) fn request(&self, info: impl FnOnce() -> RequestInfo) {
+        if let Some(hook) = &self.on_request {
+            hook(&info());
+        }
+    }
+
+    pub(crate) fn response(&self, info: impl FnOnce() -> ResponseInfo) {
+        if let Some(hook) = &self.on_response {
+            hook(&info());
+        }
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("on_request", &self.on_request.is_some())
+            .field("on_response", &self.on_response.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+    use crate::RetryPolicy;
+
+    /// Every attempt of a retried request is reported, with its status.
+    #[tokio::test]
+    async fn reports_each_attempt() {
+        let backend = FakeBackend::new();
+        backend.on("GET", "get-gas-address", FakeResponse::json(503, json!({})));
+        backend.on(
+            "GET",
+            "get-gas-address",
+            FakeResponse::json(200, json!({ "gasAddress": "tank" })),
+        );
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (requests, responses) = (seen.clone(), seen.clone());
+        let client = backend
+            .client()
+            .retry_policy(RetryPolicy {
+                initial_backoff: Duration::ZERO,
+                ..RetryPolicy::default()
+            })
+            .on_request(move |info| {
+                requests.lock().unwrap().push((info.attempt, None));
+            })
+            .on_response(move |info| {
+                responses.lock().unwrap().push((info.attempt, info.status));
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                (1, None),
+                (1, Some(StatusCode::SERVICE_UNAVAILABLE)),
+                (2, None),
+                (2, Some(StatusCode::OK)),
+            ]
+        );
+    }
+}
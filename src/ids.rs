@@ -0,0 +1,100 @@
+//! Typed identifiers, so an ID of one kind cannot be passed where another is expected.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Error parsing an empty string as an identifier.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} must not be empty")]
+pub struct EmptyIdError {
+    kind: &'static str,
+}
+
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = EmptyIdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                if s.is_empty() {
+                    return Err(EmptyIdError { kind: stringify!($name) });
+                }
+                Ok(Self(s.to_string()))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
+id_type! {
+    /// Identifier of a gas tank.
+    TankId
+}
+
+id_type! {
+    /// Identifier of an Aethokit project.
+    ProjectId
+}
+
+id_type! {
+    /// Identifier of a registered webhook.
+    WebhookId
+}
+
+id_type! {
+    /// Identifier of a sponsorship quota reservation.
+    ReservationId
+}
+
+id_type! {
+    /// Identifier of a transaction bundle, see [`Aethokit::sponsor_bundle`](crate::Aethokit::sponsor_bundle).
+    BundleId
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// IDs round-trip through strings and JSON unchanged, and empty strings are rejected.
+    #[test]
+    fn parses_and_serializes_ids() {
+        let id: TankId = "tank_123".parse().unwrap();
+        assert_eq!(id.to_string(), "tank_123");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"tank_123\"");
+        let back: TankId = serde_json::from_str("\"tank_123\"").unwrap();
+        assert_eq!(back, id);
+        assert_eq!(
+            " ".parse::<WebhookId>().unwrap_err().to_string(),
+            "WebhookId must not be empty"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Loading the gas key from a secret store instead of the environment or a config file, see
+//! [`AethokitBuilder::key_provider`](crate::AethokitBuilder::key_provider).
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::{self, BoxFuture};
+
+use crate::{Aethokit, AethokitError};
+
+/// A source of the current gas key, such as AWS Secrets Manager or Vault.
+///
+/// Implement it with your secret store's client and return failures as
+/// `AethokitError::KeyProvider`. [`EnvKeyProvider`] and [`FileKeyProvider`] cover keys in the
+/// environment and keys mounted as files, e.g. Kubernetes secrets.
+///
+/// ```
+/// use aethokit::{AethokitError, KeyProvider};
+/// use futures_util::future::BoxFuture;
+///
+/// struct Vault;
+///
+/// impl KeyProvider for Vault {
+///     fn gas_key(&self) -> BoxFuture<'_, Result<String, AethokitError>> {
+///         Box::pin(async {
+///             // read the secret with your Vault client here
+///             Ok("gas-key".to_string())
+///         })
+///     }
+/// }
+/// ```
+pub trait KeyProvider: Send + Sync {
+    /// Fetch the current gas key.
+    fn gas_key(&self) -> BoxFuture<'_, Result<String, AethokitError>>;
+}
+
+/// Reads the gas key from an environment variable on every fetch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvKeyProvider(pub String);
+
+impl KeyProvider for EnvKeyProvider {
+    fn gas_key(&self) -> BoxFuture<'_, Result<String, AethokitError>> {
+        let result = std::env::var(&self.0)
+            .map(|key| key.trim().to_string())
+            .map_err(|err| AethokitError::KeyProvider(format!("{}: {err}", self.0).into()));
+        Box::pin(future::ready(result))
+    }
+}
+
+/// Reads the gas key from a file, trimmed, on every fetch; files rewritten by the secret
+/// store are picked up on the next refresh.
+///
+/// Without the `tokio-runtime` feature the file is read with blocking I/O.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileKeyProvider(pub PathBuf);
+
+impl KeyProvider for FileKeyProvider {
+    fn gas_key(&self) -> BoxFuture<'_, Result<String, AethokitError>> {
+        Box::pin(async move {
+            #[cfg(feature = "tokio-runtime")]
+            let key = tokio::fs::read_to_string(&self.0).await;
+            #[cfg(not(feature = "tokio-runtime"))]
+            let key = std::fs::read_to_string(&self.0);
+            let key = key.map_err(|err| AethokitError::KeyProvider(Box::new(err)))?;
+            Ok(key.trim().to_string())
+        })
+    }
+}
+
+/// A client's key provider and when the key was last loaded from it.
+#[derive(Clone)]
+pub(crate) struct KeySource {
+    provider: Arc<dyn KeyProvider>,
+    refresh: Option<Duration>,
+    loaded: Arc<tokio::sync::Mutex<Option<Instant>>>,
+}
+
+impl KeySource {
+    pub(crate) fn new(provider: Arc<dyn KeyProvider>, refresh: Option<Duration>) -> Self {
+        Self {
+            provider,
+            refresh,
+            loaded: Arc::default(),
+        }
+    }
+}
+
+impl fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeySource")
+            .field("refresh", &self.refresh)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Aethokit {
+    /// Load the gas key from the key provider on first use and once it is due for a refresh.
+    ///
+    /// A failed refresh keeps the previous key, so a secret store outage doesn't take the
+    /// client down with it; only a failed first load is an error.
+    pub(crate) async fn load_gas_key(&self) -> Result<(), AethokitError> {
+        let Some(source) = &self.key_source else {
+            return Ok(());
+        };
+        let mut loaded = source.loaded.lock().await;
+        let due = match (*loaded, source.refresh) {
+            (None, _) => true,
+            (Some(at), Some(refresh)) => self.time.now().duration_since(at) >= refresh,
+            (Some(_), None) => false,
+        };
+        if !due {
+            return Ok(());
+        }
+        match source
+            .provider
+            .gas_key()
+            .await
+            .and_then(|key| self.set_gas_key(key))
+        {
+            Ok(()) => *loaded = Some(self.time.now()),
+            Err(err) if loaded.is_none() => return Err(err),
+            // retry on the next request
+            Err(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    #[derive(Default)]
+    struct Rotating(AtomicUsize);
+
+    impl KeyProvider for Rotating {
+        fn gas_key(&self) -> BoxFuture<'_, Result<String, AethokitError>> {
+            let n = self.0.fetch_add(1, Ordering::Relaxed);
+            Box::pin(future::ready(Ok(format!("key-{n}"))))
+        }
+    }
+
+    /// The key is loaded on the first request and again once the refresh interval passed.
+    #[tokio::test(start_paused = true)]
+    async fn loads_and_refreshes_key() {
+        let backend = FakeBackend::new();
+        let body = json!({ "gasAddress": "tank" });
+        backend.on("GET", "get-gas-address", FakeResponse::json(200, body));
+        let client = Aethokit::builder()
+            .key_provider(Arc::new(Rotating::default()), Some(Duration::from_secs(60)))
+            .middleware(backend.clone())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            client.refresh_gas_address().await.unwrap();
+        }
+        tokio::time::advance(Duration::from_secs(60)).await;
+        client.refresh_gas_address().await.unwrap();
+        let keys: Vec<_> = backend
+            .requests()
+            .into_iter()
+            .map(|r| r.gas_key.unwrap())
+            .collect();
+        assert_eq!(keys, ["key-0", "key-0", "key-1"]);
+
+        let missing = FileKeyProvider(PathBuf::from("/nonexistent/aethokit-gas-key"));
+        assert!(matches!(
+            missing.gas_key().await,
+            Err(AethokitError::KeyProvider(_))
+        ));
+    }
+}
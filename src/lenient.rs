@@ -0,0 +1,75 @@
+use std::fmt;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::Deserialize;
+
+/// Deserialize a `u64` from a JSON number or a string of digits, for lamport amounts and
+/// slots that some backend versions send as strings so they survive JavaScript clients.
+///
+/// Use with `#[serde(deserialize_with = "crate::lenient::u64")]`; serialization is unchanged.
+pub(crate) fn u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    deserializer.deserialize_any(U64Visitor)
+}
+
+/// [`u64`] for optional fields, with `null` as `None`. Pair it with `#[serde(default)]` so a
+/// missing field is `None` too.
+pub(crate) fn option_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Lenient(#[serde(deserialize_with = "u64")] u64);
+
+    Option::<Lenient>::deserialize(deserializer).map(|value| value.map(|Lenient(value)| value))
+}
+
+struct U64Visitor;
+
+impl Visitor<'_> for U64Visitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an unsigned integer or a string of digits")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        value.try_into().map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        value.parse().map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use crate::SponsoredTx;
+
+    #[derive(Debug, Deserialize)]
+    struct Amount {
+        #[serde(deserialize_with = "super::u64")]
+        lamports: u64,
+    }
+
+    /// Numbers and strings of digits decode to the same value, including ones past 2^53.
+    #[test]
+    fn accepts_numbers_and_strings() {
+        let big = 9_007_199_254_740_993u64;
+        for encoded in [json!(big), json!(big.to_string())] {
+            let amount: Amount = serde_json::from_value(json!({ "lamports": encoded })).unwrap();
+            assert_eq!(amount.lamports, big);
+        }
+        for invalid in [json!(-1), json!("12abc"), json!(1.5)] {
+            assert!(serde_json::from_value::<Amount>(json!({ "lamports": invalid })).is_err());
+        }
+
+        let tx: SponsoredTx = serde_json::from_value(json!({ "hash": "sig", "slot": "42", "feeLamports": 5000 })).unwrap();
+        assert_eq!((tx.slot, tx.fee_lamports), (Some(42), Some(5000)));
+        let tx: SponsoredTx = serde_json::from_value(json!({ "hash": "sig", "slot": null })).unwrap();
+        assert_eq!((tx.slot, tx.fee_lamports), (None, None));
+    }
+}
@@ -1,36 +1,574 @@
-use reqwest::{Client, Method, StatusCode};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 use url::Url;
 
+use crate::limiter::RateLimiter;
+
+mod airdrop;
+mod audit;
+mod base_urls;
+#[cfg(feature = "anchor")]
+pub mod anchor;
+mod batch;
+mod breaker;
+mod bundle;
+mod cache;
+mod builder;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod classify;
+mod clock;
+pub mod codes;
+pub mod codec;
+mod compat;
+mod config;
+mod confirm;
+mod dedup;
+mod deposit;
+mod deprecation;
+mod dry_run;
+mod encoding;
+mod endpoint;
+mod events;
+mod failover;
+mod fees;
+mod forecast;
+mod gas_tank;
+#[cfg(feature = "global")]
+pub mod global;
+mod health;
+mod history;
+mod hooks;
+mod ids;
+mod keys;
+mod lenient;
+mod limiter;
+#[cfg(feature = "tracing")]
+mod logging;
+mod meta;
+mod metrics;
+mod middleware;
+pub mod models;
+#[cfg(feature = "solana")]
+pub mod offline;
+mod options;
+#[cfg(feature = "solana")]
+mod pending;
+pub mod policy;
+mod pool;
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "tokio-runtime")]
+mod queue;
+mod rate_limit;
+mod rebroadcast;
+mod receipt;
+pub mod reconcile;
+mod replace;
+mod replay;
+mod reservation;
+mod retry;
+mod rt;
+#[cfg(feature = "solana")]
+mod rpc;
+mod schema;
+#[cfg(feature = "solana")]
+pub mod signer;
+mod sign_only;
+mod signing;
+mod singleflight;
+#[cfg(feature = "solana")]
+mod solana;
+#[cfg(feature = "solana-pay")]
+pub mod solana_pay;
+mod stats;
+mod status;
+mod submit;
+#[cfg(feature = "tracing")]
+mod telemetry;
+mod tenant;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod trace;
+mod transport;
+#[cfg(feature = "solana")]
+pub mod tx;
+mod usage;
+mod validate;
+#[cfg(feature = "solana")]
+mod verify;
+pub mod webhooks;
+
+pub use airdrop::{idempotency_key, AirdropItem, AirdropManifest, AirdropOutcome, IDEMPOTENCY_KEY_HEADER};
+pub use audit::{AuditRecord, AuditResult, AuditSink};
+pub use batch::{plan_batch, BatchPlan, PlannedTx};
+pub use breaker::{CircuitBreaker, CircuitState};
+pub use bundle::{SponsoredBundle, MAX_BUNDLE_SIZE};
+pub use cache::{CacheStore, MemoryCache};
+pub use builder::{AethokitBuilder, BASE_URL_ENV, GAS_KEY_ENV, NETWORK_ENV};
+pub use classify::{Classifier, DefaultClassifier, RequestOutcome};
+pub use clock::{Clock, Sleeper, SystemClock};
+pub use codes::ErrorCode;
+pub use compat::{
+    Compatibility, CompatibilityReport, ACCEPT_VERSION_HEADER, API_SCHEMA_VERSION, API_VERSION_HEADER,
+    SDK_VERSION_HEADER,
+};
+pub use config::{Environment, Timeouts, TransportProfile};
+pub use confirm::{CommitmentLevel, ConfirmOptions, ConfirmationProgress, ConfirmedSponsorship};
+pub use deposit::{AcceptedToken, DepositInfo};
+pub use deprecation::ServerDeprecation;
+pub use dry_run::{Change, DryRun, InstructionSummary, TxDiff, DRY_RUN_SIGNATURE_PREFIX};
+pub use encoding::TxEncoding;
+pub use events::{BalanceChanged, SponsorEvent};
+pub use fees::{FeeBreakdown, FeeEstimate};
+pub use forecast::DepletionForecast;
+pub use gas_tank::{GasTank, TankInfo};
+pub use history::{Consistency, HistoryStatus, ListParams, TxPage, TxRecord};
+pub use hooks::{RequestInfo, ResponseInfo};
+pub use ids::{BundleId, EmptyIdError, ProjectId, ReservationId, TankId, WebhookId};
+pub use keys::{EnvKeyProvider, FileKeyProvider, KeyProvider};
+pub use meta::{Responded, ResponseMeta};
+pub use metrics::OPENMETRICS_CONTENT_TYPE;
+pub use middleware::{Middleware, Next};
+pub use options::{SponsorOptions, SponsorTxRequestBuilder};
+#[cfg(feature = "solana")]
+pub use pending::PendingSponsorship;
+pub use pool::ClientPool;
+pub use rate_limit::RateLimitInfo;
+pub use receipt::Receipt;
+pub use rebroadcast::{EscalationSchedule, PendingTx};
+pub use replace::{ReplacementOutcome, ReplayProtection};
+pub use replay::{Divergence, RecordedSponsorship, ReplayReport};
+pub use reservation::Reservation;
+pub use retry::{PostRetries, RetryBudget, RetryBudgetStats, RetryPolicy};
+pub use schema::SchemaError;
+pub use sign_only::SignedTransaction;
+pub use signing::{REQUEST_SIGNATURE_HEADER, REQUEST_TIMESTAMP_HEADER};
+pub use stats::{Granularity, SponsorshipStats, StatsBucket};
+pub use status::TxStatus;
+pub use submit::{JobResult, SponsorJob, SponsorSink};
+pub use tenant::{Guardrails, TenantConfig, Tenants};
+pub use transport::HttpTransport;
+pub use usage::{UsageFormat, UsageRange};
+pub use validate::{precheck_transaction, ValidationError, MAX_SIGNATURES};
+#[cfg(feature = "tracing")]
+pub use logging::Redaction;
+#[cfg(feature = "tokio-runtime")]
+pub use queue::{QueueOptions, SponsorOutcome, SponsorQueue};
+#[cfg(feature = "solana")]
+pub use receipt::verify_receipt;
+#[cfg(feature = "solana")]
+pub use solana::{encode_transaction, encode_transaction_into, encode_versioned_transaction};
+#[cfg(feature = "tracing")]
+pub use telemetry::TraceSampling;
+#[cfg(feature = "solana")]
+pub use tx::{Priority, SponsoredTxBuilder, TransactionBuilder};
+#[cfg(feature = "solana")]
+pub use verify::{verify_sponsor_signature, VerifyError};
+
+/// The `chrono` timestamps in responses are typed with.
+pub use chrono;
+/// DNS resolver interface accepted by [`AethokitBuilder::dns_resolver`].
+pub use reqwest::dns;
+/// The `solana-sdk` the Solana helpers are built against, for apps that depend on another
+/// major version: build the values passed to the helpers with these types.
+#[cfg(feature = "solana")]
+pub use solana_sdk;
+#[cfg(feature = "solana")]
+pub use solana_system_interface;
+/// Token accepted by [`Aethokit::with_cancellation`].
+pub use tokio_util::sync::CancellationToken;
+
 const DEFAULT_BASE_URL: &str = "https://aethokit.onrender.com/api/";
 
+/// Cache entry kind of the gas address, see [`CacheStore`].
+const GAS_ADDRESS_CACHE: &str = "gas-address";
+const JSON_CONTENT_TYPE: &str = "application/json";
+
 #[derive(Debug, Error)]
 pub enum AethokitError {
     #[error("GAS KEY is required to initialize the SDK")]
     MissingGasKey,
+    #[error("invalid header `{0}`")]
+    InvalidHeader(String),
+    #[error("invalid base URL `{0}`: expected an absolute http(s) URL")]
+    InvalidBaseUrl(String),
+    /// `rpc_or_network` looks like a URL but is not an absolute http(s) or ws(s) one.
+    #[error("invalid RPC URL `{0}`: expected an absolute http(s) or ws(s) URL")]
+    InvalidRpcUrl(String),
+    /// A request path could not be joined onto the base URL.
+    #[error("invalid request URL: cannot join `{0}` onto the base URL")]
+    InvalidUrl(String),
+    /// An invariant of the SDK did not hold; please report it.
+    #[error("internal error: {0}")]
+    Internal(&'static str),
+    /// `rpc_or_network` points at a cluster the builder's environment doesn't allow.
+    #[error("network `{network}` is not allowed in the {environment:?} environment")]
+    EnvironmentMismatch { environment: Environment, network: String },
+    #[error("invalid proxy URL `{0}`")]
+    InvalidProxy(String),
+    #[error("environment variable {var} {message}")]
+    Env { var: &'static str, message: String },
+    #[error("invalid config file {path}: {message}")]
+    Config { path: String, message: String },
+    #[error("airdrop manifest {path}: {message}")]
+    Manifest { path: String, message: String },
+    /// The deadline set with [`Aethokit::with_deadline`] passed before the call completed.
+    #[error("deadline exceeded")]
+    DeadlineExceeded,
+    /// The token set with [`Aethokit::with_cancellation`] was cancelled before the call completed.
+    #[error("cancelled")]
+    Cancelled,
     #[error("http error: {0}")]
     Http(#[from] reqwest::Error),
     #[error("unexpected response status: {status} - {body}")]
-    UnexpectedStatus { status: StatusCode, body: String },
+    UnexpectedStatus {
+        status: StatusCode,
+        method: Method,
+        /// Path of the endpoint relative to the base URL, without the query string
+        path: String,
+        body: String,
+        /// `body` parsed, if it is JSON; boxed to keep the error small
+        json: Option<Box<Value>>,
+        /// Backend request ID, to quote when contacting support
+        request_id: Option<String>,
+    },
+    /// The server answered `429 Too Many Requests`.
+    #[error("rate limited by the server (retry after {retry_after:?}) - {body}")]
+    RateLimited {
+        /// Wait suggested by the `retry-after` header, if present
+        retry_after: Option<Duration>,
+        body: String,
+        request_id: Option<String>,
+    },
     #[error("serialization error: {0}")]
     Serde(#[from] serde_json::Error),
+    /// A response had fields the SDK doesn't know, with strict parsing enabled.
+    #[error("unknown fields in response: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+    #[error("invalid transaction: {0}")]
+    Codec(#[from] codec::CodecError),
+    #[error("transaction rejected by policy: {0}")]
+    Policy(#[from] policy::PolicyViolation),
+    /// The transaction failed the pre-submission check, see [`AethokitBuilder::precheck`].
+    #[error("{0}")]
+    Validation(#[from] ValidationError),
+    /// The request body does not match the endpoint's schema (checked in debug builds only).
+    #[error("invalid request body: {}", schema::format_errors(.0))]
+    InvalidRequestBody(Vec<SchemaError>),
+    #[cfg(feature = "solana")]
+    #[error("signing error: {0}")]
+    Signer(#[from] signer::SignerError),
+    /// The signed transaction returned by the backend failed verification, see
+    /// [`SponsorOptions::verify_signature`].
+    #[cfg(feature = "solana")]
+    #[error("sponsorship failed verification: {0}")]
+    Verify(#[from] VerifyError),
+    #[cfg(feature = "solana")]
+    #[error("gas address returned by the server is not a valid pubkey: {0}")]
+    InvalidGasAddress(String),
+    #[cfg(feature = "solana")]
+    #[error("solana rpc error: {0}")]
+    Rpc(String),
+    #[cfg(feature = "solana")]
+    #[error("transaction encoding error: {0}")]
+    TxEncoding(#[from] bincode::Error),
+    /// The instructions could not be compiled into a v0 message with the given lookup tables.
+    #[cfg(feature = "solana")]
+    #[error("cannot compile message: {0}")]
+    Compile(#[from] solana_sdk::message::CompileError),
+    /// A Solana Pay transaction request could not be fetched or sponsored.
+    #[cfg(feature = "solana-pay")]
+    #[error("solana pay: {0}")]
+    SolanaPay(String),
+    /// A sponsored transaction landed but failed on chain.
+    #[error("transaction {hash} failed")]
+    TxFailed { hash: String },
+    /// A sponsored transaction did not reach the requested commitment in time.
+    #[error("transaction {hash} not confirmed in time (last status: {status:?})")]
+    NotConfirmed {
+        hash: String,
+        /// Last status seen, `None` if the history API never returned the transaction
+        status: Option<HistoryStatus>,
+    },
+    /// A replacement transaction is not protected against replay the same way as the
+    /// transaction it replaces, so both could land.
+    #[error("replacement uses {replacement:?}, but the original uses {original:?}")]
+    ReplacementMismatch {
+        original: ReplayProtection,
+        replacement: ReplayProtection,
+    },
+    /// The results stream of a [`SponsorSink`] was dropped, so no more jobs can be submitted.
+    #[error("sponsor sink closed")]
+    SinkClosed,
+    /// The quota reservation a sponsorship was charged to has no budget left.
+    #[error("quota reservation {id} is exhausted")]
+    ReservationExhausted { id: ReservationId },
+    /// The quota reservation a sponsorship was charged to expired or was released.
+    #[error("quota reservation {id} has expired")]
+    ReservationExpired { id: ReservationId },
+    /// The transaction's recent blockhash expired before it was sponsored; rebuild it with a
+    /// fresh blockhash and sign it again, see `sponsor_with_fresh_blockhash` with the `solana`
+    /// feature.
+    #[error("the transaction's blockhash has expired")]
+    BlockhashExpired,
+    /// A [`Guardrails`] limit of the client was reached, so nothing was sent.
+    #[error("guardrail exceeded: {0}")]
+    GuardrailExceeded(&'static str),
+    /// The [`global`] client was used before [`global::init`], or initialized twice.
+    #[cfg(feature = "global")]
+    #[error("global client {0}")]
+    GlobalClient(&'static str),
+    /// A bundle was refused before sending, or answered inconsistently, see
+    /// [`Aethokit::sponsor_bundle`].
+    #[error("invalid bundle: {0}")]
+    InvalidBundle(String),
+    /// A sign-only sponsorship was answered without the signed transaction.
+    #[error("the backend did not return the signed transaction")]
+    MissingSignedTransaction,
+    /// The client's [`CircuitBreaker`] is open after repeated backend failures, so nothing
+    /// was sent.
+    #[error("circuit open after repeated backend failures, retry in {retry_after:?}")]
+    CircuitOpen { retry_after: Duration },
+    /// The [`KeyProvider`] could not load the gas key.
+    #[error("gas key provider failed: {0}")]
+    KeyProvider(Box<dyn std::error::Error + Send + Sync>),
+    /// Writing a usage export to its destination failed.
+    #[error("writing usage export: {0}")]
+    Export(std::io::Error),
+    /// The workers of a [`SponsorQueue`] have stopped, so no more jobs can be enqueued.
+    #[error("sponsor queue closed")]
+    QueueClosed,
+    /// The error of a concurrent identical request this call was coalesced with.
+    #[error("{0}")]
+    Shared(Arc<AethokitError>),
+    /// A failed request made with tracing enabled, see [`Aethokit::with_trace`].
+    #[error("{error}")]
+    Traced {
+        /// cURL command reproducing the request, with the gas key redacted
+        curl: String,
+        error: Box<AethokitError>,
+    },
 }
 
-#[derive(Debug, Serialize, Clone)]
+impl AethokitError {
+    /// cURL reproduction of the failing request, if it was made with tracing enabled.
+    pub fn curl(&self) -> Option<&str> {
+        match self {
+            AethokitError::Traced { curl, .. } => Some(curl),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of a rejected request, looking through trace wrappers.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self.inner() {
+            AethokitError::UnexpectedStatus { status, .. } => Some(*status),
+            AethokitError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+
+    /// Body of a rejected request parsed as JSON, for extracting fields in logs and alerts.
+    pub fn body_json(&self) -> Option<&Value> {
+        match self.inner() {
+            AethokitError::UnexpectedStatus { json, .. } => json.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Backend request ID of a rejected request, to quote when contacting support.
+    pub fn request_id(&self) -> Option<&str> {
+        match self.inner() {
+            AethokitError::UnexpectedStatus { request_id, .. }
+            | AethokitError::RateLimited { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// `true` if the server answered `429 Too Many Requests`.
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.inner(), AethokitError::RateLimited { .. })
+    }
+
+    /// `true` if the gas tank is depleted (`402 Payment Required`) or the key is over
+    /// its quota (`429`), so another gas key may still succeed.
+    pub fn is_quota_exceeded(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(StatusCode::PAYMENT_REQUIRED | StatusCode::TOO_MANY_REQUESTS)
+        )
+    }
+
+    /// `true` if the server rejected the request itself (a 4xx status), so sending it
+    /// again unchanged will fail the same way. Rate limiting is not counted.
+    pub fn is_client_error(&self) -> bool {
+        self.status()
+            .is_some_and(|status| status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// `true` if the request may succeed when sent again: connection errors, timeouts,
+    /// rate limiting and 5xx responses.
+    ///
+    /// Retrying a `sponsor_tx` is only safe if the transaction cannot land twice, e.g.
+    /// because it is the same signed transaction.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner() {
+            AethokitError::Http(err) => err.is_connect() || err.is_timeout(),
+            AethokitError::UnexpectedStatus { status, .. } => status.is_server_error(),
+            AethokitError::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// An `UnexpectedStatus` for a response to `method path`, with the body parsed if it is JSON.
+    pub(crate) fn unexpected_status(
+        status: StatusCode,
+        method: Method,
+        path: &str,
+        body: String,
+        request_id: Option<String>,
+    ) -> Self {
+        AethokitError::UnexpectedStatus {
+            status,
+            method,
+            path: path.split('?').next().unwrap_or(path).to_string(),
+            json: serde_json::from_str(&body).ok().map(Box::new),
+            body,
+            request_id,
+        }
+    }
+
+    /// The error for a failed response to `method path`: `RateLimited` with the server's
+    /// retry-after for a 429, `UnexpectedStatus` otherwise.
+    pub(crate) fn from_status(
+        status: StatusCode,
+        method: Method,
+        path: &str,
+        body: String,
+        meta: ResponseMeta,
+    ) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return AethokitError::RateLimited {
+                retry_after: meta.rate_limit.and_then(|info| info.retry_after),
+                body,
+                request_id: meta.request_id,
+            };
+        }
+        Self::unexpected_status(status, method, path, body, meta.request_id)
+    }
+
+    /// The underlying error, without trace or coalescing wrappers.
+    fn inner(&self) -> &AethokitError {
+        match self {
+            AethokitError::Traced { error, .. } => error.inner(),
+            AethokitError::Shared(error) => error.inner(),
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct AethokitConfig {
-    gas_key: String,
-    #[serde(rename = "rpcOrNetwork", skip_serializing_if = "Option::is_none")]
-    rpc_or_network: Option<String>
+    pub gas_key: String,
+    /// Gas keys to fall back to when the primary tank is depleted, in priority order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_gas_keys: Vec<String>,
+    #[serde(rename = "rpcOrNetwork", alias = "network", alias = "rpc_or_network", default, skip_serializing_if = "Option::is_none")]
+    pub rpc_or_network: Option<String>,
+    /// API base URL, defaults to the hosted Aethokit API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Retry policy for idempotent requests, no retries when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    #[serde(default)]
+    pub timeouts: Timeouts,
 }
 
 /// Rust client for the Aethokit Gas Sponsorship API.
+///
+/// The client is a handle: cloning it is cheap, and clones (including the copies returned by
+/// `with_header`, `with_deadline`, `with_cancellation` and `with_transport`) share one
+/// set of runtime state, so build it once and clone it into each task or handler:
+///
+/// - the HTTP connection pool, unless replaced with [`with_transport`](Self::with_transport)
+/// - the gas key in use, so a failover or key rotation applies to every clone
+/// - the cache of gas addresses and key policies, and in-flight gas address lookups
+/// - the rate limiter, the last [`rate_limit_info`](Self::rate_limit_info) and the tenant budget
+/// - the retry budget, circuit breaker and duplicate-submission cache
+/// - metrics, spend history, deprecation notices and the server version
+///
+/// Only the per-call settings of the `with_*` methods differ between clones. Build a
+/// separate client with [`AethokitBuilder`] for state that must not be shared, e.g. a
+/// rate limit per tenant.
 #[derive(Debug, Clone)]
 pub struct Aethokit {
-    gas_key: String,
+    gas_key: failover::SharedGasKey,
+    key_source: Option<keys::KeySource>,
+    fallback_keys: Arc<[String]>,
     http: Client,
+    transport: transport::SharedTransport,
     base_url: Url,
+    /// Primary and fallback base URLs, set when there are fallbacks
+    base_urls: Option<Arc<base_urls::BaseUrls>>,
     rpc_or_network: Option<String>,
+    trace: bool,
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    limiter: Option<Arc<RateLimiter>>,
+    extra_headers: HeaderMap,
+    middleware: middleware::MiddlewareChain,
+    retry: Option<RetryPolicy>,
+    retry_budget: Option<Arc<retry::RetryBucket>>,
+    deadline: Option<Instant>,
+    cancel: Option<CancellationToken>,
+    gas_address_flight: Arc<singleflight::Group<Responded<String>>>,
+    /// Gas address and key policy between requests, shared between clones
+    cache: cache::SharedCacheStore,
+    gas_address_ttl: Duration,
+    time: clock::Time,
+    /// Reject responses with unknown fields, see [`AethokitBuilder::strict`]
+    strict: bool,
+    /// Check transaction size before sponsoring, see [`AethokitBuilder::precheck`]
+    precheck: bool,
+    /// Check transactions against the gas key's policy, see
+    /// [`AethokitBuilder::enforce_key_policy`]
+    enforce_key_policy: bool,
+    /// Leave out the SDK version and sponsorship labels, see
+    /// [`AethokitBuilder::minimal_metadata`]
+    minimal_metadata: bool,
+    /// Answer sponsorships without sending them, see [`AethokitBuilder::dry_run`]
+    dry_run: bool,
+    dry_run_simulation: bool,
+    budget: Option<Arc<tenant::Budget>>,
+    /// Set when sponsorships are sent as CBOR, and flipped once the backend rejects it
+    #[cfg(feature = "cbor")]
+    cbor: Option<Arc<std::sync::atomic::AtomicBool>>,
+    signing_secret: Option<signing::SigningSecret>,
+    hooks: hooks::Hooks,
+    audit: Option<audit::SharedAuditSink>,
+    breaker: Option<Arc<breaker::Breaker>>,
+    dedup: Option<Arc<dedup::DedupCache>>,
+    metrics: Arc<metrics::Metrics>,
+    classifier: classify::SharedClassifier,
+    spend: Arc<forecast::SpendLog>,
+    deprecations: Arc<deprecation::Deprecations>,
+    server_version: Arc<compat::ServerVersion>,
+    #[cfg(feature = "otel")]
+    otel: otel::OtelMetrics,
+    #[cfg(feature = "tracing")]
+    sampler: Arc<telemetry::Sampler>,
+    #[cfg(feature = "tracing")]
+    log_redaction: logging::Redaction,
 }
 
 impl Aethokit {
@@ -39,46 +577,365 @@ impl Aethokit {
     /// # Errors
     /// - `MissingGasKey` if `gas_key` is empty
     pub fn new(config: AethokitConfig) -> Result<Self, AethokitError> {
-        let key = &config.gas_key;
-        if key.trim().is_empty() {
-            return Err(AethokitError::MissingGasKey);
-        }
-        let base_url = Url::parse(DEFAULT_BASE_URL).unwrap();
-        Ok(Self {
-            gas_key: key.to_string(),
-            http: Client::new(),
-            base_url,
-            rpc_or_network: config.rpc_or_network,
+        let mut builder = Self::builder()
+            .gas_key(config.gas_key)
+            .fallback_gas_keys(config.fallback_gas_keys);
+        if let Some(rpc_or_network) = config.rpc_or_network {
+            builder = builder.rpc_or_network(rpc_or_network);
+        }
+        if let Some(base_url) = config.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(retry) = config.retry {
+            builder = builder.retry_policy(retry);
+        }
+        if let Some(timeout) = config.timeouts.request {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = config.timeouts.connect {
+            builder = builder.connect_timeout(timeout);
+        }
+        builder.build()
+    }
+
+    /// Initialize the SDK from `AETHOKIT_GAS_KEY`, and optionally `AETHOKIT_BASE_URL` and
+    /// `AETHOKIT_NETWORK`.
+    ///
+    /// # Errors
+    /// - `Env` if `AETHOKIT_GAS_KEY` is missing or a variable holds an invalid value
+    pub fn from_env() -> Result<Self, AethokitError> {
+        AethokitBuilder::from_env()?.build()
+    }
+
+    /// Start building a client with non-default options.
+    pub fn builder() -> AethokitBuilder {
+        AethokitBuilder::default()
+    }
+
+    /// Rate-limit state from the most recent response that reported it.
+    ///
+    /// Shared between clones of the client, since they count against the same gas key.
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// Capture a cURL reproduction of each request, attached to the error if it fails.
+    ///
+    /// The command carries the headers and body the SDK sent, with the gas key and request
+    /// signature redacted; the rest, including the body, is not, so only enable this while
+    /// debugging.
+    pub fn with_trace(mut self, enabled: bool) -> Self {
+        self.trace = enabled;
+        self
+    }
+
+    /// Return a copy of the client that adds `name: value` to its requests, on top of the
+    /// builder's default headers.
+    ///
+    /// Cheap enough to call per request:
+    /// `client.with_header("x-tenant", "acme")?.sponsor_tx(tx)`.
+    ///
+    /// # Errors
+    /// - `InvalidHeader` if the name or value is not valid HTTP
+    pub fn with_header(&self, name: &str, value: &str) -> Result<Self, AethokitError> {
+        let (name, value) = builder::parse_header(name, value)?;
+        let mut client = self.clone();
+        client.extra_headers.insert(name, value);
+        Ok(client)
+    }
+
+    /// Return a copy of the client whose calls all fail with `DeadlineExceeded` once
+    /// `deadline` passes, including time spent waiting on the rate limiter, retries and
+    /// confirmation polling. Calls made after the deadline fail without sending anything.
+    ///
+    /// Meant to bound all SDK work in a handler by the handler's own deadline:
+    /// `let client = client.with_deadline(request_deadline);`. The deadline is optional so a
+    /// tower or tonic handler's remaining deadline can be passed straight through; `None`
+    /// leaves the client unchanged (convert a tokio `Instant` with `into_std`). An earlier
+    /// deadline already set on the client is kept.
+    pub fn with_deadline(&self, deadline: impl Into<Option<Instant>>) -> Self {
+        let mut client = self.clone();
+        if let Some(deadline) = deadline.into() {
+            client.deadline = Some(self.deadline.map_or(deadline, |current| current.min(deadline)));
+        }
+        client
+    }
+
+    /// Return a copy of the client whose calls all fail with `Cancelled` once `token` is
+    /// cancelled, including polling helpers such as
+    /// [`sponsor_and_confirm`](Self::sponsor_and_confirm) and the workers of a
+    /// [`sponsor_queue`](Self::sponsor_queue), which stop.
+    ///
+    /// Replaces any token set earlier; use a child token to cancel from several places.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        let mut client = self.clone();
+        client.cancel = Some(token);
+        client
+    }
+
+    /// Return a copy of the client that sends its requests through `http`, e.g. a client
+    /// configured with a compliance proxy for the few calls that need one.
+    ///
+    /// Everything else is shared with the original client: gas keys, headers, retries,
+    /// middleware, rate limits and metrics. The builder's proxy, DNS and timeout settings
+    /// live on the HTTP client, so they are replaced by those of `http`.
+    pub fn with_transport(&self, http: reqwest::Client) -> Self {
+        let mut client = self.clone();
+        client.transport = transport::SharedTransport(Arc::new(http.clone()));
+        client.http = http;
+        client
+    }
+
+    /// Run `fut`, failing with `DeadlineExceeded` or `Cancelled` if the client's deadline
+    /// passes or its cancellation token fires first.
+    pub(crate) async fn within_scope<T>(
+        &self,
+        fut: impl Future<Output = Result<T, AethokitError>>,
+    ) -> Result<T, AethokitError> {
+        let fut = async {
+            match self.deadline {
+                Some(deadline) if deadline <= Instant::now() => Err(AethokitError::DeadlineExceeded),
+                Some(deadline) => rt::timeout_at(deadline, fut)
+                    .await
+                    .unwrap_or(Err(AethokitError::DeadlineExceeded)),
+                None => fut.await,
+            }
+        };
+        match &self.cancel {
+            Some(token) => token
+                .run_until_cancelled(fut)
+                .await
+                .unwrap_or(Err(AethokitError::Cancelled)),
+            None => fut.await,
+        }
+    }
+
+    /// Sleep between polls or retries, cut short by the client's deadline or cancellation.
+    pub(crate) async fn scoped_sleep(&self, duration: Duration) -> Result<(), AethokitError> {
+        self.within_scope(async {
+            self.time.sleep(duration).await;
+            Ok(())
         })
+        .await
     }
 
     /// Retrieve the gas address for the gas tank associated with the GAS KEY.
+    ///
+    /// The address is cached for [`AethokitBuilder::gas_address_ttl`] after each fetch.
     pub async fn get_gas_address(&self) -> Result<String, AethokitError> {
-        let path = "get-gas-address";
-        let resp: GasAddressResponse = self
-            .make_request::<(), GasAddressResponse>(path, Method::GET, None)
-            .await?;
-        Ok(resp.gas_address)
+        if let Some(address) = self.cache_get(GAS_ADDRESS_CACHE).await {
+            return Ok(address);
+        }
+        self.refresh_gas_address().await
+    }
+
+    /// Fetch the gas address from the server, replacing the cached one.
+    pub async fn refresh_gas_address(&self) -> Result<String, AethokitError> {
+        Ok(self.get_gas_address_with_meta().await?.value)
+    }
+
+    /// `path` joined onto the base URL.
+    pub(crate) fn join_url(&self, path: &str) -> Result<Url, AethokitError> {
+        self.base_url
+            .join(path)
+            .map_err(|_| AethokitError::InvalidUrl(path.to_string()))
+    }
+
+    /// Like [`refresh_gas_address`](Self::refresh_gas_address), also returning the response metadata.
+    ///
+    /// Concurrent calls share a single request and all receive its result.
+    pub async fn get_gas_address_with_meta(&self) -> Result<Responded<String>, AethokitError> {
+        let key = format!("{:?}", self.extra_headers);
+        let client = self.clone();
+        let flight = self.gas_address_flight.run(key, || async move {
+            let path = "get-gas-address";
+            let resp = client
+                .make_request_with_meta::<(), GasAddressResponse>(path, Method::GET, None)
+                .await?;
+            Ok(resp.map(|r| r.gas_address))
+        });
+        // the shared request carries the first caller's deadline, so apply ours on top
+        let resp = self.within_scope(flight).await?;
+        self.cache_set(GAS_ADDRESS_CACHE, resp.value.clone(), self.gas_address_ttl)
+            .await;
+        Ok(resp)
     }
 
     /// Submit a transaction for sponsorship. Returns the transaction hash.
+    ///
+    /// Prefer [`sponsor_tx_detailed`](Self::sponsor_tx_detailed); with the
+    /// `deprecation-warnings` feature the first call logs a warning.
     pub async fn sponsor_tx(
         &self,
         tx: String,
     ) -> Result<String, AethokitError> {
+        deprecation::warn_once("Aethokit::sponsor_tx", "Aethokit::sponsor_tx_detailed");
+        self.sponsor_signature(tx).await
+    }
+
+    /// Like [`sponsor_tx`](Self::sponsor_tx), also returning the response metadata
+    /// (request ID, server time, latency).
+    ///
+    /// With fallback gas keys configured, a depleted or over-quota tank is skipped for the
+    /// next key, so `tx` must be acceptable to every tank; the Solana helpers rebuild the
+    /// transaction for each tank instead.
+    ///
+    /// Prefer [`sponsor_tx_detailed_with_meta`](Self::sponsor_tx_detailed_with_meta); with
+    /// the `deprecation-warnings` feature the first call logs a warning.
+    pub async fn sponsor_tx_with_meta(
+        &self,
+        tx: String,
+    ) -> Result<Responded<String>, AethokitError> {
+        deprecation::warn_once(
+            "Aethokit::sponsor_tx_with_meta",
+            "Aethokit::sponsor_tx_detailed_with_meta",
+        );
+        self.sponsor_signature_with_meta(tx).await
+    }
+
+    /// [`sponsor_tx`](Self::sponsor_tx) for the SDK's own helpers, which shouldn't report
+    /// the caller as using a deprecated API.
+    pub(crate) async fn sponsor_signature(&self, tx: String) -> Result<String, AethokitError> {
+        Ok(self.sponsor_signature_with_meta(tx).await?.value)
+    }
+
+    pub(crate) async fn sponsor_signature_with_meta(
+        &self,
+        tx: String,
+    ) -> Result<Responded<String>, AethokitError> {
+        Ok(self.sponsor_tx_detailed_with_meta(tx).await?.map(|tx| tx.signature))
+    }
+
+    /// Like [`sponsor_tx`](Self::sponsor_tx), returning everything the backend reports
+    /// about the sponsorship instead of just the hash.
+    pub async fn sponsor_tx_detailed(&self, tx: String) -> Result<SponsoredTx, AethokitError> {
+        Ok(self.sponsor_tx_detailed_with_meta(tx).await?.value)
+    }
+
+    /// Like [`sponsor_tx_detailed`](Self::sponsor_tx_detailed), also returning the response
+    /// metadata.
+    pub async fn sponsor_tx_detailed_with_meta(
+        &self,
+        tx: String,
+    ) -> Result<Responded<SponsoredTx>, AethokitError> {
+        self.sponsor_with_options(tx, SponsorOptions::default(), true).await
+    }
+
+    /// Sponsor `tx`, leaving it to the caller to broadcast the signed transaction unless
+    /// `broadcast` is set.
+    pub(crate) async fn sponsor_with_options(
+        &self,
+        tx: String,
+        options: SponsorOptions,
+        broadcast: bool,
+    ) -> Result<Responded<SponsoredTx>, AethokitError> {
+        self.precheck(&tx)?;
+        self.enforce_key_policy(&tx).await?;
+        if self.dry_run && broadcast {
+            return self.dry_run_sponsorship(&tx).await;
+        }
+        let dedup = self
+            .dedup
+            .as_ref()
+            .filter(|_| broadcast)
+            .and_then(|cache| Some((cache, dedup::DedupCache::key(&tx)?)));
+        if let Some(resp) = dedup.and_then(|(cache, key)| cache.get(&key)) {
+            return Ok(resp);
+        }
+        if let Some(budget) = &self.budget {
+            if !budget.try_take() {
+                return Err(AethokitError::GuardrailExceeded("max_sponsorships"));
+            }
+        }
+        let result = self
+            .with_failover(|client| client.submit_sponsor_tx(tx.clone(), options.clone(), broadcast))
+            .await;
+        if let (Some(budget), Err(_)) = (&self.budget, &result) {
+            budget.refund();
+        }
+        if let Some(fee) = result.as_ref().ok().and_then(|resp| resp.value.fee_lamports) {
+            self.spend.record(fee);
+        }
+        if let (Some((cache, key)), Ok(resp)) = (dedup, &result) {
+            cache.insert(key, resp);
+        }
+        result
+    }
+
+    /// `GET` an endpoint the SDK doesn't wrap yet, parsing the response as `T`.
+    ///
+    /// `path` is relative to the base URL and may carry a query string. The request goes
+    /// through the same authentication, retries, rate limiting, deadline and errors as the
+    /// typed methods; see [`models::raw`] for untyped JSON access.
+    pub async fn get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<T, AethokitError> {
+        self.make_request::<(), T>(path, Method::GET, None).await
+    }
+
+    /// `POST` `body` as JSON to an endpoint the SDK doesn't wrap yet, parsing the response
+    /// as `T`, as with [`get`](Self::get).
+    ///
+    /// Failed POSTs are retried according to the policy's [`PostRetries`]: by default
+    /// only when the connection was refused, or on any retryable error if the client carries
+    /// an [`IDEMPOTENCY_KEY_HEADER`] (see [`with_header`](Self::with_header)).
+    pub async fn post<B: Serialize + ?Sized, T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, AethokitError> {
+        self.make_request(path, Method::POST, Some(body)).await
+    }
+
+    async fn submit_sponsor_tx(
+        self,
+        tx: String,
+        options: SponsorOptions,
+        broadcast: bool,
+    ) -> Result<Responded<SponsoredTx>, AethokitError> {
         let path = "sponsor-tx";
         let tx_req = SponsorTxRequest {
             transaction: tx,
             rpc_or_network: self.rpc_or_network.clone(),
+            commitment: options.commitment,
+            skip_preflight: options.skip_preflight,
+            max_retries: options.max_retries,
+            reservation_id: options.reservation_id.clone(),
+            broadcast: (!broadcast).then_some(false),
+            return_signed: (!broadcast || options.verifies_signature()).then_some(true),
+            label: options.label.clone().filter(|_| !self.minimal_metadata),
+            end_user_id: options.end_user_id.clone().filter(|_| !self.minimal_metadata),
+            campaign_id: options.campaign_id.clone().filter(|_| !self.minimal_metadata),
         };
-        let resp: SponsorTxResponse = self
-            .make_request::<SponsorTxRequest, SponsorTxResponse>(
-                path,
-                Method::POST,
-                Some(&tx_req),
-            )
-            .await?;
-        Ok(resp.hash)
+        #[cfg(feature = "cbor")]
+        let sent = self.send_cbor(path, &tx_req).await;
+        #[cfg(not(feature = "cbor"))]
+        let sent: Option<Result<Responded<SponsoredTx>, AethokitError>> = None;
+        let result = match sent {
+            Some(result) => result,
+            None => {
+                self.make_request_with_meta::<SponsorTxRequest, SponsoredTx>(
+                    path,
+                    Method::POST,
+                    Some(&tx_req),
+                )
+                .await
+            }
+        };
+        let result = reservation::map_result(result, options.reservation_id).map_err(|err| {
+            match err.code() {
+                Some(codes::BLOCKHASH_EXPIRED) => AethokitError::BlockhashExpired,
+                _ => err,
+            }
+        });
+        #[cfg(feature = "solana")]
+        let result = match result {
+            Ok(resp) if options.verify_signature => self
+                .verify_sponsorship(&tx_req.transaction, &resp.value)
+                .await
+                .map(|()| resp),
+            result => result,
+        };
+        self.audit(path, &tx_req.transaction, &result);
+        result
     }
 
     async fn make_request<B: Serialize + ?Sized, R: for<'de> Deserialize<'de>>(
@@ -87,26 +944,260 @@ impl Aethokit {
         method: Method,
         body: Option<&B>,
     ) -> Result<R, AethokitError> {
-        let url = self.base_url.join(path).expect("valid path join");
-        let mut req = self.http
-            .request(method, url)
-            .header("accept", "application/json")
-            .header("x-gas-key", &self.gas_key);
+        self.make_request_with_meta(path, method, body)
+            .await
+            .map(Responded::into_inner)
+    }
+
+    async fn make_request_with_meta<B: Serialize + ?Sized, R: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        method: Method,
+        body: Option<&B>,
+    ) -> Result<Responded<R>, AethokitError> {
+        if cfg!(debug_assertions) {
+            if let (Some(schema), Some(b)) = (schema::for_path(path), body) {
+                let errors = schema::validate(schema, &serde_json::to_value(b)?);
+                if !errors.is_empty() {
+                    return Err(AethokitError::InvalidRequestBody(errors));
+                }
+            }
+        }
+        // serialized once, so every attempt sends the same bytes
+        let body = body.map(serde_json::to_vec).transpose()?.map(Bytes::from);
+        self.send_body(path, method, body, JSON_CONTENT_TYPE).await
+    }
+
+    /// Send an already encoded body of `content_type`, recording metrics and adding the
+    /// trace wrapper.
+    async fn send_body<R: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        method: Method,
+        body: Option<Bytes>,
+        content_type: &'static str,
+    ) -> Result<Responded<R>, AethokitError> {
+        let url = self.join_url(path)?;
+        // holds a reproduction of the last request sent, when tracing
+        let traced = self.trace.then(Mutex::default);
+        let started = Instant::now();
+        let result = self
+            .within_scope(self.send_with_retries(url, method.clone(), body, content_type, traced.as_ref()))
+            .await;
+        let latency = started.elapsed();
+        let class = self.classify(&method, path, &result);
+        self.metrics.record(&method, path, latency, &result, &class);
+        #[cfg(feature = "otel")]
+        self.otel.record(&method, path, latency, &result, &class);
+        #[cfg(feature = "tracing")]
+        self.sampler.record(&method, path, latency, &result, &class);
+        match traced.and_then(|curl| curl.into_inner().unwrap()) {
+            Some(curl) => result.map_err(|error| AethokitError::Traced {
+                curl,
+                error: Box::new(error),
+            }),
+            None => result,
+        }
+    }
+
+    fn classify<T>(
+        &self,
+        method: &Method,
+        path: &str,
+        result: &Result<Responded<T>, AethokitError>,
+    ) -> std::borrow::Cow<'static, str> {
+        let (status, error) = match result {
+            Ok(resp) => (Some(resp.meta.status), None),
+            Err(err) => (err.status(), Some(err)),
+        };
+        self.classifier.0.classify(&RequestOutcome {
+            method,
+            endpoint: path.split('?').next().unwrap_or(path),
+            status,
+            error,
+        })
+    }
+
+    async fn send_with_retries<R: for<'de> Deserialize<'de>>(
+        &self,
+        url: Url,
+        method: Method,
+        body: Option<Bytes>,
+        content_type: &'static str,
+        traced: Option<&Mutex<Option<String>>>,
+    ) -> Result<Responded<R>, AethokitError> {
+        self.load_gas_key().await?;
+        if let Some(bucket) = &self.retry_budget {
+            bucket.deposit();
+        }
+        let policy = match self.retry {
+            Some(policy) if method == Method::GET || policy.post_retries != PostRetries::Never => policy,
+            _ => return self.send_request(url, method, body, content_type, 1, traced).await,
+        };
+        let keyed = self.extra_headers.contains_key(IDEMPOTENCY_KEY_HEADER);
+        let may_resend = |err: &AethokitError| match method {
+            Method::GET => err.is_retryable(),
+            _ => policy.may_resend_post(keyed, err),
+        };
+        let mut retry = 0;
+        loop {
+            let attempt = retry + 1;
+            match self.send_request(url.clone(), method.clone(), body.clone(), content_type, attempt, traced).await {
+                Err(err) if retry < policy.max_retries && may_resend(&err) && self.may_retry() => {
+                    let retry_after = match &err {
+                        AethokitError::RateLimited { retry_after, .. } => *retry_after,
+                        _ => None,
+                    };
+                    self.scoped_sleep(policy.backoff(retry, retry_after)).await?;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Take a retry from the client's [`RetryBudget`], if it has one.
+    pub(crate) fn may_retry(&self) -> bool {
+        self.retry_budget.as_ref().is_none_or(|bucket| bucket.try_retry())
+    }
+
+    async fn send_request<R: for<'de> Deserialize<'de>>(
+        &self,
+        url: Url,
+        method: Method,
+        body: Option<Bytes>,
+        content_type: &'static str,
+        attempt: u32,
+        traced: Option<&Mutex<Option<String>>>,
+    ) -> Result<Responded<R>, AethokitError> {
+        self.with_base_url_failover(url, |url| async {
+            let Some(breaker) = &self.breaker else {
+                return self.send_attempt(url, method.clone(), body.clone(), content_type, attempt, traced).await;
+            };
+            let admission = breaker.admit()?;
+            let result = self.send_attempt(url, method.clone(), body.clone(), content_type, attempt, traced).await;
+            breaker.record(admission, &result);
+            result
+        })
+        .await
+    }
+
+    async fn send_attempt<R: for<'de> Deserialize<'de>>(
+        &self,
+        url: Url,
+        method: Method,
+        body: Option<Bytes>,
+        content_type: &'static str,
+        attempt: u32,
+        traced: Option<&Mutex<Option<String>>>,
+    ) -> Result<Responded<R>, AethokitError> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+        let endpoint = self.relative_path(&url);
+        #[cfg(feature = "tracing")]
+        logging::log_request(
+            self.log_redaction,
+            &method,
+            &endpoint,
+            &self.gas_key.get(),
+            body.as_deref().filter(|_| content_type == JSON_CONTENT_TYPE),
+        );
+        self.hooks.request(|| RequestInfo {
+            method: method.clone(),
+            url: url.clone(),
+            attempt,
+        });
+        let response_info = |started: Instant, status| ResponseInfo {
+            method: method.clone(),
+            url: url.clone(),
+            attempt,
+            status,
+            latency: started.elapsed(),
+        };
+        let mut req = self
+            .request(method.clone(), url.clone())
+            .header("accept", JSON_CONTENT_TYPE);
 
         if let Some(b) = body {
-            req = req.json(b);
+            req = req.header(CONTENT_TYPE, content_type).body(b);
         }
 
-        let res = req.send().await?;
-        let status = res.status();
-        let text = res.text().await?;
+        let started = Instant::now();
+        let sent = async {
+            let req = self.build_request(req)?;
+            if let Some(traced) = traced {
+                *traced.lock().unwrap() = Some(trace::curl_command(&req));
+            }
+            let res = self.middleware.send(&*self.transport.0, req).await?;
+            let status = res.status();
+            let headers = res.headers().clone();
+            Ok::<_, AethokitError>((status, headers, res.text().await?))
+        };
+        let (status, headers, text) = match sent.await {
+            Ok(sent) => sent,
+            Err(err) => {
+                self.hooks.response(|| response_info(started, None));
+                return Err(err);
+            }
+        };
+        self.hooks.response(|| response_info(started, Some(status)));
+        #[cfg(feature = "tracing")]
+        logging::log_response(self.log_redaction, &endpoint, status, &text);
+        let meta = ResponseMeta::new(status, &headers, started.elapsed());
+        self.observe_response(&endpoint, &headers, &meta);
 
         if !status.is_success() {
-            return Err(AethokitError::UnexpectedStatus { status, body: text });
+            return Err(AethokitError::from_status(status, method, &endpoint, text, meta));
         }
 
-        let parsed = serde_json::from_str::<R>(&text)?;
-        Ok(parsed)
+        let text = compat::upgrade_response(&endpoint, &headers, text);
+        let parsed = if self.strict {
+            let mut unknown = Vec::new();
+            let parsed = serde_ignored::deserialize(&mut serde_json::Deserializer::from_str(&text), |path| {
+                unknown.push(path.to_string())
+            })?;
+            if !unknown.is_empty() {
+                return Err(AethokitError::UnknownFields(unknown));
+            }
+            parsed
+        } else {
+            serde_json::from_str::<R>(&text)?
+        };
+        Ok(Responded { value: parsed, meta })
+    }
+
+    /// Record the deprecation, server version and rate-limit headers of a response.
+    pub(crate) fn observe_response(&self, endpoint: &str, headers: &HeaderMap, meta: &ResponseMeta) {
+        self.deprecations.observe(endpoint, headers);
+        self.server_version.observe(headers);
+        if meta.rate_limit.is_some() {
+            *self.rate_limit.lock().unwrap() = meta.rate_limit;
+        }
+    }
+
+    /// Start a request carrying the gas key, the SDK and schema versions and any per-client
+    /// extra headers.
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let req = self
+            .http
+            .request(method, url)
+            .headers(self.extra_headers.clone())
+            .header("x-gas-key", &*self.gas_key.get());
+        let req = match self.minimal_metadata {
+            true => req,
+            false => req.header(compat::SDK_VERSION_HEADER, env!("CARGO_PKG_VERSION")),
+        };
+        req.header(compat::ACCEPT_VERSION_HEADER, API_SCHEMA_VERSION)
+    }
+
+    /// Build `req`, signing it if the client has a signing secret.
+    fn build_request(&self, req: RequestBuilder) -> Result<reqwest::Request, AethokitError> {
+        let mut req = req.build()?;
+        if let Some(secret) = &self.signing_secret {
+            secret.sign(&mut req);
+        }
+        Ok(req)
     }
 }
 
@@ -125,23 +1216,100 @@ pub struct SponsorTxRequest {
     /// Optional RPC endpoint or network name
     #[serde(rename = "rpcOrNetwork", skip_serializing_if = "Option::is_none")]
     pub rpc_or_network: Option<String>,
+    /// Commitment the backend waits for before answering
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<CommitmentLevel>,
+    /// Skip the RPC node's preflight simulation
+    #[serde(rename = "skipPreflight", skip_serializing_if = "Option::is_none")]
+    pub skip_preflight: Option<bool>,
+    /// How many times the RPC node rebroadcasts the transaction
+    #[serde(rename = "maxRetries", skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    /// Quota reservation the sponsorship is charged to
+    #[serde(rename = "reservationId", skip_serializing_if = "Option::is_none")]
+    pub reservation_id: Option<ReservationId>,
+    /// Have the backend sign the transaction without sending it to the network
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcast: Option<bool>,
+    /// Ask the backend to include the fully-signed transaction in its answer
+    #[serde(rename = "returnSigned", skip_serializing_if = "Option::is_none")]
+    pub return_signed: Option<bool>,
+    /// Feature or flow the sponsorship belongs to, for usage reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The app's identifier of the end user the transaction is for
+    #[serde(rename = "endUserId", skip_serializing_if = "Option::is_none")]
+    pub end_user_id: Option<String>,
+    /// Campaign the sponsorship is attributed to
+    #[serde(rename = "campaignId", skip_serializing_if = "Option::is_none")]
+    pub campaign_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct SponsorTxResponse {
-    hash: String,
+/// A sponsorship accepted by the backend, returned by [`Aethokit::sponsor_tx_detailed`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SponsoredTx {
+    /// Transaction hash (signature)
+    #[serde(rename = "hash")]
+    pub signature: String,
+    /// Slot the transaction landed in, if the backend already knows it
+    #[serde(default, deserialize_with = "crate::lenient::option_u64")]
+    pub slot: Option<u64>,
+    /// Fee paid by the gas tank, in lamports, if the backend already knows it
+    #[serde(default, rename = "feeLamports", alias = "feePaid", deserialize_with = "crate::lenient::option_u64")]
+    pub fee_lamports: Option<u64>,
+    /// Time the transaction was sponsored, if the backend reports it
+    #[serde(default, rename = "sponsoredAt", alias = "timestamp")]
+    pub sponsored_at: Option<DateTime<Utc>>,
+    /// The transaction as signed by the gas tank, base64, if requested with
+    /// [`SponsorOptions::verify_signature`]
+    #[serde(default, rename = "signedTransaction")]
+    pub signed_transaction: Option<String>,
+    /// What the fee was spent on, if the backend reports it
+    #[serde(default, rename = "feeBreakdown")]
+    pub fee_breakdown: Option<FeeBreakdown>,
+    /// Set when the client is in dry-run mode and nothing was sent, see
+    /// [`AethokitBuilder::dry_run`]
+    #[serde(skip)]
+    pub dry_run: bool,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Clones share the gas address cache, rate-limit state and retry budget instead of
+    /// starting over with their own.
+    #[tokio::test]
+    async fn clones_share_state() {
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let backend = FakeBackend::new();
+        backend.on(
+            "GET",
+            "get-gas-address",
+            FakeResponse::json(200, serde_json::json!({ "gasAddress": "tank" }))
+                .header("x-ratelimit-remaining", "7"),
+        );
+        let client = backend.client().retry_budget(RetryBudget::default()).build().unwrap();
+        let clone = client.with_header("x-tenant", "acme").unwrap();
+
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        assert_eq!(clone.get_gas_address().await.unwrap(), "tank");
+        assert_eq!(backend.requests().len(), 1);
+        assert_eq!(clone.rate_limit_info().and_then(|info| info.remaining), Some(7));
+        assert!(Arc::ptr_eq(
+            client.retry_budget.as_ref().unwrap(),
+            clone.retry_budget.as_ref().unwrap()
+        ));
+    }
+
     /// Verify that initializing the SDK with an empty string results in a MissingGasKey error.
     #[test]
     fn rejects_empty_key() {
         let cfg = AethokitConfig {
             gas_key: "".to_string(),
             rpc_or_network: None,
+            ..Default::default()
         };
         let err = Aethokit::new(cfg).unwrap_err();
         match err {
@@ -149,4 +1317,161 @@ mod tests {
             other => panic!("expected MissingGasKey, got {other:?}"),
         }
     }
+
+    /// Calls through a deadline-scoped client give up when the deadline passes.
+    #[tokio::test]
+    async fn enforces_deadline() {
+        // accepts connections but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url(format!("http://{}/", listener.local_addr().unwrap()))
+            .build()
+            .unwrap()
+            .with_deadline(Instant::now() + Duration::from_millis(50));
+        let err = client.get_gas_address().await.unwrap_err();
+        assert!(matches!(err, AethokitError::DeadlineExceeded), "got {err:?}");
+    }
+
+    /// A passed deadline fails before anything is sent; no deadline leaves the client as is.
+    #[tokio::test]
+    async fn passes_optional_deadlines_through() {
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let backend = FakeBackend::new();
+        let body = serde_json::json!({ "gasAddress": "tank" });
+        backend.on("GET", "get-gas-address", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let remaining: Option<Instant> = None;
+        assert_eq!(client.with_deadline(remaining).get_gas_address().await.unwrap(), "tank");
+        let expired = client.with_deadline(Instant::now() - Duration::from_millis(1));
+        assert!(matches!(
+            expired.refresh_gas_address().await,
+            Err(AethokitError::DeadlineExceeded)
+        ));
+        assert_eq!(backend.requests().len(), 1);
+    }
+
+    /// Classification looks through trace wrappers and treats 429 as retryable, not a client error.
+    #[test]
+    fn classifies_errors() {
+        let rejected = AethokitError::Traced {
+            curl: "curl".to_string(),
+            error: Box::new(AethokitError::unexpected_status(
+                StatusCode::BAD_REQUEST,
+                Method::POST,
+                "sponsor-tx",
+                r#"{"error":"bad tx"}"#.to_string(),
+                Some("req-1".to_string()),
+            )),
+        };
+        assert_eq!(rejected.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(rejected.request_id(), Some("req-1"));
+        assert_eq!(rejected.body_json().unwrap()["error"], "bad tx");
+        assert!(rejected.is_client_error() && !rejected.is_retryable());
+
+        let limited = AethokitError::RateLimited {
+            retry_after: None,
+            body: String::new(),
+            request_id: None,
+        };
+        assert!(limited.is_rate_limited() && limited.is_retryable() && !limited.is_client_error());
+        assert!(!AethokitError::MissingGasKey.is_retryable());
+    }
+
+    /// Cancelling the token fails in-flight and later calls of the scoped client.
+    #[tokio::test]
+    async fn honours_cancellation() {
+        // accepts connections but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let token = CancellationToken::new();
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url(format!("http://{}/", listener.local_addr().unwrap()))
+            .build()
+            .unwrap()
+            .with_cancellation(token.clone());
+        let call = client.get_gas_address();
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel();
+        };
+        let (result, ()) = futures_util::join!(call, cancel);
+        assert!(matches!(result, Err(AethokitError::Cancelled)), "got {result:?}");
+        assert!(matches!(client.get_gas_address().await, Err(AethokitError::Cancelled)));
+    }
+
+    /// A fresh cached gas address is returned without a request; an expired one is refetched.
+    #[tokio::test]
+    async fn caches_gas_address() {
+        let clock = Arc::new(crate::test_util::MockClock::new());
+        // nothing listens on port 1, so any request fails
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url("http://127.0.0.1:1/")
+            .gas_address_ttl(Duration::from_secs(60))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        client.cache_set(GAS_ADDRESS_CACHE, "tank".to_string(), client.gas_address_ttl).await;
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        assert!(client.refresh_gas_address().await.is_err());
+
+        clock.advance(Duration::from_secs(61));
+        assert!(client.get_gas_address().await.is_err());
+    }
+
+    /// Fields beyond the hash are optional, so older backends still parse.
+    #[test]
+    fn parses_sponsored_tx() {
+        let bare: SponsoredTx = serde_json::from_str(r#"{"hash":"sig"}"#).unwrap();
+        assert_eq!(bare.signature, "sig");
+        assert_eq!(bare.fee_lamports, None);
+
+        let full: SponsoredTx =
+            serde_json::from_str(r#"{"hash":"sig","slot":7,"feePaid":5000,"timestamp":"2024-05-01T14:00:00+02:00"}"#)
+                .unwrap();
+        assert_eq!((full.slot, full.fee_lamports), (Some(7), Some(5000)));
+        assert_eq!(full.sponsored_at, Some("2024-05-01T12:00:00Z".parse().unwrap()));
+    }
+
+    /// The escape hatches reach unwrapped endpoints with the client's auth.
+    #[tokio::test]
+    async fn calls_unwrapped_endpoints() {
+        use crate::test_util::{FakeBackend, FakeResponse};
+        use serde_json::{json, Value};
+
+        let backend = FakeBackend::new();
+        backend
+            .on("GET", "beta/limits", FakeResponse::json(200, json!({ "daily": 10 })))
+            .on("POST", "beta/echo", FakeResponse::json(200, json!({ "ok": true })));
+        let client = backend.client().build().unwrap();
+
+        let limits: Value = client.get("beta/limits?tank=a").await.unwrap();
+        assert_eq!(limits["daily"], 10);
+        let echoed: Value = client.post("beta/echo", &json!({ "x": 1 })).await.unwrap();
+        assert_eq!(echoed["ok"], true);
+        let requests = backend.requests();
+        assert_eq!(requests[0].path, "beta/limits?tank=a");
+        assert_eq!(requests[1].body, Some(json!({ "x": 1 })));
+    }
+
+    /// Unknown response fields are ignored unless strict parsing is on.
+    #[tokio::test]
+    async fn rejects_unknown_fields_when_strict() {
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let backend = FakeBackend::new();
+        let body = serde_json::json!({ "gasAddress": "tank", "region": "eu" });
+        backend.on("GET", "get-gas-address", FakeResponse::json(200, body));
+        let lenient = backend.client().build().unwrap();
+        assert_eq!(lenient.refresh_gas_address().await.unwrap(), "tank");
+
+        let strict = backend.client().strict(true).build().unwrap();
+        match strict.refresh_gas_address().await {
+            Err(AethokitError::UnknownFields(fields)) => assert_eq!(fields, vec!["region".to_string()]),
+            other => panic!("expected UnknownFields, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use crate::clock::Time;
+
+/// Token-bucket limiter shared by every clone of a client.
+///
+/// Waiters hold the lock while they sleep, so queued requests are released in
+/// the order they arrived.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    per_second: f64,
+    bucket: Mutex<Bucket>,
+    time: Time,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `per_second` requests per second, with bursts of up to `per_second` requests.
+    pub(crate) fn new(per_second: u32, time: Time) -> Self {
+        let per_second = f64::from(per_second.max(1));
+        Self {
+            per_second,
+            bucket: Mutex::new(Bucket {
+                tokens: per_second,
+                last_refill: time.now(),
+            }),
+            time,
+        }
+    }
+
+    /// Wait until a request may be sent.
+    pub(crate) async fn acquire(&self) {
+        let mut bucket = self.bucket.lock().await;
+        self.refill(&mut bucket);
+        if bucket.tokens < 1.0 {
+            let wait = (1.0 - bucket.tokens) / self.per_second;
+            self.time.sleep(Duration::from_secs_f64(wait)).await;
+            self.refill(&mut bucket);
+        }
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = self.time.now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.per_second);
+        bucket.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// After the initial burst is spent, further requests are spaced at the configured rate.
+    #[tokio::test(start_paused = true)]
+    async fn spaces_requests_after_burst() {
+        let limiter = RateLimiter::new(2, Time::default());
+        let start = crate::rt::Instant::now();
+        for _ in 0..4 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed().as_millis(), 1000);
+    }
+}
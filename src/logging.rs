@@ -0,0 +1,124 @@
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::trace::REDACTED_KEY;
+
+/// How the gas key, transactions and addresses appear in the `debug` request and response
+/// logs (target `aethokit::http`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Redaction {
+    /// Replace them with `<redacted>`
+    #[default]
+    Full,
+    /// Replace them with a short SHA-256 fingerprint, so equal values can be matched across
+    /// log lines without being readable
+    Fingerprint,
+    /// Log them as they are; for local development only
+    Plaintext,
+}
+
+impl Redaction {
+    fn redact(self, value: &str) -> String {
+        match self {
+            Redaction::Full => REDACTED_KEY.to_string(),
+            Redaction::Fingerprint => {
+                let digest = Sha256::digest(value.as_bytes());
+                format!("sha256:{}", &hex::encode(digest)[..12])
+            }
+            Redaction::Plaintext => value.to_string(),
+        }
+    }
+
+    /// A JSON body with sensitive string fields redacted; bodies that aren't JSON are kept.
+    fn redact_body(self, body: &str) -> String {
+        if self == Redaction::Plaintext {
+            return body.to_string();
+        }
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                self.redact_value(&mut value);
+                value.to_string()
+            }
+            Err(_) => body.to_string(),
+        }
+    }
+
+    fn redact_value(self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    match field {
+                        Value::String(s) if is_sensitive(key) => *s = self.redact(s),
+                        _ => self.redact_value(field),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            _ => {}
+        }
+    }
+}
+
+/// Transactions and anything holding an address.
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key == "transaction" || key == "recipient" || key.contains("address")
+}
+
+pub(crate) fn log_request(
+    redaction: Redaction,
+    method: &Method,
+    endpoint: &str,
+    gas_key: &str,
+    body: Option<&[u8]>,
+) {
+    if tracing::enabled!(target: "aethokit::http", tracing::Level::DEBUG) {
+        let body = body.map(|b| redaction.redact_body(&String::from_utf8_lossy(b)));
+        tracing::debug!(
+            target: "aethokit::http",
+            %method,
+            endpoint,
+            gas_key = %redaction.redact(gas_key),
+            body,
+            "sending request"
+        );
+    }
+}
+
+pub(crate) fn log_response(redaction: Redaction, endpoint: &str, status: StatusCode, body: &str) {
+    if tracing::enabled!(target: "aethokit::http", tracing::Level::DEBUG) {
+        tracing::debug!(
+            target: "aethokit::http",
+            endpoint,
+            status = status.as_u16(),
+            body = %redaction.redact_body(body),
+            "received response"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nested transaction and address fields are redacted, everything else is kept.
+    #[test]
+    fn redacts_sensitive_fields() {
+        let body = r#"{"transaction":"AQID","rpcOrNetwork":"devnet","items":[{"gasAddress":"Gas111","slot":7}]}"#;
+        assert_eq!(
+            Redaction::Full.redact_body(body),
+            r#"{"items":[{"gasAddress":"<redacted>","slot":7}],"rpcOrNetwork":"devnet","transaction":"<redacted>"}"#
+        );
+        let fingerprinted = Redaction::Fingerprint.redact_body(body);
+        assert!(
+            !fingerprinted.contains("Gas111") && fingerprinted.contains("\"gasAddress\":\"sha256:")
+        );
+        assert_eq!(
+            Redaction::Fingerprint.redact("key"),
+            Redaction::Fingerprint.redact("key")
+        );
+        assert_eq!(Redaction::Plaintext.redact_body(body), body);
+        assert_eq!(Redaction::Full.redact_body("not json"), "not json");
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, DATE};
+use reqwest::StatusCode;
+
+use crate::RateLimitInfo;
+
+/// Header the backend uses to identify each request in its logs.
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Details about the HTTP exchange behind a result, useful when filing support tickets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    /// Backend request ID (`x-request-id`), if the server sent one
+    pub request_id: Option<String>,
+    /// Server time from the `date` header, as sent
+    pub server_date: Option<String>,
+    /// Time from sending the request to reading the full response body
+    pub latency: Duration,
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Gas key that served the request: 0 for the primary key, then fallbacks in order
+    pub gas_key_index: usize,
+    /// Base URL that served the request: 0 for the primary, then fallbacks in order
+    pub base_url_index: usize,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(status: StatusCode, headers: &HeaderMap, latency: Duration) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            status,
+            request_id: header(REQUEST_ID_HEADER),
+            server_date: header(DATE.as_str()),
+            latency,
+            rate_limit: RateLimitInfo::from_headers(headers),
+            gas_key_index: 0,
+            base_url_index: 0,
+        }
+    }
+}
+
+/// A result paired with the metadata of the response it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Responded<T> {
+    pub value: T,
+    pub meta: ResponseMeta,
+}
+
+impl<T> Responded<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Responded<U> {
+        Responded {
+            value: f(self.value),
+            meta: self.meta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Request ID and server date are read from their headers when present.
+    #[test]
+    fn reads_meta_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, "req_123".parse().unwrap());
+        headers.insert(DATE, "Tue, 15 Nov 1994 08:12:31 GMT".parse().unwrap());
+        let meta = ResponseMeta::new(StatusCode::OK, &headers, Duration::from_millis(42));
+        assert_eq!(meta.request_id.as_deref(), Some("req_123"));
+        assert_eq!(meta.server_date.as_deref(), Some("Tue, 15 Nov 1994 08:12:31 GMT"));
+        assert_eq!(meta.rate_limit, None);
+    }
+}
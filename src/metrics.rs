@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::Method;
+
+use crate::{Aethokit, AethokitError, Responded, RetryBudgetStats};
+
+/// `Content-Type` to serve [`Aethokit::render_openmetrics`] output with.
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Request counters and latency histograms, shared between clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    endpoints: Mutex<BTreeMap<(String, String), EndpointStats>>,
+}
+
+#[derive(Debug, Default)]
+struct EndpointStats {
+    /// Requests by status code (`error` when no response was received) and class
+    outcomes: BTreeMap<(String, String), u64>,
+    /// Non-cumulative counts per bucket, with a final `+Inf` bucket
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    latency_sum: f64,
+}
+
+impl EndpointStats {
+    fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+}
+
+impl Metrics {
+    /// Count a finished request to `path` (query string ignored).
+    pub(crate) fn record<T>(
+        &self,
+        method: &Method,
+        path: &str,
+        latency: Duration,
+        result: &Result<Responded<T>, AethokitError>,
+        class: &str,
+    ) {
+        let endpoint = path.split('?').next().unwrap_or(path).to_string();
+        let outcome = match result {
+            Ok(resp) => resp.meta.status.as_u16().to_string(),
+            Err(err) => err.status().map_or("error".to_string(), |status| status.as_u16().to_string()),
+        };
+        let seconds = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&le| seconds <= le)
+            .unwrap_or(LATENCY_BUCKETS.len());
+
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let stats = endpoints.entry((endpoint, method.to_string())).or_default();
+        *stats.outcomes.entry((outcome, class.to_string())).or_default() += 1;
+        stats.buckets[bucket] += 1;
+        stats.latency_sum += seconds;
+    }
+
+    fn render(&self, retry_budget: Option<RetryBudgetStats>) -> String {
+        let endpoints = self.endpoints.lock().unwrap();
+        let mut out = String::new();
+        out.push_str("# TYPE aethokit_requests counter\n");
+        out.push_str("# HELP aethokit_requests Aethokit API requests by endpoint, response status and class.\n");
+        for ((endpoint, method), stats) in endpoints.iter() {
+            for ((status, class), count) in &stats.outcomes {
+                let _ = writeln!(
+                    out,
+                    "aethokit_requests_total{{endpoint=\"{endpoint}\",method=\"{method}\",status=\"{status}\",class=\"{class}\"}} {count}"
+                );
+            }
+        }
+        out.push_str("# TYPE aethokit_request_duration_seconds histogram\n");
+        out.push_str("# UNIT aethokit_request_duration_seconds seconds\n");
+        out.push_str("# HELP aethokit_request_duration_seconds Aethokit API request latency.\n");
+        for ((endpoint, method), stats) in endpoints.iter() {
+            let labels = format!("endpoint=\"{endpoint}\",method=\"{method}\"");
+            let mut cumulative = 0;
+            for (i, count) in stats.buckets.iter().enumerate() {
+                cumulative += count;
+                let le = LATENCY_BUCKETS.get(i).map_or("+Inf".to_string(), |le| format!("{le:?}"));
+                let _ = writeln!(
+                    out,
+                    "aethokit_request_duration_seconds_bucket{{{labels},le=\"{le}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(out, "aethokit_request_duration_seconds_sum{{{labels}}} {}", stats.latency_sum);
+            let _ = writeln!(out, "aethokit_request_duration_seconds_count{{{labels}}} {}", stats.count());
+        }
+        if let Some(stats) = retry_budget {
+            out.push_str("# TYPE aethokit_retry_budget_tokens gauge\n");
+            out.push_str("# HELP aethokit_retry_budget_tokens Retries the retry budget allows right now.\n");
+            let _ = writeln!(out, "aethokit_retry_budget_tokens {}", stats.available);
+            out.push_str("# TYPE aethokit_retry_budget_retries counter\n");
+            out.push_str("# HELP aethokit_retry_budget_retries Retries by whether the retry budget allowed them.\n");
+            let _ = writeln!(out, "aethokit_retry_budget_retries_total{{outcome=\"allowed\"}} {}", stats.retries);
+            let _ = writeln!(out, "aethokit_retry_budget_retries_total{{outcome=\"denied\"}} {}", stats.denied);
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+impl Aethokit {
+    /// Render the client's request counters and latency histograms in the OpenMetrics
+    /// text format, ready to serve with [`OPENMETRICS_CONTENT_TYPE`] from a `/metrics` route.
+    ///
+    /// Covers every request made through this client and its clones, and the use of its
+    /// [`RetryBudget`](crate::RetryBudget) if it has one.
+    pub fn render_openmetrics(&self) -> String {
+        self.metrics.render(self.retry_budget_stats())
+    }
+
+    /// Tokens left in the client's [`RetryBudget`](crate::RetryBudget) and the retries it
+    /// allowed and refused, `None` without a budget.
+    pub fn retry_budget_stats(&self) -> Option<RetryBudgetStats> {
+        self.retry_budget.as_ref().map(|bucket| bucket.stats())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::header::HeaderMap;
+    use reqwest::StatusCode;
+
+    use crate::ResponseMeta;
+
+    /// Requests are grouped by endpoint without the query string, with cumulative buckets,
+    /// followed by the retry budget.
+    #[test]
+    fn renders_openmetrics() {
+        let metrics = Metrics::default();
+        let ok = Ok(Responded {
+            value: (),
+            meta: ResponseMeta::new(StatusCode::OK, &HeaderMap::new(), Duration::ZERO),
+        });
+        metrics.record(&Method::GET, "get-sponsored-tx?hash=abc", Duration::from_millis(80), &ok, "success");
+        metrics.record(&Method::GET, "get-sponsored-tx?hash=def", Duration::from_secs(20), &ok, "success");
+        metrics.record::<()>(
+            &Method::GET,
+            "get-sponsored-tx",
+            Duration::from_millis(10),
+            &Err(AethokitError::MissingGasKey),
+            "sdk_error",
+        );
+
+        let stats = RetryBudgetStats {
+            available: 2.5,
+            retries: 4,
+            denied: 1,
+        };
+        let text = metrics.render(Some(stats));
+        let labels = "endpoint=\"get-sponsored-tx\",method=\"GET\"";
+        assert!(text.contains(&format!("aethokit_requests_total{{{labels},status=\"200\",class=\"success\"}} 2\n")));
+        assert!(text.contains(&format!("aethokit_requests_total{{{labels},status=\"error\",class=\"sdk_error\"}} 1\n")));
+        assert!(text.contains(&format!("aethokit_request_duration_seconds_bucket{{{labels},le=\"0.1\"}} 2\n")));
+        assert!(text.contains(&format!("aethokit_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} 3\n")));
+        assert!(text.contains(&format!("aethokit_request_duration_seconds_count{{{labels}}} 3\n")));
+        assert!(text.contains("aethokit_retry_budget_tokens 2.5\n"));
+        assert!(text.contains("aethokit_retry_budget_retries_total{outcome=\"denied\"} 1\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
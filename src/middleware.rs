@@ -0,0 +1,152 @@
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use reqwest::{Request, Response};
+
+use crate::{AethokitError, HttpTransport};
+
+/// Logic run around every request the client sends to the Aethokit API, such as
+/// refreshing auth tokens, audit logging or injecting failures in tests.
+///
+/// Middleware runs in the order it was added to the builder, after the gas key and
+/// default headers are set and before the response status is checked. Call
+/// [`Next::run`] to pass the request on, or return a response directly to short-circuit.
+///
+/// Request bodies are buffered, so [`Request::try_clone`] always succeeds and middleware
+/// can replay a request, POSTs included, with the exact same bytes.
+///
+/// ```
+/// use aethokit::{AethokitError, Middleware, Next};
+/// use futures_util::future::BoxFuture;
+///
+/// struct AuditLog;
+///
+/// impl Middleware for AuditLog {
+///     fn handle<'a>(
+///         &'a self,
+///         request: reqwest::Request,
+///         next: Next<'a>,
+///     ) -> BoxFuture<'a, Result<reqwest::Response, AethokitError>> {
+///         Box::pin(async move {
+///             let url = request.url().clone();
+///             let response = next.run(request).await?;
+///             println!("{url} -> {}", response.status());
+///             Ok(response)
+///         })
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>>;
+}
+
+/// The rest of the middleware chain, ending with the client's [`HttpTransport`].
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    transport: &'a dyn HttpTransport,
+    chain: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    /// Pass `request` to the next middleware, or send it if this is the last one.
+    pub fn run(self, request: Request) -> BoxFuture<'a, Result<Response, AethokitError>> {
+        match self.chain.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    transport: self.transport,
+                    chain: rest,
+                },
+            ),
+            None => self.transport.execute(request),
+        }
+    }
+}
+
+/// Middleware added to a builder, shared by the clients it builds and their clones.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.0.push(middleware);
+    }
+
+    /// Send `request` through the chain.
+    pub(crate) async fn send(
+        &self,
+        transport: &dyn HttpTransport,
+        request: Request,
+    ) -> Result<Response, AethokitError> {
+        Next {
+            transport,
+            chain: &self.0,
+        }
+        .run(request)
+        .await
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiddlewareChain({} middleware)", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use crate::Aethokit;
+
+    /// Middleware sees the authenticated request and can answer it without sending it.
+    #[tokio::test]
+    async fn short_circuits_requests() {
+        #[derive(Default)]
+        struct Stub(Mutex<Vec<String>>);
+        impl Middleware for Stub {
+            fn handle<'a>(&'a self, request: Request, _next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+                let key = request.headers()["x-gas-key"].to_str().unwrap().to_string();
+                self.0.lock().unwrap().push(key);
+                let response = http::Response::new(r#"{"gasAddress":"tank"}"#);
+                Box::pin(async { Ok(Response::from(response)) })
+            }
+        }
+
+        let stub = Arc::new(Stub::default());
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .middleware(stub.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        assert_eq!(*stub.0.lock().unwrap(), vec!["key".to_string()]);
+    }
+
+    /// POST requests can be cloned and replayed, e.g. after a connection reset.
+    #[tokio::test]
+    async fn replays_buffered_bodies() {
+        struct Replay;
+        impl Middleware for Replay {
+            fn handle<'a>(&'a self, request: Request, _next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+                let copy = request.try_clone().expect("buffered body");
+                let body = |request: &Request| request.body().and_then(|body| body.as_bytes()).map(<[u8]>::to_vec);
+                assert!(body(&copy).is_some());
+                assert_eq!(body(&copy), body(&request));
+                assert_eq!(copy.headers()["content-type"], "application/json");
+                let response = http::Response::new(r#"{"hash":"sig"}"#);
+                Box::pin(async { Ok(Response::from(response)) })
+            }
+        }
+
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .middleware(Arc::new(Replay))
+            .build()
+            .unwrap();
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+    }
+}
@@ -0,0 +1,6 @@
+//! Data models for the Aethokit API.
+//!
+//! The typed request and response types are re-exported at the crate root; [`raw`]
+//! offers the same endpoints with untyped JSON in and out.
+
+pub mod raw;
@@ -0,0 +1,123 @@
+//! Untyped endpoint bindings.
+//!
+//! Each function sends its endpoint's request with `serde_json::Value` bodies and returns
+//! the response body as a `Value`, going through the same authentication, rate limiting,
+//! retries, deadline and errors as the typed methods on [`Aethokit`]. Useful for trying
+//! out fields or beta endpoints the typed layer doesn't cover yet.
+//!
+//! ```no_run
+//! # async fn run(client: &aethokit::Aethokit) -> Result<(), aethokit::AethokitError> {
+//! use aethokit::models::raw;
+//!
+//! for endpoint in raw::ENDPOINTS {
+//!     println!("{} {} - {}", endpoint.method, endpoint.path, endpoint.description);
+//! }
+//! let address = raw::get_gas_address(client).await?;
+//! println!("{}", address["gasAddress"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::Method;
+use serde_json::Value;
+use url::form_urlencoded;
+
+use crate::{Aethokit, AethokitError};
+
+/// An API endpoint, as exposed in this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Name of the binding in this module
+    pub name: &'static str,
+    pub method: Method,
+    /// Path relative to the client's base URL
+    pub path: &'static str,
+    pub description: &'static str,
+}
+
+/// Every endpoint with a binding in this module.
+pub const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        name: "get_gas_address",
+        method: Method::GET,
+        path: "get-gas-address",
+        description: "Gas tank address of the gas key",
+    },
+    Endpoint {
+        name: "sponsor_tx",
+        method: Method::POST,
+        path: "sponsor-tx",
+        description: "Sponsor a serialized transaction; body `{transaction, rpcOrNetwork?}`",
+    },
+    Endpoint {
+        name: "get_sponsored_txs",
+        method: Method::GET,
+        path: "get-sponsored-txs",
+        description: "Page of sponsorship history; query `cursor`, `limit`, `status`, `since`",
+    },
+    Endpoint {
+        name: "get_sponsored_tx",
+        method: Method::GET,
+        path: "get-sponsored-tx",
+        description: "Single sponsored transaction; query `hash`",
+    },
+];
+
+/// `GET get-gas-address`
+pub async fn get_gas_address(client: &Aethokit) -> Result<Value, AethokitError> {
+    call(client, Method::GET, "get-gas-address", &[], None).await
+}
+
+/// `POST sponsor-tx`
+pub async fn sponsor_tx(client: &Aethokit, body: &Value) -> Result<Value, AethokitError> {
+    call(client, Method::POST, "sponsor-tx", &[], Some(body)).await
+}
+
+/// `GET get-sponsored-txs`
+pub async fn get_sponsored_txs(client: &Aethokit, query: &[(&str, &str)]) -> Result<Value, AethokitError> {
+    call(client, Method::GET, "get-sponsored-txs", query, None).await
+}
+
+/// `GET get-sponsored-tx`
+pub async fn get_sponsored_tx(client: &Aethokit, hash: &str) -> Result<Value, AethokitError> {
+    call(client, Method::GET, "get-sponsored-tx", &[("hash", hash)], None).await
+}
+
+/// Call any endpoint, including ones not listed in [`ENDPOINTS`].
+///
+/// `path` is relative to the client's base URL and `query` is URL-encoded onto it.
+pub async fn call(
+    client: &Aethokit,
+    method: Method,
+    path: &str,
+    query: &[(&str, &str)],
+    body: Option<&Value>,
+) -> Result<Value, AethokitError> {
+    client.make_request(&with_query(path, query), method, body).await
+}
+
+fn with_query(path: &str, query: &[(&str, &str)]) -> String {
+    if query.is_empty() {
+        return path.to_string();
+    }
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(query)
+        .finish();
+    format!("{path}?{query}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Listed endpoints are unique and query parameters are URL-encoded onto the path.
+    #[test]
+    fn describes_endpoints() {
+        let mut names: Vec<_> = ENDPOINTS.iter().map(|endpoint| endpoint.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), ENDPOINTS.len());
+        assert_eq!(with_query("get-sponsored-tx", &[("hash", "a b")]), "get-sponsored-tx?hash=a+b");
+        assert_eq!(with_query("get-gas-address", &[]), "get-gas-address");
+    }
+}
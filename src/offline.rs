@@ -0,0 +1,161 @@
+//! Building sponsorship payloads without network access.
+//!
+//! Everything here is synchronous and never touches the network, so an air-gapped machine
+//! can produce the exact base64 payload that an online machine later submits with
+//! [`Aethokit::sponsor_tx`](crate::Aethokit::sponsor_tx). The online side supplies what the
+//! offline side can't look up: the gas tank's address from
+//! [`get_gas_address`](crate::Aethokit::get_gas_address) and a blockhash, best a durable
+//! nonce so the payload doesn't expire while it is carried across.
+//!
+//! ```no_run
+//! # fn run(gas_address: solana_sdk::pubkey::Pubkey, nonce: solana_sdk::hash::Hash,
+//! #     ix: solana_sdk::instruction::Instruction, sender: &solana_sdk::signature::Keypair)
+//! #     -> Result<(), aethokit::AethokitError> {
+//! use aethokit::offline;
+//!
+//! let payload = offline::build_payload(&gas_address, &[ix], &[sender], nonce)?;
+//! // carry `payload` to the online machine and call `client.sponsor_tx(payload)` there
+//! # Ok(())
+//! # }
+//! ```
+
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+use crate::signer::{missing_signers, required_keys, SignerError};
+use crate::tx::Priority;
+use crate::{encode_transaction, AethokitError};
+
+/// `instructions` preceded by the compute budget instructions for `compute_unit_limit` and
+/// `priority`, where set.
+pub fn with_compute_budget(
+    instructions: impl IntoIterator<Item = Instruction>,
+    priority: Option<Priority>,
+    compute_unit_limit: Option<u32>,
+) -> Vec<Instruction> {
+    let budget = compute_unit_limit
+        .map(ComputeBudgetInstruction::set_compute_unit_limit)
+        .into_iter()
+        .chain(priority.map(|priority| {
+            ComputeBudgetInstruction::set_compute_unit_price(priority.micro_lamports_per_cu())
+        }));
+    budget.chain(instructions).collect()
+}
+
+/// Legacy message for `instructions` with the gas tank at `gas_address` as fee payer.
+pub fn sponsored_message(
+    gas_address: &Pubkey,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+) -> Message {
+    let mut message = Message::new(instructions, Some(gas_address));
+    message.recent_blockhash = recent_blockhash;
+    message
+}
+
+/// Sign the slots of `signers` in `tx`, leaving the others, such as the gas tank's, untouched.
+///
+/// # Errors
+/// - `Signer(MalformedTransaction)` if the header requires more signers than `tx` has keys
+/// - `Signer(NotASigner)` if a signer is not a required signer of `tx`
+/// - `Signer(Failed)` if a signer fails
+pub fn partial_sign(tx: &mut Transaction, signers: &[&dyn Signer]) -> Result<(), AethokitError> {
+    let required = usize::from(tx.message.header.num_required_signatures);
+    let keys = required_keys(&tx.message.account_keys, required, required)?.to_vec();
+    tx.signatures.resize(required, Signature::default());
+    let message = tx.message_data();
+    for signer in signers {
+        let pubkey = signer.pubkey();
+        let index = keys
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or(SignerError::NotASigner(pubkey))?;
+        let signature = signer.try_sign_message(&message).map_err(|err| SignerError::Failed {
+            pubkey,
+            source: Box::new(err),
+        })?;
+        tx.signatures[index] = signature;
+    }
+    Ok(())
+}
+
+/// Build, partially sign and encode a transaction for [`Aethokit::sponsor_tx`](crate::Aethokit::sponsor_tx).
+///
+/// # Errors
+/// - `Signer(MissingSignature)` if a required signer other than the gas tank was not provided
+/// - Any error from [`partial_sign`]
+pub fn build_payload(
+    gas_address: &Pubkey,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer],
+    recent_blockhash: Hash,
+) -> Result<String, AethokitError> {
+    let mut tx = Transaction::new_unsigned(sponsored_message(
+        gas_address,
+        instructions,
+        recent_blockhash,
+    ));
+    partial_sign(&mut tx, signers)?;
+    if let Some(&missing) = missing_signers(&tx)?.first() {
+        return Err(SignerError::MissingSignature(missing).into());
+    }
+    encode_transaction(&tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_sdk::signature::Keypair;
+
+    use crate::codec::RawTransaction;
+
+    /// The payload leaves only the gas tank's slot unsigned, and unknown or missing signers
+    /// are reported.
+    #[test]
+    fn builds_payload_offline() {
+        let gas_address = Pubkey::new_unique();
+        let sender = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transfer = solana_system_interface::instruction::transfer(&sender.pubkey(), &to, 1_000);
+        let instructions = with_compute_budget([transfer], Some(Priority::Low), None);
+        assert_eq!(instructions.len(), 2);
+
+        let blockhash = Hash::new_unique();
+        let payload = build_payload(&gas_address, &instructions, &[&sender], blockhash).unwrap();
+        let raw = RawTransaction::from_base64(&payload).unwrap();
+        assert_eq!(raw.unsigned_slots(), vec![0]);
+        assert_eq!(raw.message.account_keys[0], gas_address.to_bytes());
+        assert_eq!(raw.message.recent_blockhash, blockhash.to_bytes());
+
+        let stranger = Keypair::new();
+        assert!(matches!(
+            build_payload(&gas_address, &instructions, &[&stranger], blockhash),
+            Err(AethokitError::Signer(SignerError::NotASigner(_)))
+        ));
+        assert!(matches!(
+            build_payload(&gas_address, &instructions, &[], blockhash),
+            Err(AethokitError::Signer(SignerError::MissingSignature(_)))
+        ));
+    }
+
+    /// A header requiring more signers than the message has keys is an error, not a panic.
+    #[test]
+    fn rejects_oversized_signer_count() {
+        let sender = Keypair::new();
+        let transfer = solana_system_interface::instruction::transfer(&sender.pubkey(), &Pubkey::new_unique(), 1);
+        let message = sponsored_message(&Pubkey::new_unique(), &[transfer], Hash::new_unique());
+        let mut tx = Transaction::new_unsigned(message);
+        tx.message.header.num_required_signatures = 200;
+        assert!(matches!(
+            partial_sign(&mut tx, &[&sender]),
+            Err(AethokitError::Signer(SignerError::MalformedTransaction { required: 200, .. }))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{Aethokit, AethokitError, CommitmentLevel, ReservationId, SponsorTxRequest, SponsoredTx};
+
+/// Per-call settings for [`Aethokit::sponsor_tx_with_options`], forwarded to the backend.
+///
+/// Unset fields keep the backend's defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SponsorOptions {
+    /// Commitment the backend waits for before answering
+    pub commitment: Option<CommitmentLevel>,
+    /// Skip the RPC node's preflight simulation, trading safety for latency
+    pub skip_preflight: Option<bool>,
+    /// How many times the RPC node rebroadcasts the transaction before giving up
+    pub max_retries: Option<u32>,
+    /// Charge the sponsorship to this hold from [`Aethokit::reserve_quota`]
+    pub reservation_id: Option<ReservationId>,
+    /// Feature or flow the sponsorship belongs to, so usage reports can be split by it
+    pub label: Option<String>,
+    /// The app's identifier of the end user, for per-user usage reports
+    pub end_user_id: Option<String>,
+    /// Campaign the sponsorship is attributed to
+    pub campaign_id: Option<String>,
+    /// Have the backend return the fully-signed transaction and check locally that the gas
+    /// tank signed it and no instructions were changed, failing with `Verify` otherwise
+    #[cfg(feature = "solana")]
+    pub verify_signature: bool,
+}
+
+impl SponsorOptions {
+    /// Whether the answer has to carry the signed transaction for verification.
+    pub(crate) fn verifies_signature(&self) -> bool {
+        #[cfg(feature = "solana")]
+        return self.verify_signature;
+        #[cfg(not(feature = "solana"))]
+        false
+    }
+}
+
+impl SponsorTxRequest {
+    /// Build a request for sponsoring `transaction`, for [`Aethokit::sponsor_request`].
+    pub fn builder(transaction: impl Into<String>) -> SponsorTxRequestBuilder {
+        SponsorTxRequestBuilder {
+            transaction: transaction.into(),
+            options: SponsorOptions::default(),
+        }
+    }
+}
+
+/// Builds a [`SponsorTxRequest`] with options and attribution fields, see
+/// [`SponsorTxRequest::builder`].
+///
+/// ```no_run
+/// # use aethokit::{Aethokit, SponsorTxRequest};
+/// # async fn run(client: &Aethokit, tx: String) -> Result<(), aethokit::AethokitError> {
+/// let request = SponsorTxRequest::builder(tx)
+///     .label("checkout")
+///     .end_user_id("user-42")
+///     .campaign_id("spring-promo")
+///     .build();
+/// let sponsored = client.sponsor_request(request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SponsorTxRequestBuilder {
+    transaction: String,
+    options: SponsorOptions,
+}
+
+impl SponsorTxRequestBuilder {
+    pub fn commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.options.commitment = Some(commitment);
+        self
+    }
+
+    pub fn skip_preflight(mut self, skip: bool) -> Self {
+        self.options.skip_preflight = Some(skip);
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.options.max_retries = Some(max_retries);
+        self
+    }
+
+    pub fn reservation_id(mut self, id: ReservationId) -> Self {
+        self.options.reservation_id = Some(id);
+        self
+    }
+
+    /// Tag the sponsorship with the feature or flow it belongs to.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.options.label = Some(label.into());
+        self
+    }
+
+    /// Attribute the sponsorship to an end user, by the app's own identifier.
+    pub fn end_user_id(mut self, id: impl Into<String>) -> Self {
+        self.options.end_user_id = Some(id.into());
+        self
+    }
+
+    pub fn campaign_id(mut self, id: impl Into<String>) -> Self {
+        self.options.campaign_id = Some(id.into());
+        self
+    }
+
+    /// The request. The network and signing fields are left for the client to fill in.
+    pub fn build(self) -> SponsorTxRequest {
+        let options = self.options;
+        SponsorTxRequest {
+            transaction: self.transaction,
+            rpc_or_network: None,
+            commitment: options.commitment,
+            skip_preflight: options.skip_preflight,
+            max_retries: options.max_retries,
+            reservation_id: options.reservation_id,
+            broadcast: None,
+            return_signed: None,
+            label: options.label,
+            end_user_id: options.end_user_id,
+            campaign_id: options.campaign_id,
+        }
+    }
+}
+
+impl Aethokit {
+    /// Sponsor a request from [`SponsorTxRequest::builder`].
+    ///
+    /// The client's network is used and `broadcast` and `return_signed` are ignored, so
+    /// the transaction is sent like with [`sponsor_tx_detailed`](Self::sponsor_tx_detailed).
+    pub async fn sponsor_request(&self, request: SponsorTxRequest) -> Result<SponsoredTx, AethokitError> {
+        let options = SponsorOptions {
+            commitment: request.commitment,
+            skip_preflight: request.skip_preflight,
+            max_retries: request.max_retries,
+            reservation_id: request.reservation_id,
+            label: request.label,
+            end_user_id: request.end_user_id,
+            campaign_id: request.campaign_id,
+            #[cfg(feature = "solana")]
+            verify_signature: false,
+        };
+        Ok(self.sponsor_with_options(request.transaction, options, true).await?.value)
+    }
+
+    /// Like [`sponsor_tx_detailed`](Self::sponsor_tx_detailed), with per-call `options`,
+    /// e.g. skipping preflight for time-sensitive transactions.
+    pub async fn sponsor_tx_with_options(
+        &self,
+        tx: String,
+        options: SponsorOptions,
+    ) -> Result<SponsoredTx, AethokitError> {
+        Ok(self.sponsor_with_options(tx, options, true).await?.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Set options are sent in the request body, unset ones are left out.
+    #[tokio::test]
+    async fn forwards_options() {
+        let backend = FakeBackend::new();
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        let client = backend.client().build().unwrap();
+        let options = SponsorOptions {
+            commitment: Some(CommitmentLevel::Processed),
+            skip_preflight: Some(true),
+            ..Default::default()
+        };
+        client.sponsor_tx_with_options("tx".to_string(), options).await.unwrap();
+        assert_eq!(
+            backend.requests()[0].body,
+            Some(json!({ "transaction": "tx", "commitment": "processed", "skipPreflight": true }))
+        );
+    }
+
+    /// Attribution fields set on the builder are forwarded with the transaction.
+    #[tokio::test]
+    async fn forwards_attribution_fields() {
+        let backend = FakeBackend::new();
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        let client = backend.client().build().unwrap();
+        let request = SponsorTxRequest::builder("tx")
+            .label("checkout")
+            .end_user_id("user-42")
+            .campaign_id("spring")
+            .build();
+        client.sponsor_request(request).await.unwrap();
+        assert_eq!(
+            backend.requests()[0].body,
+            Some(json!({
+                "transaction": "tx",
+                "label": "checkout",
+                "endUserId": "user-42",
+                "campaignId": "spring",
+            }))
+        );
+    }
+}
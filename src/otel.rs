@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
+use opentelemetry::KeyValue;
+use reqwest::Method;
+
+use crate::{AethokitError, Responded};
+
+/// Latency histogram bucket boundaries, in seconds.
+const LATENCY_BOUNDARIES: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// OpenTelemetry instruments the client reports to.
+#[derive(Debug, Clone)]
+pub(crate) struct OtelMetrics {
+    requests: Counter<u64>,
+    duration: Histogram<f64>,
+    balance: Gauge<u64>,
+}
+
+impl OtelMetrics {
+    pub(crate) fn new(meter: &Meter) -> Self {
+        Self {
+            requests: meter
+                .u64_counter("aethokit.requests")
+                .with_description("Aethokit API requests by endpoint, status class and class")
+                .build(),
+            duration: meter
+                .f64_histogram("aethokit.request.duration")
+                .with_description("Aethokit API request latency")
+                .with_unit("s")
+                .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+                .build(),
+            balance: meter
+                .u64_gauge("aethokit.gas_tank.balance")
+                .with_description("Last gas tank balance seen")
+                .with_unit("lamports")
+                .build(),
+        }
+    }
+
+    /// Count a finished request to `path` (query string ignored).
+    pub(crate) fn record<T>(
+        &self,
+        method: &Method,
+        path: &str,
+        latency: Duration,
+        result: &Result<Responded<T>, AethokitError>,
+        class: &str,
+    ) {
+        let status = match result {
+            Ok(resp) => Some(resp.meta.status),
+            Err(err) => err.status(),
+        };
+        let attributes = [
+            KeyValue::new("endpoint", path.split('?').next().unwrap_or(path).to_string()),
+            KeyValue::new("method", method.to_string()),
+            KeyValue::new("status_class", status_class(status)),
+            KeyValue::new("class", class.to_string()),
+        ];
+        self.requests.add(1, &attributes);
+        self.duration.record(latency.as_secs_f64(), &attributes);
+    }
+
+    /// Record a gas tank balance seen in a `balance.changed` event.
+    pub(crate) fn record_balance(&self, gas_address: &str, lamports: u64) {
+        self.balance
+            .record(lamports, &[KeyValue::new("gas_address", gas_address.to_string())]);
+    }
+}
+
+/// `2xx` to `5xx`, or `error` when no response was received.
+fn status_class(status: Option<reqwest::StatusCode>) -> &'static str {
+    match status.map(|status| status.as_u16() / 100) {
+        Some(1) => "1xx",
+        Some(2) => "2xx",
+        Some(3) => "3xx",
+        Some(4) => "4xx",
+        Some(5) => "5xx",
+        _ => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::StatusCode;
+
+    /// Statuses are reduced to their class to keep metric cardinality low.
+    #[test]
+    fn classifies_status() {
+        assert_eq!(status_class(Some(StatusCode::OK)), "2xx");
+        assert_eq!(status_class(Some(StatusCode::TOO_MANY_REQUESTS)), "4xx");
+        assert_eq!(status_class(Some(StatusCode::BAD_GATEWAY)), "5xx");
+        assert_eq!(status_class(None), "error");
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use crate::codec::CodecError;
+use crate::signer::{required_keys, sign_slots, Signer, SignerError};
+use crate::{encode_versioned_transaction, Aethokit, AethokitError};
+
+/// A sponsored transaction on its way between services: one builds and partially signs it,
+/// others add their signatures, and the last submits it with [`Aethokit::sponsor_pending`].
+///
+/// [`to_json`](Self::to_json) writes a versioned format that later versions of the SDK
+/// keep reading, so the services can be upgraded one at a time.
+///
+/// ```no_run
+/// # async fn run(client: &aethokit::Aethokit, tx: solana_sdk::transaction::Transaction,
+/// #     cosigner: &solana_sdk::signature::Keypair) -> Result<(), aethokit::AethokitError> {
+/// use aethokit::PendingSponsorship;
+///
+/// // builder service
+/// let mut pending = PendingSponsorship::from_transaction(tx);
+/// pending.metadata.insert("order".to_string(), "42".to_string());
+/// let json = pending.to_json()?;
+///
+/// // signing service
+/// let mut pending = PendingSponsorship::from_json(&json)?;
+/// pending.sign(&[cosigner]).await?;
+/// let hash = client.sponsor_pending(&pending).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSponsorship {
+    pub message: VersionedMessage,
+    /// One slot per required signer, in message order; empty slots are `Signature::default()`
+    pub signatures: Vec<Signature>,
+    /// Free-form context for the other services, e.g. an order ID; not sent to the backend
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Serialized forms of [`PendingSponsorship`], tagged by `format`. Add a variant for each
+/// new format and keep reading the old ones.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "format")]
+enum Wire {
+    #[serde(rename = "aethokit.pending-sponsorship.v1")]
+    V1 {
+        /// Base64 of the serialized message
+        message: String,
+        /// Signature of each signer that has signed, base58, by base58 pubkey
+        signatures: BTreeMap<String, String>,
+        #[serde(default)]
+        metadata: BTreeMap<String, String>,
+    },
+}
+
+impl PendingSponsorship {
+    pub fn from_transaction(tx: impl Into<VersionedTransaction>) -> Self {
+        let VersionedTransaction { message, mut signatures } = tx.into();
+        signatures.resize(usize::from(message.header().num_required_signatures), Signature::default());
+        Self {
+            message,
+            signatures,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Keys of the required signers, the gas tank first as fee payer.
+    ///
+    /// A malformed transaction, whose header requires more signers than it has keys, lists
+    /// only the keys it has; [`sign`](Self::sign) and [`Aethokit::sponsor_pending`] reject it.
+    pub fn required_signers(&self) -> &[Pubkey] {
+        let keys = self.message.static_account_keys();
+        &keys[..self.signatures.len().min(keys.len())]
+    }
+
+    /// [`required_signers`](Self::required_signers), checking the message has a key and
+    /// a signature slot for every signer its header requires.
+    fn checked_signers(&self) -> Result<&[Pubkey], SignerError> {
+        let required = usize::from(self.message.header().num_required_signatures);
+        required_keys(self.message.static_account_keys(), required, self.signatures.len())
+    }
+
+    /// Required signers, other than the gas tank, that haven't signed yet.
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.required_signers()
+            .iter()
+            .zip(&self.signatures)
+            .skip(1)
+            .filter(|(_, signature)| **signature == Signature::default())
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    /// Add the signatures of `signers`, leaving the other slots as they are.
+    ///
+    /// # Errors
+    /// - `Signer(MalformedTransaction)` if the header requires more signers than the message
+    ///   has keys or signature slots
+    /// - `Signer(NotASigner)` if a signer is not a required signer of the transaction
+    /// - `Signer(Failed)` if a signer fails
+    pub async fn sign(&mut self, signers: &[&dyn Signer]) -> Result<(), AethokitError> {
+        let keys = self.checked_signers()?.to_vec();
+        sign_slots(&keys, &mut self.signatures, &self.message.serialize(), signers).await?;
+        Ok(())
+    }
+
+    /// The transaction with the signatures collected so far.
+    pub fn to_transaction(&self) -> VersionedTransaction {
+        VersionedTransaction {
+            signatures: self.signatures.clone(),
+            message: self.message.clone(),
+        }
+    }
+
+    /// Serialize in the current format, for handing over to another service.
+    pub fn to_json(&self) -> Result<String, AethokitError> {
+        let signatures = self
+            .required_signers()
+            .iter()
+            .zip(&self.signatures)
+            .filter(|(_, signature)| **signature != Signature::default())
+            .map(|(key, signature)| (key.to_string(), signature.to_string()))
+            .collect();
+        let wire = Wire::V1 {
+            message: base64::engine::general_purpose::STANDARD.encode(self.message.serialize()),
+            signatures,
+            metadata: self.metadata.clone(),
+        };
+        Ok(serde_json::to_string(&wire)?)
+    }
+
+    /// Read any format written by [`to_json`](Self::to_json), checking each signature
+    /// against the message.
+    ///
+    /// # Errors
+    /// - `Serde` if `json` is not a known format, or a key or signature isn't base58
+    /// - `Codec` if the message doesn't decode
+    /// - `Signer(MalformedTransaction)` if the message requires more signers than it has keys
+    /// - `Signer(NotASigner)` if a signature is for a key that isn't a required signer
+    /// - `Signer(WrongSignature)` if a signature doesn't match the message
+    pub fn from_json(json: &str) -> Result<Self, AethokitError> {
+        let Wire::V1 { message, signatures, metadata } = serde_json::from_str(json)?;
+        let message = base64::engine::general_purpose::STANDARD
+            .decode(message)
+            .map_err(|err| CodecError::Base64(err.to_string()))?;
+        let message: VersionedMessage = bincode::deserialize(&message)?;
+        let mut pending = Self::from_transaction(VersionedTransaction {
+            signatures: Vec::new(),
+            message,
+        });
+        pending.checked_signers()?;
+        pending.metadata = metadata;
+        let data = pending.message.serialize();
+        for (key, signature) in signatures {
+            let invalid = |err: &dyn std::fmt::Display| {
+                <serde_json::Error as serde::de::Error>::custom(format_args!("signature of {key}: {err}"))
+            };
+            let pubkey: Pubkey = key.parse().map_err(|err| invalid(&err))?;
+            let signature: Signature = signature.parse().map_err(|err| invalid(&err))?;
+            let slot = pending
+                .required_signers()
+                .iter()
+                .position(|required| *required == pubkey)
+                .ok_or(SignerError::NotASigner(pubkey))?;
+            if !signature.verify(pubkey.as_ref(), &data) {
+                return Err(SignerError::WrongSignature(pubkey).into());
+            }
+            pending.signatures[slot] = signature;
+        }
+        Ok(pending)
+    }
+}
+
+impl From<Transaction> for PendingSponsorship {
+    fn from(tx: Transaction) -> Self {
+        Self::from_transaction(tx)
+    }
+}
+
+impl Aethokit {
+    /// Sponsor a [`PendingSponsorship`] once every signer but the gas tank has signed.
+    ///
+    /// # Errors
+    /// - `Signer(MalformedTransaction)` if the header requires more signers than the message
+    ///   has keys or signature slots
+    /// - `Signer(MissingSignature)` naming the first signer that hasn't signed
+    pub async fn sponsor_pending(&self, pending: &PendingSponsorship) -> Result<String, AethokitError> {
+        pending.checked_signers()?;
+        if let Some(&missing) = pending.missing_signers().first() {
+            return Err(SignerError::MissingSignature(missing).into());
+        }
+        self.sponsor_signature(encode_versioned_transaction(&pending.to_transaction())?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::Message;
+    use solana_sdk::signature::Keypair;
+
+    use crate::tx::MEMO_PROGRAM_ID;
+
+    /// Signatures and metadata survive the round trip, and tampered signatures are caught.
+    #[tokio::test]
+    async fn round_trips_between_services() {
+        let (alice, bob) = (Keypair::new(), Keypair::new());
+        let memo = Instruction::new_with_bytes(
+            MEMO_PROGRAM_ID,
+            b"two signers",
+            vec![
+                AccountMeta::new_readonly(Signer::pubkey(&alice), true),
+                AccountMeta::new_readonly(Signer::pubkey(&bob), true),
+            ],
+        );
+        let message = Message::new_with_blockhash(&[memo], Some(&Pubkey::new_unique()), &Hash::new_unique());
+        let mut pending = PendingSponsorship::from_transaction(Transaction::new_unsigned(message));
+        pending.metadata.insert("order".to_string(), "42".to_string());
+        pending.sign(&[&alice]).await.unwrap();
+
+        let mut received = PendingSponsorship::from_json(&pending.to_json().unwrap()).unwrap();
+        assert_eq!(received, pending);
+        assert_eq!(received.missing_signers(), [Signer::pubkey(&bob)]);
+        received.sign(&[&bob]).await.unwrap();
+        assert!(PendingSponsorship::from_json(&received.to_json().unwrap()).unwrap().missing_signers().is_empty());
+
+        let forged = pending.to_json().unwrap().replace(&Signer::pubkey(&alice).to_string(), &Signer::pubkey(&bob).to_string());
+        assert!(matches!(
+            PendingSponsorship::from_json(&forged),
+            Err(AethokitError::Signer(SignerError::WrongSignature(_)))
+        ));
+        assert!(PendingSponsorship::from_json(r#"{"format":"aethokit.pending-sponsorship.v9"}"#).is_err());
+    }
+
+    /// A transaction whose header requires more signers than it has keys can be wrapped
+    /// but not signed, serialized back in or sponsored.
+    #[tokio::test]
+    async fn rejects_malformed_transactions() {
+        let signer = Keypair::new();
+        let memo = Instruction::new_with_bytes(
+            MEMO_PROGRAM_ID,
+            b"one signer",
+            vec![AccountMeta::new_readonly(Signer::pubkey(&signer), true)],
+        );
+        let mut message = Message::new_with_blockhash(&[memo], Some(&Pubkey::new_unique()), &Hash::new_unique());
+        message.header.num_required_signatures = 9;
+        let mut pending = PendingSponsorship::from_transaction(Transaction::new_unsigned(message));
+        assert_eq!(pending.required_signers().len(), 3);
+
+        let malformed = |result: Result<(), AethokitError>| {
+            matches!(result, Err(AethokitError::Signer(SignerError::MalformedTransaction { required: 9, .. })))
+        };
+        assert!(malformed(pending.sign(&[&signer]).await));
+        assert!(malformed(PendingSponsorship::from_json(&pending.to_json().unwrap()).map(drop)));
+        let client = Aethokit::builder().gas_key("key").build().unwrap();
+        assert!(malformed(client.sponsor_pending(&pending).await.map(drop)));
+    }
+}
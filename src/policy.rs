@@ -0,0 +1,432 @@
+//! Client-side allowlist of the instructions a sponsored transaction may contain, and the
+//! server-side rules of the gas key from [`Aethokit::get_policy`].
+//!
+//! ```
+//! use aethokit::policy::{Policy, COMPUTE_BUDGET_PROGRAM_ID};
+//!
+//! const USDC_MINT: [u8; 32] = [0; 32]; // the mint's address bytes
+//!
+//! // allow SOL transfers up to 0.05 SOL and USDC transfers; deny everything else
+//! let policy = Policy::builder()
+//!     .allow_system_transfer(50_000_000)
+//!     .allow_token_transfer(USDC_MINT)
+//!     .allow_program(COMPUTE_BUDGET_PROGRAM_ID)
+//!     .build();
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::codec::{Address, CompiledInstruction, RawTransaction};
+use crate::{Aethokit, AethokitError};
+
+/// Cache entry kind of the gas key's policy, see [`CacheStore`](crate::CacheStore).
+const POLICY_CACHE: &str = "policy";
+/// How long an enforced policy is reused before it is fetched again.
+const POLICY_TTL: Duration = Duration::from_secs(600);
+
+/// System program, `11111111111111111111111111111111`.
+pub const SYSTEM_PROGRAM_ID: Address = [0; 32];
+/// SPL Token program, `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`.
+pub const TOKEN_PROGRAM_ID: Address = [
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237, 95, 91, 55, 145,
+    58, 140, 245, 133, 126, 255, 0, 169,
+];
+/// SPL Token-2022 program, `TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb`.
+pub const TOKEN_2022_PROGRAM_ID: Address = [
+    6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218, 182, 26, 252, 77, 131, 185, 13, 39,
+    254, 189, 249, 40, 216, 161, 139, 252,
+];
+/// Compute budget program, `ComputeBudget111111111111111111111111111111`.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Address = [
+    3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187, 197, 247, 18, 107, 44,
+    67, 155, 58, 64, 0, 0, 0,
+];
+
+/// System program `Transfer` instruction index.
+const SYSTEM_TRANSFER: u32 = 2;
+/// SPL Token `TransferChecked` instruction index, the transfer that names its mint.
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+
+/// Why a transaction does not pass a [`Policy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("instruction {instruction} ({}) {reason}", bs58::encode(.program_id).into_string())]
+pub struct PolicyViolation {
+    /// Position of the offending instruction in the message
+    pub instruction: usize,
+    /// Program the instruction calls, all zeros if its program index is out of range
+    pub program_id: Address,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    AnyInstruction,
+    SystemTransfer { max_lamports: u64 },
+    TokenTransfer { mint: Address },
+}
+
+/// A compiled allowlist: every instruction must match one of its rules.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    rules: HashMap<Address, Vec<Rule>>,
+}
+
+/// Builder for [`Policy`]; anything not allowed is denied.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyBuilder {
+    rules: Vec<(Address, Rule)>,
+}
+
+impl PolicyBuilder {
+    /// Allow any instruction of `program_id`.
+    pub fn allow_program(mut self, program_id: Address) -> Self {
+        self.rules.push((program_id, Rule::AnyInstruction));
+        self
+    }
+
+    /// Allow system program transfers of up to `max_lamports` each.
+    pub fn allow_system_transfer(mut self, max_lamports: u64) -> Self {
+        self.rules
+            .push((SYSTEM_PROGRAM_ID, Rule::SystemTransfer { max_lamports }));
+        self
+    }
+
+    /// Allow SPL Token and Token-2022 transfers of `mint`.
+    ///
+    /// Only `TransferChecked` names its mint, so plain `Transfer` instructions are denied.
+    pub fn allow_token_transfer(mut self, mint: Address) -> Self {
+        for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            self.rules.push((program_id, Rule::TokenTransfer { mint }));
+        }
+        self
+    }
+
+    pub fn build(self) -> Policy {
+        let mut rules: HashMap<Address, Vec<Rule>> = HashMap::new();
+        for (program_id, rule) in self.rules {
+            let program_rules = rules.entry(program_id).or_default();
+            if !program_rules.contains(&rule) {
+                program_rules.push(rule);
+            }
+        }
+        Policy { rules }
+    }
+}
+
+impl Policy {
+    pub fn builder() -> PolicyBuilder {
+        PolicyBuilder::default()
+    }
+
+    /// Check every instruction of `tx` is allowed, returning the first that is not.
+    pub fn validate(&self, tx: &RawTransaction) -> Result<(), PolicyViolation> {
+        let keys = &tx.message.account_keys;
+        for (index, ix) in tx.message.instructions.iter().enumerate() {
+            let violation = |program_id: Address, reason| PolicyViolation {
+                instruction: index,
+                program_id,
+                reason,
+            };
+            let Some(&program_id) = keys.get(usize::from(ix.program_id_index)) else {
+                return Err(violation([0; 32], "has an invalid program index"));
+            };
+            let Some(rules) = self.rules.get(&program_id) else {
+                return Err(violation(program_id, "calls a program outside the policy"));
+            };
+            if !rules.iter().any(|rule| rule.allows(ix, keys)) {
+                return Err(violation(program_id, "is not allowed by the policy"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Rule {
+    fn allows(&self, ix: &CompiledInstruction, keys: &[Address]) -> bool {
+        match *self {
+            Rule::AnyInstruction => true,
+            Rule::SystemTransfer { max_lamports } => {
+                transfer_lamports(ix).is_some_and(|lamports| lamports <= max_lamports)
+            }
+            Rule::TokenTransfer { mint } => {
+                // accounts: source, mint, destination, owner; a mint loaded from a lookup
+                // table can't be checked here, so it is denied
+                ix.data.first() == Some(&TOKEN_TRANSFER_CHECKED)
+                    && ix
+                        .accounts
+                        .get(1)
+                        .and_then(|&i| keys.get(usize::from(i)))
+                        == Some(&mint)
+            }
+        }
+    }
+}
+
+/// The sponsorship rules the backend enforces for a gas key, from [`Aethokit::get_policy`].
+///
+/// Unset limits are not enforced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyPolicy {
+    /// Programs, base58, that sponsored transactions may call; `None` allows any program
+    #[serde(rename = "allowedPrograms", default)]
+    pub allowed_programs: Option<Vec<String>>,
+    /// Largest amount of lamports a sponsored transaction may transfer
+    #[serde(rename = "maxLamportsPerTx", default, deserialize_with = "crate::lenient::option_u64")]
+    pub max_lamports_per_tx: Option<u64>,
+    /// Networks the key may sponsor on, e.g. `mainnet-beta`; empty allows any network
+    #[serde(rename = "allowedNetworks", default)]
+    pub allowed_networks: Vec<String>,
+    #[serde(rename = "perUserLimits", default)]
+    pub per_user_limits: Option<UserLimits>,
+}
+
+/// Limits applied to each end user (fee payer) of a gas key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserLimits {
+    /// Sponsored transactions per day
+    #[serde(rename = "maxTxsPerDay", default)]
+    pub max_txs_per_day: Option<u32>,
+    /// Lamports of fees sponsored per day
+    #[serde(rename = "maxLamportsPerDay", default, deserialize_with = "crate::lenient::option_u64")]
+    pub max_lamports_per_day: Option<u64>,
+}
+
+impl KeyPolicy {
+    /// Whether transactions may call `program_id`, given in base58.
+    pub fn allows_program(&self, program_id: &str) -> bool {
+        self.allowed_programs
+            .as_ref()
+            .is_none_or(|programs| programs.iter().any(|program| program == program_id))
+    }
+
+    /// Whether the key may sponsor on `network`.
+    pub fn allows_network(&self, network: &str) -> bool {
+        self.allowed_networks.is_empty() || self.allowed_networks.iter().any(|n| n == network)
+    }
+
+    /// Check `tx` only calls allowed programs and its system transfers add up to at most
+    /// `max_lamports_per_tx`, returning the first instruction that breaks a rule.
+    ///
+    /// Networks and per-user limits depend on where and by whom the transaction is sent, so
+    /// they are not checked.
+    pub fn validate(&self, tx: &RawTransaction) -> Result<(), PolicyViolation> {
+        let keys = &tx.message.account_keys;
+        let mut lamports: u64 = 0;
+        for (index, ix) in tx.message.instructions.iter().enumerate() {
+            let violation = |program_id: Address, reason| PolicyViolation {
+                instruction: index,
+                program_id,
+                reason,
+            };
+            let Some(&program_id) = keys.get(usize::from(ix.program_id_index)) else {
+                return Err(violation([0; 32], "has an invalid program index"));
+            };
+            if !self.allows_program(&bs58::encode(program_id).into_string()) {
+                return Err(violation(program_id, "calls a program outside the key's policy"));
+            }
+            if program_id != SYSTEM_PROGRAM_ID {
+                continue;
+            }
+            lamports = lamports.saturating_add(transfer_lamports(ix).unwrap_or(0));
+            if self.max_lamports_per_tx.is_some_and(|max| lamports > max) {
+                return Err(violation(
+                    program_id,
+                    "transfers more lamports than the key's policy allows per transaction",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Aethokit {
+    /// The rules attached to the gas key, to filter out transactions the backend would
+    /// reject before sending them.
+    pub async fn get_policy(&self) -> Result<KeyPolicy, AethokitError> {
+        self.make_request::<(), _>("policy", Method::GET, None).await
+    }
+
+    /// Check a base64 transaction against the gas key's policy if the client was built with
+    /// [`enforce_key_policy`](crate::AethokitBuilder::enforce_key_policy), fetching the policy
+    /// the first time.
+    pub(crate) async fn enforce_key_policy(&self, tx: &str) -> Result<(), AethokitError> {
+        if !self.enforce_key_policy {
+            return Ok(());
+        }
+        let tx = RawTransaction::from_base64(tx)?;
+        let cached = self.cache_get(POLICY_CACHE).await;
+        let policy = match cached.and_then(|policy| serde_json::from_str::<KeyPolicy>(&policy).ok()) {
+            Some(policy) => policy,
+            None => {
+                let policy = self.get_policy().await?;
+                let json = serde_json::to_string(&policy)?;
+                self.cache_set(POLICY_CACHE, json, POLICY_TTL).await;
+                policy
+            }
+        };
+        policy.validate(&tx)?;
+        Ok(())
+    }
+}
+
+/// Lamports moved by a system program `Transfer` instruction, `None` for other instructions.
+fn transfer_lamports(ix: &CompiledInstruction) -> Option<u64> {
+    match ix.data.as_slice() {
+        [a, b, c, d, lamports @ ..] if u32::from_le_bytes([*a, *b, *c, *d]) == SYSTEM_TRANSFER => {
+            lamports.try_into().ok().map(u64::from_le_bytes)
+        }
+        _ => None,
+    }
+}
+
+/// Decode a base64 transaction, as passed to `sponsor_tx`, and check it against `policy`.
+///
+/// # Errors
+/// - `Codec` if `tx` is not a valid transaction
+/// - `Policy` if an instruction is not allowed
+pub fn validate_against_policy(tx: &str, policy: &Policy) -> Result<(), AethokitError> {
+    policy.validate(&RawTransaction::from_base64(tx)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::codec::{MessageHeader, MessageVersion, RawMessage};
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    fn transaction(instructions: Vec<CompiledInstruction>) -> RawTransaction {
+        let mint = [7; 32];
+        RawTransaction {
+            signatures: vec![[0; 64]],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 2,
+                },
+                account_keys: vec![[1; 32], [2; 32], mint, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID],
+                recent_blockhash: [0; 32],
+                instructions,
+                address_table_lookups: Vec::new(),
+            },
+        }
+    }
+
+    fn system_transfer(lamports: u64) -> CompiledInstruction {
+        let mut data = SYSTEM_TRANSFER.to_le_bytes().to_vec();
+        data.extend(lamports.to_le_bytes());
+        CompiledInstruction {
+            program_id_index: 3,
+            accounts: vec![0, 1],
+            data,
+        }
+    }
+
+    /// Transfers within the limits pass; larger ones, other mints and other programs don't.
+    #[test]
+    fn enforces_rules() {
+        let policy = Policy::builder()
+            .allow_system_transfer(50_000_000)
+            .allow_token_transfer([7; 32])
+            .build();
+        let token_transfer = |mint_index| CompiledInstruction {
+            program_id_index: 4,
+            accounts: vec![1, mint_index, 1, 0],
+            data: vec![TOKEN_TRANSFER_CHECKED, 0, 0, 0, 0, 0, 0, 0, 0, 6],
+        };
+        assert_eq!(
+            policy.validate(&transaction(vec![system_transfer(50_000_000), token_transfer(2)])),
+            Ok(())
+        );
+
+        let err = policy
+            .validate(&transaction(vec![system_transfer(1), system_transfer(50_000_001)]))
+            .unwrap_err();
+        assert_eq!((err.instruction, err.reason), (1, "is not allowed by the policy"));
+        assert!(policy.validate(&transaction(vec![token_transfer(1)])).is_err());
+
+        let deny_all = Policy::builder().build();
+        let err = deny_all.validate(&transaction(vec![system_transfer(1)])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "instruction 0 (11111111111111111111111111111111) calls a program outside the policy"
+        );
+    }
+
+    /// Missing limits are unset, and unset allowlists allow everything.
+    #[tokio::test]
+    async fn fetches_key_policy() {
+        let backend = FakeBackend::new();
+        let body = json!({
+            "allowedPrograms": ["11111111111111111111111111111111"],
+            "allowedNetworks": [],
+            "perUserLimits": { "maxTxsPerDay": 20 },
+        });
+        backend.on("GET", "policy", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let policy = client.get_policy().await.unwrap();
+        assert_eq!(policy.max_lamports_per_tx, None);
+        assert_eq!(
+            policy.per_user_limits,
+            Some(UserLimits {
+                max_txs_per_day: Some(20),
+                max_lamports_per_day: None,
+            })
+        );
+        assert!(policy.allows_program("11111111111111111111111111111111"));
+        assert!(!policy.allows_program("ComputeBudget111111111111111111111111111111"));
+        assert!(policy.allows_network("devnet"));
+    }
+
+    /// Violations are reported before anything is sponsored, and the policy is only fetched
+    /// once.
+    #[tokio::test]
+    async fn enforces_key_policy() {
+        let backend = FakeBackend::new();
+        let body = json!({
+            "allowedPrograms": ["11111111111111111111111111111111"],
+            "maxLamportsPerTx": 1_000_000,
+        });
+        backend.on("GET", "policy", FakeResponse::json(200, body));
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        let client = backend.client().enforce_key_policy(true).build().unwrap();
+
+        let within = transaction(vec![system_transfer(600_000), system_transfer(400_000)]);
+        assert_eq!(client.sponsor_tx(within.to_base64()).await.unwrap(), "sig");
+
+        let over = transaction(vec![system_transfer(600_000), system_transfer(400_001)]);
+        match client.sponsor_tx(over.to_base64()).await {
+            Err(AethokitError::Policy(violation)) => {
+                assert_eq!((violation.instruction, violation.program_id), (1, SYSTEM_PROGRAM_ID))
+            }
+            other => panic!("expected Policy, got {other:?}"),
+        }
+        let token = CompiledInstruction {
+            program_id_index: 4,
+            accounts: vec![],
+            data: vec![],
+        };
+        let err = client
+            .sponsor_tx(transaction(vec![token]).to_base64())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "transaction rejected by policy: instruction 0 \
+             (TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA) calls a program outside the key's policy"
+        );
+
+        let paths: Vec<_> = backend.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, ["policy", "sponsor-tx"]);
+    }
+}
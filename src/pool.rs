@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{Aethokit, AethokitError};
+
+/// Clients created on demand for each gas key, sharing `base`'s connection pool, for
+/// platforms sponsoring on behalf of many customer projects from one process.
+///
+/// Unlike [`Tenants`](crate::Tenants), keys don't need registering up front. Clients unused
+/// for longer than the idle timeout are dropped by [`evict_idle`](Self::evict_idle), and the
+/// least recently used one is dropped when the pool is full, so a platform with many
+/// occasional customers doesn't keep every gas address cache alive.
+///
+/// ```no_run
+/// # async fn run(base: aethokit::Aethokit, customer_key: &str, tx: String) -> Result<(), aethokit::AethokitError> {
+/// use std::time::Duration;
+/// use aethokit::ClientPool;
+///
+/// let pool = ClientPool::new(base).max_clients(1_000).idle_timeout(Duration::from_secs(600));
+/// pool.get(customer_key)?.sponsor_tx(tx).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientPool {
+    base: Aethokit,
+    clients: Mutex<HashMap<String, Entry>>,
+    max_clients: usize,
+    idle_timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    client: Aethokit,
+    last_used: Instant,
+}
+
+impl ClientPool {
+    /// Pool whose clients inherit `base`'s HTTP settings, headers, network and retries,
+    /// holding up to 256 clients with no idle timeout.
+    pub fn new(base: Aethokit) -> Self {
+        Self {
+            base,
+            clients: Mutex::default(),
+            max_clients: 256,
+            idle_timeout: None,
+        }
+    }
+
+    /// Most clients kept at once, at least one.
+    pub fn max_clients(mut self, max_clients: usize) -> Self {
+        self.max_clients = max_clients.max(1);
+        self
+    }
+
+    /// Drop clients unused for `timeout` on the next [`evict_idle`](Self::evict_idle) or
+    /// [`get`](Self::get) of a new key.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// The client for `gas_key`, created on first use.
+    ///
+    /// The client has no fallback keys and no guardrail budget of its own; clones share its
+    /// gas address cache.
+    ///
+    /// # Errors
+    /// - `MissingGasKey` if `gas_key` is empty
+    pub fn get(&self, gas_key: &str) -> Result<Aethokit, AethokitError> {
+        let gas_key = gas_key.trim();
+        if gas_key.is_empty() {
+            return Err(AethokitError::MissingGasKey);
+        }
+        let now = Instant::now();
+        let mut clients = self.clients.lock().unwrap();
+        if let Some(entry) = clients.get_mut(gas_key) {
+            entry.last_used = now;
+            return Ok(entry.client.clone());
+        }
+
+        self.evict_expired(&mut clients, now);
+        if clients.len() >= self.max_clients {
+            let oldest = clients
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                clients.remove(&key);
+            }
+        }
+        let mut client = self.base.with_gas_key(gas_key.to_string());
+        client.fallback_keys = Arc::default();
+        client.budget = None;
+        clients.insert(
+            gas_key.to_string(),
+            Entry {
+                client: client.clone(),
+                last_used: now,
+            },
+        );
+        Ok(client)
+    }
+
+    /// Drop clients unused for longer than the idle timeout, returning how many were dropped.
+    pub fn evict_idle(&self) -> usize {
+        self.evict_expired(&mut self.clients.lock().unwrap(), Instant::now())
+    }
+
+    /// Drop the client for `gas_key`, e.g. when a customer rotates their key.
+    pub fn remove(&self, gas_key: &str) -> bool {
+        self.clients
+            .lock()
+            .unwrap()
+            .remove(gas_key.trim())
+            .is_some()
+    }
+
+    /// Number of clients currently kept.
+    pub fn len(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn evict_expired(&self, clients: &mut HashMap<String, Entry>, now: Instant) -> usize {
+        let Some(timeout) = self.idle_timeout else {
+            return 0;
+        };
+        let before = clients.len();
+        clients.retain(|_, entry| now.duration_since(entry.last_used) <= timeout);
+        before - clients.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clients are cached per key, and the least recently used one makes room for a new key.
+    #[test]
+    fn caches_and_evicts_clients() {
+        let base = Aethokit::builder().gas_key("base").build().unwrap();
+        let pool = ClientPool::new(base).max_clients(2);
+        assert_eq!(&*pool.get("a").unwrap().gas_key.get(), "a");
+        pool.get("b").unwrap();
+        pool.get("a").unwrap();
+        assert_eq!(pool.len(), 2);
+
+        pool.get("c").unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(!pool.remove("b"));
+        assert!(pool.remove("a"));
+        assert!(matches!(pool.get(" "), Err(AethokitError::MissingGasKey)));
+
+        let pool = ClientPool::new(pool.base.clone()).idle_timeout(Duration::ZERO);
+        pool.get("a").unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(pool.evict_idle(), 1);
+        assert!(pool.is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio::sync::{mpsc, Mutex};
+
+use crate::{idempotency_key, Aethokit, AethokitError, RetryPolicy, SponsorJob, IDEMPOTENCY_KEY_HEADER};
+
+/// Options for [`Aethokit::sponsor_queue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueOptions {
+    /// Transactions submitted at the same time
+    pub concurrency: usize,
+    /// Jobs held before [`SponsorQueue::enqueue`] waits for room
+    pub capacity: usize,
+    /// Retries for failed submissions, see [`SponsorQueue`] for which are resent
+    pub retry: RetryPolicy,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            capacity: 64,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Outcome of a job submitted through a [`SponsorQueue`].
+#[derive(Debug)]
+pub struct SponsorOutcome {
+    pub id: String,
+    /// Transaction hash, or the error of the last attempt
+    pub result: Result<String, AethokitError>,
+    /// Submissions made, including retries
+    pub attempts: u32,
+}
+
+/// Handle for enqueueing transactions to a pool of background workers.
+///
+/// Failed submissions are retried per the queue's [`RetryPolicy`], the only retries they
+/// get, following its [`post_retries`](RetryPolicy::post_retries): unless the client
+/// carries an [`IDEMPOTENCY_KEY_HEADER`], only submissions that never reached the server
+/// are resent, since the backend may otherwise sponsor and charge for the transaction
+/// twice. With a key, each job is sent with its own key derived from the client's and the
+/// job ID. Workers stop once every handle is dropped and
+/// the queue has drained, or right away when the client's deadline passes or its
+/// cancellation token fires (see [`Aethokit::with_cancellation`]).
+#[derive(Debug, Clone)]
+pub struct SponsorQueue {
+    jobs: mpsc::Sender<SponsorJob>,
+}
+
+impl SponsorQueue {
+    /// Add a job, waiting while the queue is full.
+    ///
+    /// # Errors
+    /// - `QueueClosed` if the workers have stopped
+    pub async fn enqueue(&self, job: SponsorJob) -> Result<(), AethokitError> {
+        self.jobs.send(job).await.map_err(|_| AethokitError::QueueClosed)
+    }
+
+    /// Add a job if the queue has room, handing it back otherwise.
+    pub fn try_enqueue(&self, job: SponsorJob) -> Result<(), SponsorJob> {
+        self.jobs.try_send(job).map_err(|err| match err {
+            mpsc::error::TrySendError::Full(job) | mpsc::error::TrySendError::Closed(job) => job,
+        })
+    }
+}
+
+impl Aethokit {
+    /// Start a pool of background workers sponsoring enqueued transactions.
+    ///
+    /// Returns the queue handle and the stream of outcomes, in completion order. The
+    /// stream ends once the queue is closed and every job has completed; if it is not
+    /// polled, workers wait once `capacity` outcomes are pending.
+    ///
+    /// Must be called within a Tokio runtime.
+    ///
+    /// # Panics
+    /// If `concurrency` or `capacity` is zero.
+    pub fn sponsor_queue(&self, options: QueueOptions) -> (SponsorQueue, impl Stream<Item = SponsorOutcome>) {
+        assert!(options.concurrency > 0, "concurrency must be at least 1");
+        let (jobs, queue) = mpsc::channel(options.capacity);
+        let (outcomes, results) = mpsc::channel(options.capacity);
+        let queue = Arc::new(Mutex::new(queue));
+        for _ in 0..options.concurrency {
+            let client = self.clone();
+            let queue = queue.clone();
+            let outcomes = outcomes.clone();
+            tokio::spawn(async move {
+                loop {
+                    let next = client.within_scope(async { Ok(queue.lock().await.recv().await) });
+                    let Ok(Some(job)) = next.await else {
+                        break;
+                    };
+                    let outcome = client.submit_job(job, options.retry).await;
+                    if outcomes.send(outcome).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        let results = stream::unfold(results, |mut results| async move {
+            results.recv().await.map(|outcome| (outcome, results))
+        });
+        (SponsorQueue { jobs }, results)
+    }
+
+    /// Sponsor every transaction of `transactions`, up to `options.concurrency` at a time,
+    /// retrying as [`sponsor_queue`](Self::sponsor_queue) does.
+    ///
+    /// Transactions are pulled from the input only as submission slots free up, so a batch of
+    /// any size is never held in memory. Outcomes come out in completion order, with the
+    /// position of the transaction in the input as their `id`. `options.capacity` is unused.
+    ///
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # async fn run(client: &aethokit::Aethokit, txs: impl futures_util::Stream<Item = String>) {
+    /// use aethokit::QueueOptions;
+    ///
+    /// let outcomes = client.sponsor_stream(txs, QueueOptions { concurrency: 16, ..Default::default() });
+    /// outcomes.for_each(|outcome| async move { println!("{}: {:?}", outcome.id, outcome.result) }).await;
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// If `options.concurrency` is zero.
+    pub fn sponsor_stream(
+        &self,
+        transactions: impl Stream<Item = String>,
+        options: QueueOptions,
+    ) -> impl Stream<Item = SponsorOutcome> {
+        assert!(options.concurrency > 0, "concurrency must be at least 1");
+        let client = self.clone();
+        transactions
+            .enumerate()
+            .map(move |(index, transaction)| {
+                let client = client.clone();
+                let job = SponsorJob {
+                    id: index.to_string(),
+                    transaction,
+                };
+                async move { client.submit_job(job, options.retry).await }
+            })
+            .buffer_unordered(options.concurrency)
+    }
+
+    async fn submit_job(&self, job: SponsorJob, policy: RetryPolicy) -> SponsorOutcome {
+        let job_key = self
+            .extra_headers
+            .get(IDEMPOTENCY_KEY_HEADER)
+            .and_then(|key| key.to_str().ok())
+            .map(|key| idempotency_key(key, &job.id));
+        let mut client = match &job_key {
+            Some(key) => match self.with_header(IDEMPOTENCY_KEY_HEADER, key) {
+                Ok(client) => client,
+                Err(err) => {
+                    return SponsorOutcome {
+                        id: job.id,
+                        result: Err(err),
+                        attempts: 0,
+                    }
+                }
+            },
+            None => self.clone(),
+        };
+        // retried below, so the attempts don't multiply with the client's own retries
+        client.retry = None;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match client.sponsor_signature(job.transaction.clone()).await {
+                Err(err)
+                    if attempts <= policy.max_retries
+                        && policy.may_resend_post(job_key.is_some(), &err)
+                        && self.may_retry() =>
+                {
+                    let retry_after = match &err {
+                        AethokitError::RateLimited { retry_after, .. } => *retry_after,
+                        _ => None,
+                    };
+                    if let Err(err) = self.scoped_sleep(policy.backoff(attempts - 1, retry_after)).await {
+                        return SponsorOutcome {
+                            id: job.id,
+                            result: Err(err),
+                            attempts,
+                        };
+                    }
+                }
+                result => {
+                    return SponsorOutcome {
+                        id: job.id,
+                        result,
+                        attempts,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Retryable failures are retried per the policy and every job gets exactly one outcome.
+    #[tokio::test]
+    async fn retries_and_reports_jobs() {
+        // nothing listens on port 1, so each submission fails with a connection error
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url("http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let options = QueueOptions {
+            concurrency: 2,
+            retry: RetryPolicy {
+                max_retries: 1,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            },
+            ..Default::default()
+        };
+        let (queue, outcomes) = client.sponsor_queue(options);
+        for id in ["a", "b", "c"] {
+            let job = SponsorJob {
+                id: id.to_string(),
+                transaction: "tx".to_string(),
+            };
+            queue.enqueue(job).await.unwrap();
+        }
+        drop(queue);
+
+        let outcomes: Vec<_> = outcomes.collect().await;
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes.iter().all(|outcome| outcome.result.is_err() && outcome.attempts == 2));
+    }
+
+    /// A 503 may have reached the backend, so it is only resent when the job carries an
+    /// idempotency key, which is derived per job from the client's.
+    #[tokio::test]
+    async fn resends_only_keyed_jobs() {
+        let submit = |key: Option<&'static str>| async move {
+            let backend = FakeBackend::new();
+            backend
+                .on("POST", "sponsor-tx", FakeResponse::json(503, json!({ "error": "busy" })))
+                .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+            let mut client = backend.client().build().unwrap();
+            if let Some(key) = key {
+                client = client.with_header(IDEMPOTENCY_KEY_HEADER, key).unwrap();
+            }
+            let retry = RetryPolicy {
+                initial_backoff: Duration::ZERO,
+                ..RetryPolicy::default()
+            };
+            let job = SponsorJob {
+                id: "job-1".to_string(),
+                transaction: "AQID".to_string(),
+            };
+            (client.submit_job(job, retry).await, backend.requests())
+        };
+
+        let (outcome, requests) = submit(None).await;
+        assert!(outcome.result.is_err());
+        assert_eq!((outcome.attempts, requests.len()), (1, 1));
+
+        let (outcome, requests) = submit(Some("batch-7")).await;
+        assert_eq!(outcome.result.unwrap(), "sig");
+        assert_eq!((outcome.attempts, requests.len()), (2, 2));
+        let key = idempotency_key("batch-7", "job-1");
+        assert!(requests.iter().all(|req| req.idempotency_key.as_ref() == Some(&key)));
+    }
+
+    /// A stream of transactions yields one outcome each, identified by input position.
+    #[tokio::test]
+    async fn sponsors_streamed_transactions() {
+        let backend = FakeBackend::new();
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+        let client = backend.client().build().unwrap();
+        let transactions = stream::iter((0..5).map(|_| "AQID".to_string()));
+        let options = QueueOptions {
+            concurrency: 2,
+            ..Default::default()
+        };
+
+        let mut ids: Vec<_> = client
+            .sponsor_stream(transactions, options)
+            .map(|outcome| {
+                assert_eq!(outcome.result.unwrap(), "sig");
+                outcome.id
+            })
+            .collect()
+            .await;
+        ids.sort();
+        assert_eq!(ids, ["0", "1", "2", "3", "4"]);
+        assert_eq!(backend.requests().len(), 5);
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Rate-limit state reported by the server in response headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window (`x-ratelimit-limit`)
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`x-ratelimit-remaining`)
+    pub remaining: Option<u64>,
+    /// Time until the current window resets (`x-ratelimit-reset`, in seconds)
+    pub reset: Option<Duration>,
+    /// How long to wait before retrying (`retry-after`, in seconds)
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parse rate-limit headers, returning `None` if the response carries none of them.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let info = RateLimitInfo {
+            limit: header_u64(headers, "x-ratelimit-limit"),
+            remaining: header_u64(headers, "x-ratelimit-remaining"),
+            reset: header_u64(headers, "x-ratelimit-reset").map(Duration::from_secs),
+            retry_after: header_u64(headers, RETRY_AFTER.as_str()).map(Duration::from_secs),
+        };
+        (info != RateLimitInfo::default()).then_some(info)
+    }
+
+    /// `true` if the server reported no requests left in the current window.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Known headers are parsed and unparseable ones are ignored.
+    #[test]
+    fn parses_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimitInfo::from_headers(&headers), None);
+
+        headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.limit, Some(100));
+        assert!(info.is_exhausted());
+        assert_eq!(info.retry_after, None);
+    }
+}
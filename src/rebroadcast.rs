@@ -0,0 +1,173 @@
+use std::future::Future;
+use std::iter;
+use std::time::Duration;
+
+use crate::{Aethokit, AethokitError, CommitmentLevel, ConfirmedSponsorship};
+
+/// Transaction handed to [`Aethokit::rebroadcast_with_escalation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingTx {
+    /// Signature of a transaction that was already sponsored
+    Signature(String),
+    /// Base64 transaction to sponsor first
+    Transaction(String),
+}
+
+/// Priority fees tried by [`Aethokit::rebroadcast_with_escalation`] and, with the `solana`
+/// feature, `SponsoredTxBuilder::sponsor_with_fee_bump`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscalationSchedule {
+    /// How long each attempt may take to reach the commitment before the next is sent
+    pub confirm_within: Duration,
+    /// Compute-unit price of the first re-signed attempt, in micro-lamports
+    pub start_micro_lamports: u64,
+    /// Factor the compute-unit price is multiplied by on each attempt
+    pub multiplier: u64,
+    /// Highest compute-unit price to pay, in micro-lamports
+    pub max_micro_lamports: u64,
+}
+
+impl Default for EscalationSchedule {
+    fn default() -> Self {
+        Self {
+            confirm_within: Duration::from_secs(20),
+            start_micro_lamports: 10_000,
+            multiplier: 2,
+            max_micro_lamports: 1_000_000,
+        }
+    }
+}
+
+impl EscalationSchedule {
+    /// Compute-unit prices of the re-signed attempts, ending at the cap.
+    fn prices(&self) -> impl Iterator<Item = u64> {
+        let max = self.max_micro_lamports;
+        let multiplier = self.multiplier;
+        iter::successors(Some(self.start_micro_lamports.min(max)), move |&price| {
+            (price < max).then(|| price.saturating_mul(multiplier).max(price + 1).min(max))
+        })
+    }
+}
+
+impl Aethokit {
+    /// Wait for `pending` to reach `commitment`, re-submitting it with a higher priority fee
+    /// each time an attempt isn't confirmed within the schedule's `confirm_within`.
+    ///
+    /// `resign` is called with each compute-unit price of the schedule and returns the
+    /// transaction rebuilt with that price and signed again, base64. Keep the original's
+    /// blockhash or durable nonce so the attempts expire together; any earlier attempt that
+    /// lands first is returned instead of the latest.
+    ///
+    /// # Errors
+    /// - `TxFailed` if the latest attempt landed but failed
+    /// - `NotConfirmed` if the attempt at the price cap is not confirmed in time
+    pub async fn rebroadcast_with_escalation<F, Fut>(
+        &self,
+        pending: PendingTx,
+        commitment: CommitmentLevel,
+        schedule: EscalationSchedule,
+        mut resign: F,
+    ) -> Result<ConfirmedSponsorship, AethokitError>
+    where
+        F: FnMut(u64) -> Fut,
+        Fut: Future<Output = Result<String, AethokitError>>,
+    {
+        let mut signatures = vec![match pending {
+            PendingTx::Signature(signature) => signature,
+            PendingTx::Transaction(tx) => self.sponsor_signature(tx).await?,
+        }];
+        let mut prices = schedule.prices();
+        loop {
+            let (latest, earlier) = signatures
+                .split_last()
+                .ok_or(AethokitError::Internal("no attempt was sent"))?;
+            let err = match self.confirm_tx(latest, commitment, schedule.confirm_within).await {
+                Err(err @ AethokitError::NotConfirmed { .. }) => err,
+                result => return result,
+            };
+            for signature in earlier {
+                match self.confirm_tx(signature, commitment, Duration::ZERO).await {
+                    Err(AethokitError::NotConfirmed { .. } | AethokitError::TxFailed { .. }) => {}
+                    result => return result,
+                }
+            }
+            let Some(price) = prices.next() else {
+                return Err(err);
+            };
+            let tx = resign(price).await?;
+            signatures.push(self.sponsor_signature(tx).await?);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// The price is multiplied on each attempt and the cap is tried exactly once.
+    #[test]
+    fn escalates_prices_up_to_cap() {
+        let schedule = EscalationSchedule {
+            start_micro_lamports: 1_000,
+            multiplier: 3,
+            max_micro_lamports: 20_000,
+            ..Default::default()
+        };
+        assert_eq!(schedule.prices().collect::<Vec<_>>(), [1_000, 3_000, 9_000, 20_000]);
+        let from_zero = EscalationSchedule {
+            start_micro_lamports: 0,
+            max_micro_lamports: 2,
+            ..schedule
+        };
+        assert_eq!(from_zero.prices().collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    /// Unconfirmed attempts are re-signed at rising prices until one confirms.
+    #[tokio::test(start_paused = true)]
+    async fn escalates_until_confirmed() {
+        let record = |hash: &str, status: &str| {
+            FakeResponse::json(
+                200,
+                json!({ "hash": hash, "feePaid": 5000, "timestamp": "2024-05-01T12:00:00Z", "status": status }),
+            )
+        };
+        let backend = FakeBackend::new();
+        // sig1, then sig2 and sig1 again, then sig3
+        backend
+            .on("GET", "get-sponsored-tx", record("sig1", "pending"))
+            .on("GET", "get-sponsored-tx", record("sig2", "pending"))
+            .on("GET", "get-sponsored-tx", record("sig1", "pending"))
+            .on("GET", "get-sponsored-tx", record("sig3", "confirmed"))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig2" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig3" })));
+        let client = backend.client().build().unwrap();
+        let schedule = EscalationSchedule {
+            confirm_within: Duration::from_millis(100),
+            start_micro_lamports: 10,
+            multiplier: 2,
+            max_micro_lamports: 20,
+        };
+
+        let mut prices = Vec::new();
+        let confirmed = client
+            .rebroadcast_with_escalation(
+                PendingTx::Signature("sig1".to_string()),
+                CommitmentLevel::Confirmed,
+                schedule,
+                |price| {
+                    prices.push(price);
+                    async move { Ok(format!("tx-{price}")) }
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(confirmed.signature, "sig3");
+        assert_eq!(prices, [10, 20]);
+        let requests = backend.requests();
+        assert_eq!(requests[4].body.as_ref().unwrap()["transaction"], "tx-20");
+    }
+}
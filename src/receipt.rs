@@ -0,0 +1,143 @@
+use std::fmt;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::endpoint::endpoint;
+use crate::{Aethokit, AethokitError};
+
+/// Backend-signed proof that a gas tank paid the fee of a transaction, from
+/// [`Aethokit::get_receipt`].
+///
+/// The backend signs [`payload`](Receipt::payload) with its receipt key, so the receipt can be
+/// handed to an auditor and checked without trusting whoever kept it; see `verify_receipt`
+/// with the `solana` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// Signature of the sponsored transaction
+    pub signature: String,
+    /// Gas tank that paid the fee, base58
+    pub sponsor: String,
+    /// Fee paid, in lamports
+    #[serde(rename = "feePaid", deserialize_with = "crate::lenient::u64")]
+    pub fee_paid: u64,
+    /// Slot the transaction landed in
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub slot: u64,
+    /// Ed25519 signature of the backend's receipt key over the payload, base58
+    pub proof: String,
+}
+
+impl Receipt {
+    /// The bytes the backend signs: `aethokit-receipt:v1:{signature}:{sponsor}:{fee_paid}:{slot}`.
+    pub fn payload(&self) -> Vec<u8> {
+        format!(
+            "aethokit-receipt:v1:{}:{}:{}:{}",
+            self.signature, self.sponsor, self.fee_paid, self.slot
+        )
+        .into_bytes()
+    }
+}
+
+impl fmt::Display for Receipt {
+    /// One-line summary, e.g. `5Kd3...9xQ sponsored by 7xKX...AsU, fee 5000 lamports, slot 1234`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sponsored by {}, fee {} lamports, slot {}",
+            self.signature, self.sponsor, self.fee_paid, self.slot
+        )
+    }
+}
+
+impl Aethokit {
+    /// The signed receipt of a sponsored transaction, once it has landed.
+    ///
+    /// # Errors
+    /// - `UnexpectedStatus` with `404` if the backend has no receipt for `signature` (yet)
+    pub async fn get_receipt(&self, signature: &str) -> Result<Receipt, AethokitError> {
+        let path = endpoint("tx-receipt", &[], &json!({ "signature": signature }))?;
+        self.make_request::<(), _>(&path, Method::GET, None).await
+    }
+}
+
+/// Check `receipt` was signed by the backend's receipt key `backend_key`, so its fields can
+/// be trusted.
+///
+/// # Errors
+/// - `InvalidReceiptSignature` if the proof is malformed or doesn't match the payload
+#[cfg(feature = "solana")]
+pub fn verify_receipt(
+    receipt: &Receipt,
+    backend_key: &solana_sdk::pubkey::Pubkey,
+) -> Result<(), crate::VerifyError> {
+    let proof = bs58::decode(&receipt.proof)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| solana_sdk::signature::Signature::try_from(bytes.as_slice()).ok())
+        .ok_or(crate::VerifyError::InvalidReceiptSignature)?;
+    if !proof.verify(backend_key.as_ref(), &receipt.payload()) {
+        return Err(crate::VerifyError::InvalidReceiptSignature);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Receipts render as a one-line summary of who paid what.
+    #[test]
+    fn displays_receipt() {
+        let receipt = Receipt {
+            signature: "5x".to_string(),
+            sponsor: "tank".to_string(),
+            fee_paid: 5000,
+            slot: 42,
+            proof: "proof".to_string(),
+        };
+        assert_eq!(receipt.to_string(), "5x sponsored by tank, fee 5000 lamports, slot 42");
+    }
+
+    /// A receipt signed by the backend key verifies; edited fields or another key don't.
+    #[cfg(feature = "solana")]
+    #[tokio::test]
+    async fn verifies_receipts() {
+        use solana_sdk::signature::Keypair;
+        use solana_sdk::signer::Signer;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+        use crate::VerifyError;
+
+        let backend_key = Keypair::new();
+        let mut receipt = Receipt {
+            signature: "5x".to_string(),
+            sponsor: "tank".to_string(),
+            fee_paid: 5000,
+            slot: 42,
+            proof: String::new(),
+        };
+        receipt.proof = backend_key.sign_message(&receipt.payload()).to_string();
+        let backend = FakeBackend::new();
+        let body = serde_json::to_value(&receipt).unwrap();
+        backend.on("GET", "tx-receipt", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let fetched = client.get_receipt("5x").await.unwrap();
+        assert_eq!(backend.requests()[0].path, "tx-receipt?signature=5x");
+        assert_eq!(verify_receipt(&fetched, &backend_key.pubkey()), Ok(()));
+        assert_eq!(
+            verify_receipt(&fetched, &Keypair::new().pubkey()),
+            Err(VerifyError::InvalidReceiptSignature)
+        );
+        let inflated = Receipt {
+            fee_paid: 50_000,
+            ..fetched
+        };
+        assert_eq!(
+            verify_receipt(&inflated, &backend_key.pubkey()),
+            Err(VerifyError::InvalidReceiptSignature)
+        );
+    }
+}
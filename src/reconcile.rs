@@ -0,0 +1,305 @@
+//! Merge webhook deliveries, history polls and on-chain statuses for one signature into a
+//! single state, so integrators act on exactly one terminal outcome.
+//!
+//! Conflict rules:
+//! - Each source keeps its own latest claim. A landing never moves back to a lower
+//!   commitment, so a stale poll can't undo a newer confirmation.
+//! - Whether the transaction failed or landed is decided by the most authoritative source
+//!   with an opinion: the chain, then history polls, then webhooks. Commitment is the highest
+//!   reported by any source that agrees it landed.
+//! - The first terminal state (finalized or failed) is final. Later observations that
+//!   disagree with it are kept in [`Reconciler::conflicts`] but don't change it.
+
+use crate::events::SponsorEvent;
+use crate::webhooks::WebhookEvent;
+use crate::{CommitmentLevel, HistoryStatus, TxRecord};
+
+/// Where an observation came from, from least to most authoritative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Webhook,
+    Poll,
+    Chain,
+}
+
+/// Settlement of a transaction as reported by the RPC, e.g. from `getSignatureStatuses`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainStatus {
+    pub slot: u64,
+    pub commitment: CommitmentLevel,
+    /// Execution error, if the transaction landed but failed
+    pub error: Option<String>,
+}
+
+/// One piece of evidence about a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Observation {
+    /// A webhook delivery; `balance.low` and events for other signatures are ignored
+    Webhook(WebhookEvent),
+    /// A record from the history API
+    Poll(TxRecord),
+    /// The RPC status of the reconciler's signature
+    Chain(ChainStatus),
+}
+
+impl Observation {
+    /// The observation carried by an event-stream notification, which ranks with webhooks, or
+    /// `None` for events without a transaction status.
+    pub fn from_event(event: SponsorEvent) -> Option<Self> {
+        match event {
+            SponsorEvent::TxConfirmed(ev) => Some(Observation::Webhook(WebhookEvent::TxConfirmed(ev))),
+            SponsorEvent::TxFailed(ev) => Some(Observation::Webhook(WebhookEvent::TxFailed(ev))),
+            SponsorEvent::BalanceChanged(_) | SponsorEvent::Unknown => None,
+        }
+    }
+}
+
+/// Reconciled state of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxState {
+    /// Not known to have landed yet
+    Pending,
+    Landed {
+        slot: Option<u64>,
+        commitment: CommitmentLevel,
+    },
+    /// Failed on chain or dropped; `error` is `None` when the source gave no reason
+    Failed { error: Option<String> },
+}
+
+impl TxState {
+    /// Finalized or failed: no further change is expected.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TxState::Failed { .. }
+                | TxState::Landed {
+                    commitment: CommitmentLevel::Finalized,
+                    ..
+                }
+        )
+    }
+}
+
+/// An observation that disagreed with the state the reconciler kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub source: Source,
+    pub observed: TxState,
+    pub kept: TxState,
+}
+
+/// Reconciliation state machine for one signature, see the [module docs](self) for the rules.
+#[derive(Debug, Clone)]
+pub struct Reconciler {
+    signature: String,
+    /// Latest claim per source, indexed by `Source as usize`
+    claims: [Option<TxState>; 3],
+    terminal: Option<TxState>,
+    conflicts: Vec<Conflict>,
+}
+
+impl Reconciler {
+    pub fn new(signature: impl Into<String>) -> Self {
+        Self {
+            signature: signature.into(),
+            claims: [None, None, None],
+            terminal: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Feed an observation, returning the terminal state the first time it is reached and
+    /// `None` otherwise.
+    pub fn observe(&mut self, observation: Observation) -> Option<&TxState> {
+        let (source, claim) = self.claim(observation)?;
+        let previous = &mut self.claims[source as usize];
+        let claim = match (previous.as_ref(), claim) {
+            (
+                Some(TxState::Landed {
+                    slot: old_slot,
+                    commitment: old,
+                }),
+                TxState::Landed { slot, commitment },
+            ) => TxState::Landed {
+                slot: slot.or(*old_slot),
+                commitment: commitment.max(*old),
+            },
+            (Some(TxState::Landed { .. } | TxState::Failed { .. }), TxState::Pending) => return None,
+            (_, claim) => claim,
+        };
+        *previous = Some(claim.clone());
+
+        let resolved = self.resolve();
+        if let Some(terminal) = &self.terminal {
+            if failed(&claim) != failed(terminal) {
+                self.conflicts.push(Conflict {
+                    source,
+                    observed: claim,
+                    kept: terminal.clone(),
+                });
+            }
+            return None;
+        }
+        if !matches!(claim, TxState::Pending) && failed(&claim) != failed(&resolved) {
+            self.conflicts.push(Conflict {
+                source,
+                observed: claim,
+                kept: resolved.clone(),
+            });
+        }
+        if resolved.is_terminal() {
+            self.terminal = Some(resolved);
+            return self.terminal.as_ref();
+        }
+        None
+    }
+
+    /// Current reconciled state, frozen once terminal.
+    pub fn state(&self) -> TxState {
+        self.terminal.clone().unwrap_or_else(|| self.resolve())
+    }
+
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Observations that disagreed with the kept state, oldest first.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// What `observation` claims, or `None` if it is about another signature or carries no status.
+    fn claim(&self, observation: Observation) -> Option<(Source, TxState)> {
+        let (source, hash, claim) = match observation {
+            Observation::Webhook(WebhookEvent::TxConfirmed(ev)) => (
+                Source::Webhook,
+                ev.hash,
+                TxState::Landed {
+                    slot: Some(ev.slot),
+                    commitment: CommitmentLevel::Confirmed,
+                },
+            ),
+            Observation::Webhook(WebhookEvent::TxFailed(ev)) => {
+                (Source::Webhook, ev.hash, TxState::Failed { error: Some(ev.error) })
+            }
+            Observation::Webhook(WebhookEvent::LowBalance(_)) => return None,
+            Observation::Poll(record) => {
+                let claim = match (record.status, record.slot) {
+                    (HistoryStatus::Pending, None) => TxState::Pending,
+                    (HistoryStatus::Pending, slot) => TxState::Landed {
+                        slot,
+                        commitment: CommitmentLevel::Processed,
+                    },
+                    (HistoryStatus::Confirmed, slot) => TxState::Landed {
+                        slot,
+                        commitment: CommitmentLevel::Confirmed,
+                    },
+                    (HistoryStatus::Finalized, slot) => TxState::Landed {
+                        slot,
+                        commitment: CommitmentLevel::Finalized,
+                    },
+                    (HistoryStatus::Failed, _) => TxState::Failed { error: None },
+                };
+                (Source::Poll, record.hash, claim)
+            }
+            Observation::Chain(status) => {
+                let claim = match status.error {
+                    Some(error) => TxState::Failed { error: Some(error) },
+                    None => TxState::Landed {
+                        slot: Some(status.slot),
+                        commitment: status.commitment,
+                    },
+                };
+                return Some((Source::Chain, claim));
+            }
+        };
+        (hash == self.signature).then_some((source, claim))
+    }
+
+    fn resolve(&self) -> TxState {
+        let mut by_authority = self.claims.iter().rev().flatten();
+        let Some(decider) = by_authority.find(|claim| !matches!(claim, TxState::Pending)) else {
+            return TxState::Pending;
+        };
+        if let TxState::Failed { .. } = decider {
+            return decider.clone();
+        }
+        let landed = self.claims.iter().rev().flatten().filter_map(|claim| match claim {
+            TxState::Landed { slot, commitment } => Some((*slot, *commitment)),
+            _ => None,
+        });
+        let (slot, commitment) = landed.fold((None, CommitmentLevel::Processed), |(slot, best), (s, c)| {
+            (slot.or(s), best.max(c))
+        });
+        TxState::Landed { slot, commitment }
+    }
+}
+
+fn failed(state: &TxState) -> bool {
+    matches!(state, TxState::Failed { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::webhooks::{TxConfirmed, TxFailed};
+
+    fn record(status: HistoryStatus, slot: Option<u64>) -> Observation {
+        Observation::Poll(TxRecord {
+            hash: "sig".to_string(),
+            slot,
+            fee_paid: 5000,
+            timestamp: Default::default(),
+            status,
+            fee_breakdown: None,
+        })
+    }
+
+    /// The chain outranks a webhook failure, stale polls are ignored, and the terminal
+    /// state is emitted once.
+    #[test]
+    fn reconciles_conflicting_sources() {
+        let mut reconciler = Reconciler::new("sig");
+        let failed = Observation::Webhook(WebhookEvent::TxFailed(TxFailed {
+            hash: "sig".to_string(),
+            error: "dropped".to_string(),
+            timestamp: Default::default(),
+        }));
+        let chain = |commitment| {
+            Observation::Chain(ChainStatus {
+                slot: 7,
+                commitment,
+                error: None,
+            })
+        };
+        assert_eq!(reconciler.observe(chain(CommitmentLevel::Processed)), None);
+        assert_eq!(reconciler.observe(failed), None);
+        assert_eq!(reconciler.conflicts().len(), 1);
+        assert_eq!(reconciler.observe(record(HistoryStatus::Pending, None)), None);
+        assert_eq!(
+            reconciler.state(),
+            TxState::Landed {
+                slot: Some(7),
+                commitment: CommitmentLevel::Processed,
+            }
+        );
+
+        let ignored = Observation::Webhook(WebhookEvent::TxConfirmed(TxConfirmed {
+            hash: "other".to_string(),
+            slot: 9,
+            fee_paid: 5000,
+            timestamp: Default::default(),
+        }));
+        assert_eq!(reconciler.observe(ignored), None);
+
+        let finalized = TxState::Landed {
+            slot: Some(7),
+            commitment: CommitmentLevel::Finalized,
+        };
+        assert_eq!(reconciler.observe(record(HistoryStatus::Finalized, Some(7))), Some(&finalized));
+        assert_eq!(reconciler.observe(chain(CommitmentLevel::Finalized)), None);
+        assert_eq!(reconciler.state(), finalized);
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::codec::RawTransaction;
+use crate::endpoint::endpoint;
+use crate::policy::SYSTEM_PROGRAM_ID;
+use crate::{Aethokit, AethokitError, TxStatus};
+
+/// Longest a replacement waits for one of the two transactions to settle, a bit over the
+/// lifetime of a blockhash.
+const REPLACEMENT_MAX_WAIT: Duration = Duration::from_secs(90);
+const REPLACEMENT_POLL: Duration = Duration::from_secs(1);
+
+/// System program instruction index of `AdvanceNonceAccount`.
+const ADVANCE_NONCE_ACCOUNT: [u8; 4] = [4, 0, 0, 0];
+
+/// What keeps a transaction from landing twice or alongside its replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ReplayProtection {
+    /// Advances a durable nonce, so only one transaction using it can ever land
+    #[serde(rename_all = "camelCase")]
+    DurableNonce { nonce_account: String },
+    /// Valid until the blockhash expires
+    #[serde(rename_all = "camelCase")]
+    Blockhash { recent_blockhash: String },
+}
+
+impl ReplayProtection {
+    /// Durable nonce if the first instruction advances a nonce account, else the blockhash.
+    pub fn of(tx: &RawTransaction) -> Self {
+        let message = &tx.message;
+        let key = |index: u8| message.account_keys.get(usize::from(index));
+        let nonce_account = message.instructions.first().and_then(|ix| {
+            let is_advance = key(ix.program_id_index) == Some(&SYSTEM_PROGRAM_ID)
+                && ix.data == ADVANCE_NONCE_ACCOUNT;
+            is_advance
+                .then(|| ix.accounts.first().and_then(|&index| key(index)))
+                .flatten()
+        });
+        match nonce_account {
+            Some(account) => ReplayProtection::DurableNonce {
+                nonce_account: bs58::encode(account).into_string(),
+            },
+            None => ReplayProtection::Blockhash {
+                recent_blockhash: bs58::encode(message.recent_blockhash).into_string(),
+            },
+        }
+    }
+}
+
+/// Which of an original transaction and its replacement landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplacementOutcome {
+    Original {
+        signature: String,
+    },
+    Replacement {
+        signature: String,
+    },
+    /// Both failed or expired
+    Neither,
+}
+
+#[derive(Debug, Serialize)]
+struct ReplaceRequest<'a> {
+    #[serde(rename = "originalSignature")]
+    original_signature: &'a str,
+    transaction: &'a str,
+    #[serde(rename = "rpcOrNetwork", skip_serializing_if = "Option::is_none")]
+    rpc_or_network: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplaceResponse {
+    signature: String,
+}
+
+impl Aethokit {
+    /// Replace a sponsored transaction that isn't landing with `new_tx`, and wait for
+    /// whichever of the two settles.
+    ///
+    /// `new_tx` must share the original's replay protection: the same durable nonce
+    /// account, or the same recent blockhash so both expire together. The server stops
+    /// rebroadcasting the original before submitting the replacement. Only a durable nonce
+    /// rules out both landing; with a blockhash, a replacement that doesn't conflict with
+    /// the original (e.g. spends the same funds) can land next to it.
+    ///
+    /// Waits up to 90 seconds, or less with [`with_deadline`](Self::with_deadline).
+    ///
+    /// # Errors
+    /// - `ReplacementMismatch` if `new_tx` uses different replay protection than the original
+    /// - `NotConfirmed` if neither transaction settles in time
+    pub async fn replace_transaction(
+        &self,
+        original_signature: &str,
+        new_tx: String,
+    ) -> Result<ReplacementOutcome, AethokitError> {
+        let replacement = ReplayProtection::of(&RawTransaction::from_base64(&new_tx)?);
+        let path = endpoint(
+            "replay-protection",
+            &[],
+            &json!({ "signature": original_signature }),
+        )?;
+        let original: ReplayProtection = self
+            .make_request::<(), _>(&path, Method::GET, None)
+            .await?;
+        if original != replacement {
+            return Err(AethokitError::ReplacementMismatch {
+                original,
+                replacement,
+            });
+        }
+
+        let body = ReplaceRequest {
+            original_signature,
+            transaction: new_tx.trim(),
+            rpc_or_network: self.rpc_or_network.as_deref(),
+        };
+        let resp: ReplaceResponse = self
+            .make_request("replace-tx", Method::POST, Some(&body))
+            .await?;
+
+        let deadline = self.time.now() + REPLACEMENT_MAX_WAIT;
+        loop {
+            let original = self.get_tx_status(original_signature).await?;
+            let new = self.get_tx_status(&resp.signature).await?;
+            let landed = |status: &TxStatus| {
+                matches!(status, TxStatus::Confirmed { .. } | TxStatus::Finalized)
+            };
+            if landed(&original) {
+                return Ok(ReplacementOutcome::Original {
+                    signature: original_signature.to_string(),
+                });
+            }
+            if landed(&new) {
+                return Ok(ReplacementOutcome::Replacement {
+                    signature: resp.signature,
+                });
+            }
+            let failed = |status: &TxStatus| matches!(status, TxStatus::Failed { .. });
+            if failed(&original) && failed(&new) {
+                return Ok(ReplacementOutcome::Neither);
+            }
+            if self.time.now() + REPLACEMENT_POLL > deadline {
+                return Err(AethokitError::NotConfirmed {
+                    hash: resp.signature,
+                    status: None,
+                });
+            }
+            self.scoped_sleep(REPLACEMENT_POLL).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::codec::{CompiledInstruction, MessageHeader, MessageVersion, RawMessage};
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    fn nonce_tx(nonce_account: [u8; 32]) -> RawTransaction {
+        RawTransaction {
+            signatures: vec![[0; 64]],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: vec![[1; 32], nonce_account, SYSTEM_PROGRAM_ID],
+                recent_blockhash: [9; 32],
+                instructions: vec![CompiledInstruction {
+                    program_id_index: 2,
+                    accounts: vec![1, 0],
+                    data: ADVANCE_NONCE_ACCOUNT.to_vec(),
+                }],
+                address_table_lookups: vec![],
+            },
+        }
+    }
+
+    /// A replacement on another nonce is refused; a matching one reports the confirmed side.
+    #[tokio::test]
+    async fn replaces_with_matching_nonce() {
+        let nonce = bs58::encode([5; 32]).into_string();
+        let backend = FakeBackend::new();
+        let original = json!({ "kind": "durableNonce", "nonceAccount": nonce });
+        backend.on(
+            "GET",
+            "replay-protection",
+            FakeResponse::json(200, original),
+        );
+        backend.on(
+            "POST",
+            "replace-tx",
+            FakeResponse::json(200, json!({ "signature": "new" })),
+        );
+        backend.on(
+            "GET",
+            "tx-status",
+            FakeResponse::json(200, json!({ "status": "pending" })),
+        );
+        backend.on(
+            "GET",
+            "tx-status",
+            FakeResponse::json(200, json!({ "status": "finalized" })),
+        );
+        let client = backend.client().build().unwrap();
+
+        match client
+            .replace_transaction("old", nonce_tx([6; 32]).to_base64())
+            .await
+        {
+            Err(AethokitError::ReplacementMismatch { .. }) => {}
+            other => panic!("expected ReplacementMismatch, got {other:?}"),
+        }
+        let outcome = client
+            .replace_transaction("old", nonce_tx([5; 32]).to_base64())
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ReplacementOutcome::Replacement {
+                signature: "new".to_string()
+            }
+        );
+        assert_eq!(
+            backend
+                .requests()
+                .iter()
+                .filter(|r| r.path == "replace-tx")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::io::BufRead;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Aethokit, AethokitError};
+
+/// One line of an exported sponsorship history: the request sent and the status it got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedSponsorship {
+    /// Serialized transaction string, as sent to `sponsor_tx`
+    pub transaction: String,
+    #[serde(rename = "rpcOrNetwork", default, skip_serializing_if = "Option::is_none")]
+    pub rpc_or_network: Option<String>,
+    /// HTTP status the request got in production
+    pub status: u16,
+}
+
+/// A replayed sponsorship whose outcome differs from the recorded one.
+#[derive(Debug)]
+pub struct Divergence {
+    /// 1-based line of the record in the export
+    pub line: usize,
+    pub expected_status: u16,
+    /// Status of the replay, `None` if no response was received
+    pub actual_status: Option<u16>,
+    /// The replay's error, if it failed
+    pub error: Option<AethokitError>,
+}
+
+/// Result of [`Aethokit::replay_history`].
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    pub replayed: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    /// `true` when every replayed sponsorship got its recorded status.
+    pub fn is_clean(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl Aethokit {
+    /// Replay an exported sponsorship history (one [`RecordedSponsorship`] JSON object per
+    /// line) through this client, reporting every request whose status differs from the
+    /// recorded one.
+    ///
+    /// Meant for regression-testing SDK upgrades against real traffic shapes: point the
+    /// client at a sandbox or a `FakeBackend` from the `test-util` feature first, as every
+    /// line is really submitted. Blank lines are skipped.
+    ///
+    /// # Errors
+    /// - `Serde` if a line is not a valid record, with nothing replayed after it
+    pub async fn replay_history(&self, export: impl BufRead) -> Result<ReplayReport, AethokitError> {
+        let mut report = ReplayReport::default();
+        for (i, line) in export.lines().enumerate() {
+            let line = line.map_err(serde_json::Error::io)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: RecordedSponsorship = serde_json::from_str(&line)?;
+            let mut client = self.clone();
+            if record.rpc_or_network.is_some() {
+                client.rpc_or_network = record.rpc_or_network;
+            }
+            let (actual_status, error) = match client.sponsor_signature_with_meta(record.transaction).await {
+                Ok(resp) => (Some(resp.meta.status.as_u16()), None),
+                Err(err) => (err.status().map(|status| status.as_u16()), Some(err)),
+            };
+            report.replayed += 1;
+            if actual_status != Some(record.status) {
+                report.divergences.push(Divergence {
+                    line: i + 1,
+                    expected_status: record.status,
+                    actual_status,
+                    error,
+                });
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Only requests whose status changed are reported, with their line in the export.
+    #[tokio::test]
+    async fn reports_divergences() {
+        let backend = FakeBackend::new();
+        backend
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "a" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(400, json!({ "error": "bad tx" })));
+        let client = backend.client().build().unwrap();
+        let export = concat!(
+            r#"{"transaction":"tx1","status":200}"#,
+            "\n\n",
+            r#"{"transaction":"tx2","rpcOrNetwork":"mainnet","status":200}"#,
+            "\n",
+        );
+
+        let report = client.replay_history(export.as_bytes()).await.unwrap();
+        assert_eq!(report.replayed, 2);
+        assert_eq!(report.divergences.len(), 1);
+        let divergence = &report.divergences[0];
+        assert_eq!((divergence.line, divergence.actual_status), (3, Some(400)));
+        assert_eq!(backend.requests()[1].body.as_ref().unwrap()["rpcOrNetwork"], "mainnet");
+    }
+}
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{codes, Aethokit, AethokitError, ReservationId};
+
+/// Sponsorship budget held for a job, see [`Aethokit::reserve_quota`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Reservation {
+    pub id: ReservationId,
+    /// Budget held, in lamports
+    #[serde(rename = "amount", deserialize_with = "crate::lenient::u64")]
+    pub amount_lamports: u64,
+    /// Time the hold lapses
+    #[serde(rename = "expiresAt")]
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReserveRequest {
+    amount: u64,
+    #[serde(rename = "ttlSecs")]
+    ttl_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ReleaseRequest<'a> {
+    id: &'a ReservationId,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {}
+
+impl Aethokit {
+    /// Hold `amount_lamports` of the gas tank's sponsorship budget for `ttl`, so a large
+    /// job can't be starved halfway through by other traffic.
+    ///
+    /// Charge sponsorships to the hold with [`SponsorOptions::reservation_id`](crate::SponsorOptions::reservation_id).
+    /// Unused budget returns to the tank when the hold expires or is released.
+    pub async fn reserve_quota(
+        &self,
+        amount_lamports: u64,
+        ttl: Duration,
+    ) -> Result<Reservation, AethokitError> {
+        let body = ReserveRequest {
+            amount: amount_lamports,
+            ttl_secs: ttl.as_secs().max(1),
+        };
+        self.make_request("reserve-quota", Method::POST, Some(&body))
+            .await
+    }
+
+    /// Release a hold early, returning its unused budget to the tank.
+    pub async fn release_reservation(&self, id: &ReservationId) -> Result<(), AethokitError> {
+        self.make_request::<_, ReleaseResponse>(
+            "release-reservation",
+            Method::POST,
+            Some(&ReleaseRequest { id }),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Turn a rejected sponsorship charged to `id` into a typed reservation error, if it is one.
+fn map_error(err: AethokitError, id: ReservationId) -> AethokitError {
+    match err.code() {
+        Some(codes::RESERVATION_EXHAUSTED) => AethokitError::ReservationExhausted { id },
+        Some(codes::RESERVATION_EXPIRED) => AethokitError::ReservationExpired { id },
+        _ => err,
+    }
+}
+
+/// [`map_error`] for a sponsorship result, if it was charged to a reservation.
+pub(crate) fn map_result<T>(
+    result: Result<T, AethokitError>,
+    id: Option<ReservationId>,
+) -> Result<T, AethokitError> {
+    match (result, id) {
+        (Err(err), Some(id)) => Err(map_error(err, id)),
+        (result, _) => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+    use crate::SponsorOptions;
+
+    /// Sponsorships carry the reservation and its exhaustion surfaces as a typed error.
+    #[tokio::test]
+    async fn charges_sponsorships_to_reservation() {
+        let backend = FakeBackend::new();
+        let reservation = json!({
+            "id": "res_1",
+            "amount": 2_000_000_000u64,
+            "expiresAt": "2024-05-01T12:00:00Z",
+        });
+        backend.on(
+            "POST",
+            "reserve-quota",
+            FakeResponse::json(200, reservation),
+        );
+        let exhausted = json!({ "code": "RESERVATION_EXHAUSTED", "error": "hold used up" });
+        backend.on("POST", "sponsor-tx", FakeResponse::json(402, exhausted));
+        let client = backend.client().build().unwrap();
+
+        let reservation = client
+            .reserve_quota(2_000_000_000, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(reservation.id.as_str(), "res_1");
+        let options = SponsorOptions {
+            reservation_id: Some(reservation.id.clone()),
+            ..Default::default()
+        };
+        match client
+            .sponsor_tx_with_options("tx".to_string(), options)
+            .await
+        {
+            Err(AethokitError::ReservationExhausted { id }) => assert_eq!(id, reservation.id),
+            other => panic!("expected ReservationExhausted, got {other:?}"),
+        }
+        let requests = backend.requests();
+        assert_eq!(
+            requests[0].body,
+            Some(json!({ "amount": 2_000_000_000u64, "ttlSecs": 3600 }))
+        );
+        assert_eq!(
+            requests[1].body,
+            Some(json!({ "transaction": "tx", "reservationId": "res_1" }))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{base_urls, AethokitError};
+
+/// How failed requests are retried.
+///
+/// GET requests are retried on any retryable error. `sponsor_tx` and other POSTs are only
+/// retried when that can't submit a transaction twice, see [`PostRetries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled on each further retry
+    #[serde(rename = "initial_backoff_ms", with = "duration_ms")]
+    pub initial_backoff: Duration,
+    /// Upper bound for the wait between retries
+    #[serde(rename = "max_backoff_ms", with = "duration_ms")]
+    pub max_backoff: Duration,
+    /// Which failed POSTs are retried
+    #[serde(default)]
+    pub post_retries: PostRetries,
+}
+
+/// Which failed POST requests a [`RetryPolicy`] retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostRetries {
+    /// Send POSTs once
+    Never,
+    /// Retry POSTs whose connection was refused, so nothing reached the server
+    PreTransmission,
+    /// Also retry POSTs on any retryable error when they carry an
+    /// [`IDEMPOTENCY_KEY_HEADER`](crate::IDEMPOTENCY_KEY_HEADER), which the backend uses
+    /// to sponsor them at most once
+    #[default]
+    Idempotent,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            post_retries: PostRetries::default(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (starting at 0), honouring a server-provided `retry-after`.
+    pub(crate) fn backoff(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        retry_after.map_or(exponential, |wait| wait.min(self.max_backoff))
+    }
+
+    /// Whether a POST that failed with `err` may be sent again, per
+    /// [`post_retries`](Self::post_retries). `keyed` is whether it carried an
+    /// [`IDEMPOTENCY_KEY_HEADER`](crate::IDEMPOTENCY_KEY_HEADER); otherwise it is only resent
+    /// if nothing reached the server, so a transaction can't land twice.
+    pub(crate) fn may_resend_post(&self, keyed: bool, err: &AethokitError) -> bool {
+        match self.post_retries {
+            PostRetries::Never => false,
+            PostRetries::Idempotent if keyed => err.is_retryable(),
+            _ => base_urls::is_connect_error(err),
+        }
+    }
+}
+
+/// Cap on retries across every request of a client, so that during an outage retries
+/// can't multiply the load on the backend.
+///
+/// A token bucket: each request sent earns `ratio` tokens and each retry spends one, so
+/// once the bucket is empty requests fail with their last error instead of retrying.
+/// Covers the retries of [`RetryPolicy`] and of [`Aethokit::sponsor_queue`](crate::Aethokit::sponsor_queue).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryBudget {
+    /// Tokens earned per request, e.g. `0.1` allows about one retry for every ten requests
+    pub ratio: f64,
+    /// Tokens the bucket starts with and can hold, allowing a burst of retries after a
+    /// quiet period
+    pub max_tokens: u32,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: 0.1,
+            max_tokens: 10,
+        }
+    }
+}
+
+/// Use of a client's [`RetryBudget`], from [`Aethokit::retry_budget_stats`](crate::Aethokit::retry_budget_stats).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryBudgetStats {
+    /// Tokens left; a retry needs one
+    pub available: f64,
+    /// Retries the budget allowed
+    pub retries: u64,
+    /// Retries refused because the budget was empty
+    pub denied: u64,
+}
+
+/// The tokens of a [`RetryBudget`], shared between clones of a client.
+#[derive(Debug)]
+pub(crate) struct RetryBucket {
+    budget: RetryBudget,
+    stats: Mutex<RetryBudgetStats>,
+}
+
+impl RetryBucket {
+    pub(crate) fn new(budget: RetryBudget) -> Self {
+        Self {
+            budget,
+            stats: Mutex::new(RetryBudgetStats {
+                available: f64::from(budget.max_tokens),
+                retries: 0,
+                denied: 0,
+            }),
+        }
+    }
+
+    /// Earn tokens for a request about to be sent.
+    pub(crate) fn deposit(&self) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.available = (stats.available + self.budget.ratio).min(f64::from(self.budget.max_tokens));
+    }
+
+    /// Spend a token on a retry, `false` if none is left.
+    pub(crate) fn try_retry(&self) -> bool {
+        let mut stats = self.stats.lock().unwrap();
+        if stats.available >= 1.0 {
+            stats.available -= 1.0;
+            stats.retries += 1;
+            true
+        } else {
+            stats.denied += 1;
+            false
+        }
+    }
+
+    pub(crate) fn stats(&self) -> RetryBudgetStats {
+        *self.stats.lock().unwrap()
+    }
+}
+
+pub(crate) mod duration_ms {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backoff doubles per retry, is capped, and prefers the server's `retry-after`.
+    #[test]
+    fn computes_backoff() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0, None), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2, None), Duration::from_millis(800));
+        assert_eq!(policy.backoff(10, None), Duration::from_secs(5));
+        assert_eq!(policy.backoff(0, Some(Duration::from_secs(2))), Duration::from_secs(2));
+    }
+
+    /// Retries spend the bucket down and requests refill it, up to the cap.
+    #[test]
+    fn budgets_retries() {
+        let bucket = RetryBucket::new(RetryBudget {
+            ratio: 0.5,
+            max_tokens: 1,
+        });
+        assert!(bucket.try_retry());
+        assert!(!bucket.try_retry());
+        bucket.deposit();
+        bucket.deposit();
+        bucket.deposit();
+        assert!(bucket.try_retry());
+        assert!(!bucket.try_retry());
+        let stats = bucket.stats();
+        assert_eq!((stats.retries, stats.denied), (2, 2));
+        assert_eq!(stats.available, 0.0);
+    }
+
+    /// POSTs are resent after a 5xx only with an idempotency key, unless POST retries are off.
+    #[tokio::test]
+    async fn retries_posts_with_idempotency_key() {
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+        use crate::IDEMPOTENCY_KEY_HEADER;
+
+        let policy = |post_retries| RetryPolicy {
+            initial_backoff: Duration::ZERO,
+            post_retries,
+            ..RetryPolicy::default()
+        };
+        let sponsored = |post_retries, key: Option<&'static str>| async move {
+            let backend = FakeBackend::new();
+            backend
+                .on("POST", "sponsor-tx", FakeResponse::json(503, json!({ "error": "busy" })))
+                .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })));
+            let mut client = backend.client().retry_policy(policy(post_retries)).build().unwrap();
+            if let Some(key) = key {
+                client = client.with_header(IDEMPOTENCY_KEY_HEADER, key).unwrap();
+            }
+            client.sponsor_tx("tx".to_string()).await.is_ok()
+        };
+        assert!(sponsored(PostRetries::Idempotent, Some("order-1")).await);
+        assert!(!sponsored(PostRetries::Idempotent, None).await);
+        assert!(!sponsored(PostRetries::Never, Some("order-1")).await);
+    }
+}
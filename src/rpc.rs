@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+
+use crate::{Aethokit, AethokitError};
+
+const MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+const DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+const TESTNET_RPC_URL: &str = "https://api.testnet.solana.com";
+
+impl Aethokit {
+    /// Solana RPC endpoint used by the transaction helpers.
+    ///
+    /// This is `rpc_or_network` itself when it is a URL, otherwise the public endpoint
+    /// of the named network (devnet when unset, matching the backend's default).
+    pub fn rpc_url(&self) -> &str {
+        match self.rpc_or_network.as_deref().map(str::trim) {
+            Some(url) if url.contains("://") => url,
+            Some("mainnet" | "mainnet-beta") => MAINNET_RPC_URL,
+            Some("testnet") => TESTNET_RPC_URL,
+            _ => DEVNET_RPC_URL,
+        }
+    }
+
+    /// Fetch a recent blockhash from [`rpc_url`](Self::rpc_url).
+    pub async fn get_latest_blockhash(&self) -> Result<Hash, AethokitError> {
+        let result = self.rpc_call("getLatestBlockhash", json!([])).await?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| AethokitError::Rpc("response is missing a blockhash".to_string()))?;
+        Hash::from_str(blockhash).map_err(|err| AethokitError::Rpc(err.to_string()))
+    }
+
+    pub(crate) async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, AethokitError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let request = async {
+            let req = self.http.post(self.rpc_url()).json(&body).build()?;
+            let resp = self
+                .transport
+                .0
+                .execute(req)
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            Ok(resp)
+        };
+        let mut resp: Value = self.within_scope(request).await?;
+        if let Some(message) = resp["error"]["message"].as_str() {
+            return Err(AethokitError::Rpc(format!("{method}: {message}")));
+        }
+        Ok(resp["result"].take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Network names map to public endpoints and URLs are used as-is.
+    #[test]
+    fn resolves_rpc_url() {
+        let client = |rpc: Option<&str>| {
+            let builder = Aethokit::builder().gas_key("key");
+            match rpc {
+                Some(rpc) => builder.rpc_or_network(rpc),
+                None => builder,
+            }
+            .build()
+            .unwrap()
+        };
+        assert_eq!(client(None).rpc_url(), DEVNET_RPC_URL);
+        assert_eq!(client(Some("mainnet")).rpc_url(), MAINNET_RPC_URL);
+        assert_eq!(client(Some("https://rpc.example.com")).rpc_url(), "https://rpc.example.com");
+    }
+}
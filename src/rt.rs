@@ -0,0 +1,178 @@
+//! Timers for retries, polling and deadlines, independent of the async runtime.
+//!
+//! With the default `tokio-runtime` feature these are tokio's, which also lets tests pause
+//! the clock. Without it a background thread wakes sleeping tasks, so the timers work under
+//! any executor. HTTP still needs a tokio reactor with the default reqwest transport, unless
+//! a custom [`HttpTransport`](crate::HttpTransport) is supplied.
+
+use std::future::Future;
+use std::pin::pin;
+
+use futures_util::future::{self, Either};
+
+#[cfg(feature = "tokio-runtime")]
+pub(crate) use tokio::time::{sleep, sleep_until, Instant};
+
+#[cfg(not(feature = "tokio-runtime"))]
+pub(crate) use self::timer::{sleep, sleep_until, Instant};
+
+/// The current time of the runtime's clock.
+// the conversion is the identity without `tokio-runtime`
+#[allow(clippy::useless_conversion)]
+pub(crate) fn now() -> std::time::Instant {
+    Instant::now().into()
+}
+
+/// Run `fut` until `deadline`, `None` if it passes first.
+// the conversion is the identity without `tokio-runtime`
+#[allow(clippy::useless_conversion)]
+pub(crate) async fn timeout_at<F: Future>(
+    deadline: std::time::Instant,
+    fut: F,
+) -> Option<F::Output> {
+    match future::select(pin!(fut), pin!(sleep_until(deadline.into()))).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(any(test, not(feature = "tokio-runtime")))]
+mod timer {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Condvar, Mutex, OnceLock};
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    pub(crate) use std::time::Instant;
+
+    pub(crate) fn sleep(duration: Duration) -> Sleep {
+        sleep_until(Instant::now() + duration)
+    }
+
+    pub(crate) fn sleep_until(deadline: Instant) -> Sleep {
+        Sleep {
+            deadline,
+            waker: None,
+        }
+    }
+
+    /// Future completing at `deadline`, woken by the timer thread.
+    pub(crate) struct Sleep {
+        deadline: Instant,
+        /// Waker last handed to the timer thread
+        waker: Option<Waker>,
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if Instant::now() >= self.deadline {
+                return Poll::Ready(());
+            }
+            if !self
+                .waker
+                .as_ref()
+                .is_some_and(|waker| waker.will_wake(cx.waker()))
+            {
+                self.waker = Some(cx.waker().clone());
+                timer().wake_at(self.deadline, cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+
+    struct Entry {
+        deadline: Instant,
+        waker: Waker,
+    }
+
+    // ordered so the heap pops the earliest deadline first
+    impl Ord for Entry {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    impl PartialOrd for Entry {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for Entry {}
+
+    #[derive(Default)]
+    struct Timer {
+        entries: Mutex<BinaryHeap<Entry>>,
+        changed: Condvar,
+    }
+
+    fn timer() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            std::thread::Builder::new()
+                .name("aethokit-timer".to_string())
+                .spawn(|| timer().run())
+                .expect("failed to spawn the timer thread");
+            Timer::default()
+        })
+    }
+
+    impl Timer {
+        fn wake_at(&self, deadline: Instant, waker: Waker) {
+            self.entries.lock().unwrap().push(Entry { deadline, waker });
+            self.changed.notify_one();
+        }
+
+        fn run(&self) {
+            let mut entries = self.entries.lock().unwrap();
+            loop {
+                let now = Instant::now();
+                while entries.peek().is_some_and(|entry| entry.deadline <= now) {
+                    entries.pop().expect("peeked").waker.wake();
+                }
+                entries = match entries.peek() {
+                    Some(next) => {
+                        let wait = next.deadline - now;
+                        self.changed.wait_timeout(entries, wait).unwrap().0
+                    }
+                    None => self.changed.wait(entries).unwrap(),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::timer;
+
+    /// The fallback timer wakes sleeps in deadline order, whichever was registered first.
+    #[tokio::test]
+    async fn fallback_timer_wakes_sleeps() {
+        let start = timer::Instant::now();
+        let long = async {
+            timer::sleep(Duration::from_millis(60)).await;
+            start.elapsed()
+        };
+        let short = async {
+            timer::sleep(Duration::from_millis(20)).await;
+            start.elapsed()
+        };
+        let (long, short) = futures_util::join!(long, short);
+        assert!(short >= Duration::from_millis(20) && short < long);
+        assert!(long >= Duration::from_millis(60));
+    }
+}
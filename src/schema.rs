@@ -0,0 +1,150 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+const SPONSOR_TX_SCHEMA: &str = include_str!("../schemas/sponsor-tx.json");
+
+/// A field-level problem found while validating a request body against the endpoint's schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// JSON pointer to the offending field, e.g. `/transaction`
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = if self.field.is_empty() { "/" } else { &self.field };
+        write!(f, "{field}: {}", self.message)
+    }
+}
+
+pub(crate) fn format_errors(errors: &[SchemaError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Embedded JSON Schema for the request body of the endpoint at `path`, if there is one.
+pub(crate) fn for_path(path: &str) -> Option<&'static Value> {
+    static SPONSOR_TX: OnceLock<Value> = OnceLock::new();
+    let endpoint = path.split('?').next().unwrap_or(path);
+    match endpoint {
+        "sponsor-tx" => Some(SPONSOR_TX.get_or_init(|| {
+            serde_json::from_str(SPONSOR_TX_SCHEMA).expect("embedded schema is valid JSON")
+        })),
+        _ => None,
+    }
+}
+
+/// Validate `instance` against `schema`.
+///
+/// Supports the subset of JSON Schema used by the embedded schemas: `type`, `required`,
+/// `properties`, `additionalProperties: false`, `items`, `minLength`, `minimum` and `enum`.
+pub(crate) fn validate(schema: &Value, instance: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    validate_at(schema, instance, String::new(), &mut errors);
+    errors
+}
+
+fn validate_at(schema: &Value, instance: &Value, pointer: String, errors: &mut Vec<SchemaError>) {
+    let mut fail = |message: String| {
+        errors.push(SchemaError {
+            field: pointer.clone(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        if !has_type(instance, expected) {
+            fail(format!("expected {expected}, got {}", type_name(instance)));
+            return;
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(instance) {
+            fail(format!("must be one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let (Some(min), Some(s)) = (schema.get("minLength").and_then(Value::as_u64), instance.as_str()) {
+        if (s.chars().count() as u64) < min {
+            fail(format!("must be at least {min} characters"));
+        }
+    }
+    if let (Some(min), Some(n)) = (schema.get("minimum").and_then(Value::as_f64), instance.as_f64()) {
+        if n < min {
+            fail(format!("must be at least {min}"));
+        }
+    }
+
+    if let Some(object) = instance.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    errors.push(SchemaError {
+                        field: format!("{pointer}/{name}"),
+                        message: "is required".to_string(),
+                    });
+                }
+            }
+        }
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+        for (name, value) in object {
+            let field = format!("{pointer}/{name}");
+            match properties.and_then(|p| p.get(name)) {
+                Some(sub_schema) => validate_at(sub_schema, value, field, errors),
+                None if closed => errors.push(SchemaError {
+                    field,
+                    message: "is not a known field".to_string(),
+                }),
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), instance.as_array()) {
+        for (i, value) in array.iter().enumerate() {
+            validate_at(items, value, format!("{pointer}/{i}"), errors);
+        }
+    }
+}
+
+fn has_type(instance: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => instance.is_u64() || instance.is_i64(),
+        other => type_name(instance) == other || (other == "number" && instance.is_number()),
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// An empty transaction and an unknown field are reported with their JSON pointers.
+    #[test]
+    fn reports_field_level_errors() {
+        let schema = for_path("sponsor-tx").unwrap();
+        assert!(validate(schema, &json!({ "transaction": "AQAB" })).is_empty());
+
+        let errors = validate(schema, &json!({ "transaction": "", "network": "devnet" }));
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["/network", "/transaction"]);
+        assert_eq!(errors[1].to_string(), "/transaction: must be at least 1 characters");
+    }
+}
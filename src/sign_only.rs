@@ -0,0 +1,82 @@
+use crate::{Aethokit, AethokitError, SponsorOptions};
+
+/// A transaction signed by the gas tank but not broadcast, from
+/// [`Aethokit::sponsor_sign_only`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    /// Signature the transaction will land under, the gas tank's as fee payer
+    pub signature: String,
+    /// The fully-signed transaction, base64, ready to send to any RPC node
+    pub transaction: String,
+}
+
+#[cfg(feature = "solana")]
+impl SignedTransaction {
+    /// The signed transaction, decoded.
+    pub fn decode(&self) -> Result<solana_sdk::transaction::VersionedTransaction, AethokitError> {
+        use base64::Engine as _;
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(self.transaction.trim())
+            .map_err(|err| crate::codec::CodecError::Base64(err.to_string()))?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+}
+
+impl Aethokit {
+    /// Have the gas tank sign `tx` as fee payer without broadcasting it, to submit it through
+    /// your own RPC path such as a Jito bundle or a priority endpoint.
+    ///
+    /// The sponsorship counts against quota and guardrails like any other; the fee is only
+    /// paid if the transaction lands.
+    ///
+    /// # Errors
+    /// - `MissingSignedTransaction` if the backend answers without the signed transaction
+    pub async fn sponsor_sign_only(&self, tx: String) -> Result<SignedTransaction, AethokitError> {
+        let sponsored = self
+            .sponsor_with_options(tx, SponsorOptions::default(), false)
+            .await?
+            .value;
+        Ok(SignedTransaction {
+            transaction: sponsored
+                .signed_transaction
+                .ok_or(AethokitError::MissingSignedTransaction)?,
+            signature: sponsored.signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// The backend is asked not to broadcast, and an answer without the transaction is an error.
+    #[tokio::test]
+    async fn returns_signed_transaction() {
+        let backend = FakeBackend::new();
+        let body = json!({ "hash": "sig", "signedTransaction": "AQID" });
+        backend.on("POST", "sponsor-tx", FakeResponse::json(200, body));
+        backend.on(
+            "POST",
+            "sponsor-tx",
+            FakeResponse::json(200, json!({ "hash": "sig" })),
+        );
+        let client = backend.client().build().unwrap();
+
+        let signed = client.sponsor_sign_only("tx".to_string()).await.unwrap();
+        assert_eq!(signed.signature, "sig");
+        assert_eq!(signed.transaction, "AQID");
+        assert_eq!(
+            backend.requests()[0].body,
+            Some(json!({ "transaction": "tx", "broadcast": false, "returnSigned": true }))
+        );
+        assert!(matches!(
+            client.sponsor_sign_only("tx".to_string()).await,
+            Err(AethokitError::MissingSignedTransaction)
+        ));
+    }
+}
@@ -0,0 +1,271 @@
+//! Signing abstraction used by the `solana` feature helpers.
+//!
+//! Implement [`Signer`] for keys that don't live in process memory (AWS KMS, an HSM,
+//! a signing service) so they can partially sign sponsored transactions alongside
+//! local [`Keypair`]s.
+
+use futures_util::future::{self, BoxFuture};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer as _;
+use solana_sdk::transaction::Transaction;
+use thiserror::Error;
+
+#[cfg(feature = "ledger")]
+pub mod ledger;
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("{0} is not a required signer of the transaction")]
+    NotASigner(Pubkey),
+    #[error("transaction is missing a signature from {0}")]
+    MissingSignature(Pubkey),
+    /// A program-derived address was marked as a signer; only its program can sign for it,
+    /// through `invoke_signed`, so pass it as a non-signer account
+    #[error("{0} is a program-derived address and cannot sign the transaction")]
+    ProgramDerivedSigner(Pubkey),
+    #[error("{0} was passed as a signer more than once")]
+    DuplicateSigner(Pubkey),
+    /// The signature in a key's slot was not made by that key, e.g. signatures of a
+    /// transaction assembled by several parties were put in the wrong order
+    #[error("signature in the slot of {0} was not made by that key")]
+    WrongSignature(Pubkey),
+    /// The header requires more signers than the transaction has account keys or
+    /// signature slots, so it can't be signed or submitted
+    #[error("transaction requires {required} signers but has {account_keys} account keys and {signatures} signatures")]
+    MalformedTransaction {
+        required: usize,
+        account_keys: usize,
+        signatures: usize,
+    },
+    #[error("signer {pubkey} failed: {source}")]
+    Failed {
+        pubkey: Pubkey,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// A key that can sign transaction messages, possibly asynchronously on a remote device.
+pub trait Signer: Send + Sync {
+    /// Public key the signature is made with.
+    fn pubkey(&self) -> Pubkey;
+
+    /// Sign the serialized transaction message.
+    fn sign_message<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>>;
+}
+
+impl Signer for Keypair {
+    fn pubkey(&self) -> Pubkey {
+        solana_sdk::signer::Signer::pubkey(self)
+    }
+
+    fn sign_message<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        let result = self.try_sign_message(message).map_err(|err| SignerError::Failed {
+            pubkey: Signer::pubkey(self),
+            source: Box::new(err),
+        });
+        Box::pin(future::ready(result))
+    }
+}
+
+/// Sign `tx` with each of `signers`, leaving the other signature slots (such as the
+/// gas tank's fee-payer slot) untouched.
+///
+/// # Errors
+/// - `MalformedTransaction` if the header requires more signers than `tx` has keys or slots
+/// - `NotASigner` if a signer's key is not one of the transaction's required signers
+/// - `Failed` if a signer could not produce a signature
+pub async fn partial_sign(tx: &mut Transaction, signers: &[&dyn Signer]) -> Result<(), SignerError> {
+    let message = tx.message_data();
+    let keys = required_signers(tx)?.to_vec();
+    sign_slots(&keys, &mut tx.signatures, &message, signers).await
+}
+
+/// The first `required` of `account_keys`, checking that a possibly untrusted transaction
+/// has a key and a signature slot for each signer its header requires.
+pub(crate) fn required_keys(
+    account_keys: &[Pubkey],
+    required: usize,
+    signatures: usize,
+) -> Result<&[Pubkey], SignerError> {
+    if required > account_keys.len() || required > signatures {
+        return Err(SignerError::MalformedTransaction {
+            required,
+            account_keys: account_keys.len(),
+            signatures,
+        });
+    }
+    Ok(&account_keys[..required])
+}
+
+fn required_signers(tx: &Transaction) -> Result<&[Pubkey], SignerError> {
+    let required = usize::from(tx.message.header.num_required_signatures);
+    required_keys(&tx.message.account_keys, required, tx.signatures.len())
+}
+
+/// Fill the slot of each of `signers` in `signatures`, which line up with `required_keys`.
+///
+/// Everything is checked before the first signer is asked, so a remote signer isn't
+/// prompted for a transaction that can't be completed.
+pub(crate) async fn sign_slots(
+    required_keys: &[Pubkey],
+    signatures: &mut [Signature],
+    message: &[u8],
+    signers: &[&dyn Signer],
+) -> Result<(), SignerError> {
+    if signatures.len() < required_keys.len() {
+        return Err(SignerError::MalformedTransaction {
+            required: required_keys.len(),
+            account_keys: required_keys.len(),
+            signatures: signatures.len(),
+        });
+    }
+    // the fee payer's slot is the gas tank's
+    if let Some(pda) = required_keys.iter().skip(1).find(|key| !key.is_on_curve()) {
+        return Err(SignerError::ProgramDerivedSigner(*pda));
+    }
+    for (index, signer) in signers.iter().enumerate() {
+        let pubkey = signer.pubkey();
+        if signers[..index].iter().any(|other| other.pubkey() == pubkey) {
+            return Err(SignerError::DuplicateSigner(pubkey));
+        }
+    }
+    for signer in signers {
+        let pubkey = signer.pubkey();
+        let index = required_keys
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or(SignerError::NotASigner(pubkey))?;
+        signatures[index] = signer.sign_message(message).await?;
+    }
+    Ok(())
+}
+
+/// Required signers, other than the fee payer, whose signature slot is still empty.
+///
+/// # Errors
+/// - `MalformedTransaction` if the header requires more signers than `tx` has keys or slots
+pub fn missing_signers(tx: &Transaction) -> Result<Vec<Pubkey>, SignerError> {
+    Ok(required_signers(tx)?
+        .iter()
+        .zip(&tx.signatures)
+        .skip(1)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect())
+}
+
+/// Check a transaction assembled outside the SDK before submitting it: every required
+/// signer but the fee payer has signed, in its own slot, and none is a program-derived
+/// address.
+///
+/// # Errors
+/// - `MalformedTransaction` if the header requires more signers than `tx` has keys or slots
+/// - `ProgramDerivedSigner` if a required signer is a program-derived address
+/// - `MissingSignature` if a signer other than the fee payer hasn't signed
+/// - `WrongSignature` if a signature doesn't verify against the key of its slot
+pub fn check_signatures(tx: &Transaction) -> Result<(), SignerError> {
+    let message = tx.message_data();
+    for (key, signature) in required_signers(tx)?.iter().zip(&tx.signatures).skip(1) {
+        if !key.is_on_curve() {
+            return Err(SignerError::ProgramDerivedSigner(*key));
+        }
+        if *signature == Signature::default() {
+            return Err(SignerError::MissingSignature(*key));
+        }
+        if !signature.verify(key.as_ref(), &message) {
+            return Err(SignerError::WrongSignature(*key));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    /// Only the signer's own slot is filled; the fee payer's slot stays empty for the gas tank.
+    #[tokio::test]
+    async fn fills_only_own_signature_slot() {
+        let fee_payer = Pubkey::new_unique();
+        let sender = Keypair::new();
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![AccountMeta::new(Signer::pubkey(&sender), true)],
+        );
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&fee_payer));
+
+        partial_sign(&mut tx, &[&sender]).await.unwrap();
+        assert_eq!(tx.signatures[0], Signature::default());
+        assert_ne!(tx.signatures[1], Signature::default());
+        assert!(missing_signers(&tx).unwrap().is_empty());
+
+        let stranger = Keypair::new();
+        match partial_sign(&mut tx, &[&stranger]).await {
+            Err(SignerError::NotASigner(key)) => assert_eq!(key, Signer::pubkey(&stranger)),
+            other => panic!("expected NotASigner, got {other:?}"),
+        }
+    }
+
+    /// A PDA marked as signer and signatures in swapped slots are reported before submission.
+    #[tokio::test]
+    async fn validates_signers() {
+        let fee_payer = Pubkey::new_unique();
+        let (user, approver) = (Keypair::new(), Keypair::new());
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![
+                AccountMeta::new(Signer::pubkey(&user), true),
+                AccountMeta::new_readonly(Signer::pubkey(&approver), true),
+            ],
+        );
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&fee_payer));
+        match partial_sign(&mut tx, &[&user, &user]).await {
+            Err(SignerError::DuplicateSigner(key)) => assert_eq!(key, Signer::pubkey(&user)),
+            other => panic!("expected DuplicateSigner, got {other:?}"),
+        }
+        partial_sign(&mut tx, &[&approver, &user]).await.unwrap();
+        check_signatures(&tx).unwrap();
+        tx.signatures.swap(1, 2);
+        assert!(matches!(check_signatures(&tx), Err(SignerError::WrongSignature(_))));
+
+        let (pda, _) = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique());
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![AccountMeta::new(pda, true)]);
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&fee_payer));
+        match partial_sign(&mut tx, &[&user]).await {
+            Err(SignerError::ProgramDerivedSigner(key)) => assert_eq!(key, pda),
+            other => panic!("expected ProgramDerivedSigner, got {other:?}"),
+        }
+    }
+
+    /// A header requiring more signers than there are keys or slots is an error, not a panic.
+    #[tokio::test]
+    async fn rejects_malformed_transactions() {
+        let sender = Keypair::new();
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![AccountMeta::new(Signer::pubkey(&sender), true)],
+        );
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&Pubkey::new_unique()));
+        tx.message.header.num_required_signatures = 9;
+        let malformed = |result: Result<_, SignerError>| {
+            matches!(result, Err(SignerError::MalformedTransaction { required: 9, .. }))
+        };
+        assert!(malformed(missing_signers(&tx).map(drop)));
+        assert!(malformed(check_signatures(&tx)));
+        assert!(malformed(partial_sign(&mut tx, &[&sender]).await));
+
+        tx.message.header.num_required_signatures = 2;
+        tx.signatures.truncate(1);
+        match partial_sign(&mut tx, &[&sender]).await {
+            Err(SignerError::MalformedTransaction { required, account_keys, signatures }) => {
+                assert_eq!((required, account_keys, signatures), (2, 3, 1))
+            }
+            other => panic!("expected MalformedTransaction, got {other:?}"),
+        }
+    }
+}
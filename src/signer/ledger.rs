@@ -0,0 +1,172 @@
+//! [`Signer`] backed by the Solana app on a Ledger hardware wallet.
+//!
+//! The adapter speaks the Solana app's APDU protocol and leaves the USB/HID link to a
+//! [`LedgerTransport`], so it works with whichever HID or speculos transport the
+//! application already uses.
+
+use futures_util::future::BoxFuture;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use thiserror::Error;
+
+use super::{Signer, SignerError};
+
+const CLA: u8 = 0xe0;
+const INS_GET_PUBKEY: u8 = 0x05;
+const INS_SIGN_MESSAGE: u8 = 0x06;
+const P1_NON_CONFIRM: u8 = 0x00;
+const P1_CONFIRM: u8 = 0x01;
+const P2_EXTEND: u8 = 0x01;
+const P2_MORE: u8 = 0x02;
+const MAX_CHUNK: usize = 255;
+const HARDENED: u32 = 0x8000_0000;
+
+const SW_OK: u16 = 0x9000;
+const SW_USER_REJECTED: u16 = 0x6985;
+
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    #[error("ledger transport error: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    #[error("transaction rejected on the device")]
+    UserRejected,
+    #[error("ledger returned status {0:#06x}")]
+    Status(u16),
+    #[error("malformed response from the device")]
+    MalformedResponse,
+}
+
+/// Raw APDU exchange with a Ledger device.
+pub trait LedgerTransport: Send + Sync {
+    /// Send one APDU and return the full response, including the trailing status word.
+    fn exchange<'a>(&'a self, apdu: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, LedgerError>>;
+}
+
+/// A Ledger-held key at `m/44'/501'/account'/change'`.
+#[derive(Debug)]
+pub struct LedgerSigner<T> {
+    transport: T,
+    derivation_path: Vec<u32>,
+    pubkey: Pubkey,
+}
+
+impl<T: LedgerTransport> LedgerSigner<T> {
+    /// Connect to the key at `m/44'/501'/account'` (or `.../account'/change'`), reading its
+    /// public key from the device.
+    pub async fn new(transport: T, account: u32, change: Option<u32>) -> Result<Self, LedgerError> {
+        let mut derivation_path = vec![44 | HARDENED, 501 | HARDENED, account | HARDENED];
+        derivation_path.extend(change.map(|c| c | HARDENED));
+
+        let apdu = build_apdu(INS_GET_PUBKEY, P1_NON_CONFIRM, 0, &encode_path(&derivation_path));
+        let data = check_status(transport.exchange(&apdu).await?)?;
+        let bytes: [u8; 32] = data.try_into().map_err(|_| LedgerError::MalformedResponse)?;
+        Ok(Self {
+            transport,
+            derivation_path,
+            pubkey: Pubkey::new_from_array(bytes),
+        })
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Signature, LedgerError> {
+        let mut payload = vec![1u8]; // number of signers
+        payload.extend(encode_path(&self.derivation_path));
+        payload.extend_from_slice(message);
+
+        let chunks: Vec<&[u8]> = payload.chunks(MAX_CHUNK).collect();
+        let last = chunks.len() - 1;
+        let mut response = Vec::new();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let mut p2 = 0;
+            if i > 0 {
+                p2 |= P2_EXTEND;
+            }
+            if i < last {
+                p2 |= P2_MORE;
+            }
+            let apdu = build_apdu(INS_SIGN_MESSAGE, P1_CONFIRM, p2, chunk);
+            response = check_status(self.transport.exchange(&apdu).await?)?;
+        }
+        let bytes: [u8; 64] = response.try_into().map_err(|_| LedgerError::MalformedResponse)?;
+        Ok(Signature::from(bytes))
+    }
+}
+
+impl<T: LedgerTransport> Signer for LedgerSigner<T> {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn sign_message<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        Box::pin(async move {
+            self.sign(message).await.map_err(|err| SignerError::Failed {
+                pubkey: self.pubkey,
+                source: Box::new(err),
+            })
+        })
+    }
+}
+
+fn encode_path(path: &[u32]) -> Vec<u8> {
+    let mut out = vec![path.len() as u8];
+    for component in path {
+        out.extend_from_slice(&component.to_be_bytes());
+    }
+    out
+}
+
+fn build_apdu(ins: u8, p1: u8, p2: u8, data: &[u8]) -> Vec<u8> {
+    let mut apdu = vec![CLA, ins, p1, p2, data.len() as u8];
+    apdu.extend_from_slice(data);
+    apdu
+}
+
+fn check_status(mut response: Vec<u8>) -> Result<Vec<u8>, LedgerError> {
+    if response.len() < 2 {
+        return Err(LedgerError::MalformedResponse);
+    }
+    let sw = u16::from_be_bytes([response[response.len() - 2], response[response.len() - 1]]);
+    response.truncate(response.len() - 2);
+    match sw {
+        SW_OK => Ok(response),
+        SW_USER_REJECTED => Err(LedgerError::UserRejected),
+        other => Err(LedgerError::Status(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockTransport {
+        sent: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl LedgerTransport for MockTransport {
+        fn exchange<'a>(&'a self, apdu: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, LedgerError>> {
+            self.sent.lock().unwrap().push(apdu.to_vec());
+            let mut response = match apdu[1] {
+                INS_GET_PUBKEY => vec![7u8; 32],
+                _ if apdu[3] & P2_MORE != 0 => vec![],
+                _ => vec![9u8; 64],
+            };
+            response.extend_from_slice(&SW_OK.to_be_bytes());
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    /// Long messages are split into chained APDUs and the final response is the signature.
+    #[tokio::test]
+    async fn signs_in_chunks() {
+        let signer = LedgerSigner::new(MockTransport::default(), 0, None).await.unwrap();
+        assert_eq!(signer.pubkey(), Pubkey::new_from_array([7u8; 32]));
+
+        let signature = signer.sign_message(&[0u8; 400]).await.unwrap();
+        assert_eq!(signature, Signature::from([9u8; 64]));
+
+        let sent = signer.transport.sent.lock().unwrap();
+        let p2s: Vec<u8> = sent[1..].iter().map(|apdu| apdu[3]).collect();
+        assert_eq!(p2s, vec![P2_MORE, P2_EXTEND]);
+    }
+}
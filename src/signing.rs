@@ -0,0 +1,129 @@
+//! HMAC request signing, see [`AethokitBuilder::signing_secret`](crate::AethokitBuilder::signing_secret).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderValue;
+use reqwest::Request;
+use sha2::Sha256;
+
+/// Header carrying the hex HMAC-SHA256 of a signed request.
+pub const REQUEST_SIGNATURE_HEADER: &str = "x-aethokit-request-signature";
+/// Header carrying the unix time, in seconds, a request was signed at.
+pub const REQUEST_TIMESTAMP_HEADER: &str = "x-aethokit-request-timestamp";
+
+/// Secret requests are signed with, kept out of `Debug` output.
+#[derive(Clone)]
+pub(crate) struct SigningSecret(Arc<[u8]>);
+
+impl SigningSecret {
+    pub(crate) fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self(secret.into().into())
+    }
+
+    /// Add the timestamp and signature headers to `request`, signing
+    /// `{timestamp}.{METHOD}.{path and query}.{body}`.
+    pub(crate) fn sign(&self, request: &mut Request) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .unwrap_or_default();
+        let url = request.url();
+        let target = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let signature = signature(
+            &self.0,
+            &timestamp.to_string(),
+            request.method().as_str(),
+            &target,
+            body,
+        );
+        let headers = request.headers_mut();
+        headers.insert(REQUEST_TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+        headers.insert(
+            REQUEST_SIGNATURE_HEADER,
+            HeaderValue::try_from(signature).expect("hex is a valid header value"),
+        );
+    }
+}
+
+impl fmt::Debug for SigningSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SigningSecret(..)")
+    }
+}
+
+fn signature(secret: &[u8], timestamp: &str, method: &str, target: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    for part in [timestamp.as_bytes(), method.as_bytes(), target.as_bytes()] {
+        mac.update(part);
+        mac.update(b".");
+    }
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures_util::future::BoxFuture;
+    use reqwest::Response;
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+    use crate::{AethokitError, Middleware, Next};
+
+    struct ExpectSignature;
+
+    impl Middleware for ExpectSignature {
+        fn handle<'a>(
+            &'a self,
+            request: Request,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<Response, AethokitError>> {
+            let header = |name| request.headers()[name].to_str().unwrap().to_string();
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default();
+            let expected = signature(
+                b"hunter2",
+                &header(REQUEST_TIMESTAMP_HEADER),
+                "POST",
+                "/sponsor-tx",
+                body,
+            );
+            assert_eq!(header(REQUEST_SIGNATURE_HEADER), expected);
+            assert!(request.headers().contains_key("x-gas-key"));
+            next.run(request)
+        }
+    }
+
+    /// Requests carry a signature over the timestamp, method, path and body, next to the gas key.
+    #[tokio::test]
+    async fn signs_requests() {
+        let backend = FakeBackend::new();
+        backend.on(
+            "POST",
+            "sponsor-tx",
+            FakeResponse::json(200, json!({ "hash": "sig" })),
+        );
+        let client = backend
+            .client()
+            .signing_secret("hunter2")
+            .middleware(Arc::new(ExpectSignature))
+            .build()
+            .unwrap();
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        assert!(!format!("{client:?}").contains("hunter2"));
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+
+use crate::AethokitError;
+
+type Flight<T> = Shared<BoxFuture<'static, Result<T, Arc<AethokitError>>>>;
+
+/// Coalesces concurrent identical requests so they share one in-flight call.
+///
+/// A call is only shared while it is running; once it completes, the next call with the
+/// same key starts a new one. If every caller is cancelled, the next caller with the key
+/// picks up the unfinished call instead.
+pub(crate) struct Group<T> {
+    inflight: Mutex<HashMap<String, Flight<T>>>,
+}
+
+impl<T> Default for Group<T> {
+    fn default() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Group<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inflight = self.inflight.lock().unwrap().len();
+        f.debug_struct("Group").field("inflight", &inflight).finish()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Group<T> {
+    /// Await the in-flight call for `key`, or start one with `call`.
+    ///
+    /// Callers that joined another's call receive its error as `AethokitError::Shared`.
+    pub(crate) async fn run<F>(&self, key: String, call: impl FnOnce() -> F) -> Result<T, AethokitError>
+    where
+        F: Future<Output = Result<T, AethokitError>> + Send + 'static,
+    {
+        let flight = {
+            let mut inflight = self.inflight.lock().unwrap();
+            inflight
+                .entry(key.clone())
+                .or_insert_with(|| call().map(|result| result.map_err(Arc::new)).boxed().shared())
+                .clone()
+        };
+        let result = flight.clone().await;
+        {
+            let mut inflight = self.inflight.lock().unwrap();
+            if inflight.get(&key).is_some_and(|current| current.ptr_eq(&flight)) {
+                inflight.remove(&key);
+            }
+        }
+        drop(flight);
+        result.map_err(|err| Arc::try_unwrap(err).unwrap_or_else(AethokitError::Shared))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Concurrent callers with the same key share one call; a later caller starts a new one.
+    #[tokio::test(start_paused = true)]
+    async fn coalesces_concurrent_calls() {
+        let group = Group::<u32>::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let call = || {
+            let calls = calls.clone();
+            move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok(7)
+            }
+        };
+
+        let results = futures_util::future::join_all(
+            (0..5).map(|_| group.run("gas".to_string(), call())),
+        )
+        .await;
+        assert!(results.iter().all(|result| matches!(result, Ok(7))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        group.run("gas".to_string(), call()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::str::FromStr;
+
+use base64::Engine as _;
+use serde::Serialize;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use crate::signer::{partial_sign, Signer};
+use crate::{offline, Aethokit, AethokitError};
+
+impl Aethokit {
+    /// Build a transaction from `instructions` with the gas tank as fee payer and
+    /// partially sign it with `signers`.
+    ///
+    /// The gas tank's signature slot is left empty for the backend to fill in.
+    pub async fn build_sponsored_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        recent_blockhash: Hash,
+    ) -> Result<Transaction, AethokitError> {
+        let fee_payer = self.gas_pubkey().await?;
+        let mut tx = Transaction::new_unsigned(offline::sponsored_message(&fee_payer, instructions, recent_blockhash));
+        partial_sign(&mut tx, signers).await?;
+        Ok(tx)
+    }
+
+    /// Build, partially sign and sponsor a transaction in one call. Returns the transaction hash.
+    ///
+    /// If a tank is depleted the transaction is rebuilt with the next fallback gas key's
+    /// tank as fee payer.
+    pub async fn sponsor_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        recent_blockhash: Hash,
+    ) -> Result<String, AethokitError> {
+        let resp = self
+            .with_failover(|client| async move {
+                let tx = client
+                    .build_sponsored_transaction(instructions, signers, recent_blockhash)
+                    .await?;
+                client.sponsor_signature_with_meta(encode_transaction(&tx)?).await
+            })
+            .await?;
+        Ok(resp.value)
+    }
+
+    /// Sponsor the transaction `build` creates from the latest blockhash, rebuilding it with
+    /// a fresh blockhash each time the backend reports the previous one expired, for up to
+    /// `max_attempts` submissions. Returns the transaction hash.
+    ///
+    /// `build` must sign the transaction again for every blockhash it is given, e.g. with
+    /// [`build_sponsored_transaction`](Self::build_sponsored_transaction). A transaction whose
+    /// blockhash expired can no longer land, so resubmitting never executes it twice.
+    ///
+    /// # Errors
+    /// - `BlockhashExpired` if the blockhash of the last attempt expired too
+    pub async fn sponsor_with_fresh_blockhash<F, Fut>(
+        &self,
+        max_attempts: u32,
+        mut build: F,
+    ) -> Result<String, AethokitError>
+    where
+        F: FnMut(Hash) -> Fut,
+        Fut: Future<Output = Result<Transaction, AethokitError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let tx = build(self.get_latest_blockhash().await?).await?;
+            match self.sponsor_signature(encode_transaction(&tx)?).await {
+                Err(AethokitError::BlockhashExpired) if attempt < max_attempts => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    pub(crate) async fn gas_pubkey(&self) -> Result<Pubkey, AethokitError> {
+        let address = self.get_gas_address().await?;
+        Pubkey::from_str(&address).map_err(|_| AethokitError::InvalidGasAddress(address))
+    }
+}
+
+/// Serialize a transaction into the base64 wire format expected by [`Aethokit::sponsor_tx`].
+pub fn encode_transaction(tx: &Transaction) -> Result<String, AethokitError> {
+    let mut out = String::new();
+    encode_transaction_into(tx, &mut out)?;
+    Ok(out)
+}
+
+/// [`encode_transaction`] for legacy or v0 transactions.
+pub fn encode_versioned_transaction(tx: &VersionedTransaction) -> Result<String, AethokitError> {
+    let mut out = String::new();
+    encode_into(tx, &mut out)?;
+    Ok(out)
+}
+
+/// [`encode_transaction`] into `out`, replacing its contents, so one buffer can be reused
+/// across a batch.
+///
+/// The serialized bytes go through a per-thread scratch buffer, so encoding a batch into
+/// one reused `out` doesn't allocate once the buffers have grown to the largest transaction.
+pub fn encode_transaction_into(tx: &Transaction, out: &mut String) -> Result<(), AethokitError> {
+    encode_into(tx, out)
+}
+
+fn encode_into<T: Serialize>(tx: &T, out: &mut String) -> Result<(), AethokitError> {
+    thread_local! {
+        static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    }
+    SCRATCH.with_borrow_mut(|bytes| {
+        bytes.clear();
+        bincode::serialize_into(&mut *bytes, tx)?;
+        out.clear();
+        base64::engine::general_purpose::STANDARD.encode_string(&bytes[..], out);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::signature::Keypair;
+
+    /// Streaming into a reused buffer gives the same payload as encoding the serialized bytes.
+    #[test]
+    fn encodes_into_reused_buffer() {
+        let signer = AccountMeta::new(Pubkey::new_unique(), true);
+        let ix = Instruction::new_with_bytes(Pubkey::new_unique(), &[7; 900], vec![signer]);
+        let tx = Transaction::new_with_payer(&[ix], Some(&Pubkey::new_unique()));
+        let expected = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+
+        let mut out = "stale".to_string();
+        encode_transaction_into(&tx, &mut out).unwrap();
+        assert_eq!(out, expected);
+        let capacity = out.capacity();
+        encode_transaction_into(&tx, &mut out).unwrap();
+        assert_eq!((out.as_str(), out.capacity()), (expected.as_str(), capacity));
+    }
+
+    /// An expired blockhash surfaces as `BlockhashExpired`, and the transaction is rebuilt
+    /// and signed again with the next blockhash until it is sponsored.
+    #[tokio::test]
+    async fn rebuilds_transactions_with_fresh_blockhash() {
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let blockhashes = [Hash::new_unique(), Hash::new_unique()];
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            for blockhash in blockhashes {
+                let (mut stream, _) = listener.accept().unwrap();
+                let _ = stream.read(&mut [0; 4096]).unwrap();
+                let body = json!({ "result": { "value": { "blockhash": blockhash.to_string() } } });
+                let body = body.to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        let backend = FakeBackend::new();
+        let expired = json!({ "code": "BLOCKHASH_EXPIRED", "message": "blockhash expired" });
+        backend
+            .on("POST", "sponsor-tx", FakeResponse::json(400, expired))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "5x" })));
+        let client = backend.client().rpc_or_network(rpc_url).build().unwrap();
+        let payer = Pubkey::new_unique();
+        let signer = Keypair::new();
+
+        let mut used = Vec::new();
+        let hash = client
+            .sponsor_with_fresh_blockhash(3, |blockhash| {
+                used.push(blockhash);
+                let ix = Instruction::new_with_bytes(
+                    Pubkey::new_unique(),
+                    &[],
+                    vec![AccountMeta::new(signer.pubkey(), true)],
+                );
+                let mut tx = Transaction::new_unsigned(offline::sponsored_message(&payer, &[ix], blockhash));
+                tx.partial_sign(&[&signer], blockhash);
+                async move { Ok(tx) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(hash, "5x");
+        assert_eq!(used, blockhashes);
+        assert_eq!(backend.requests().len(), 2);
+    }
+}
@@ -0,0 +1,297 @@
+//! Sponsoring [Solana Pay transaction requests], for point-of-sale flows where the merchant
+//! builds the transaction and the customer's app pays no fee.
+//!
+//! ```no_run
+//! # async fn run(client: &aethokit::Aethokit, customer: &solana_sdk::signature::Keypair) -> Result<(), aethokit::AethokitError> {
+//! let uri = "solana:https%3A%2F%2Fshop.example.com%2Fpay%3Forder%3D42";
+//! let payment = client.sponsor_solana_pay(uri, customer).await?;
+//! println!("paid in {}: {:?}", payment.signature, payment.message);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Solana Pay transaction requests]: https://docs.solanapay.com/spec#specification-transaction-request
+
+use base64::Engine as _;
+use percent_encoding::percent_decode_str;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Method, Request};
+use serde::Deserialize;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use url::Url;
+
+use crate::signer::{missing_signers, partial_sign, sign_slots, Signer, SignerError};
+use crate::{encode_transaction, encode_versioned_transaction, Aethokit, AethokitError};
+
+/// A sponsored Solana Pay payment, from [`Aethokit::sponsor_solana_pay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaPayPayment {
+    /// Transaction hash (signature)
+    pub signature: String,
+    /// Message the merchant asked to show the customer, if any
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionResponse {
+    transaction: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// The link of a `solana:` transaction request URI, percent-decoded if the spec's encoding
+/// of links with query parameters was used.
+///
+/// # Errors
+/// - `SolanaPay` if `uri` is not a `solana:` URI with an http(s) link, e.g. a transfer request
+pub fn transaction_request_link(uri: &str) -> Result<Url, AethokitError> {
+    let invalid = || AethokitError::SolanaPay(format!("`{uri}` is not a transaction request URI"));
+    let link = uri.trim().strip_prefix("solana:").ok_or_else(invalid)?;
+    let link = percent_decode_str(link)
+        .decode_utf8()
+        .map_err(|_| invalid())?;
+    let link = Url::parse(&link).map_err(|_| invalid())?;
+    match link.scheme() {
+        "https" | "http" => Ok(link),
+        _ => Err(invalid()),
+    }
+}
+
+impl Aethokit {
+    /// Fetch the transaction of a Solana Pay transaction request for `account`, sign it with
+    /// `account` and sponsor it.
+    ///
+    /// Merchants usually make the customer's account the fee payer. If no one has signed the
+    /// transaction yet it is recompiled with the gas tank as fee payer instead; if the
+    /// merchant already signed it, changing the fee payer would void that signature, so only
+    /// transactions already paying from the gas tank are accepted. The link is requested
+    /// without the gas key.
+    ///
+    /// # Errors
+    /// - `SolanaPay` if the link doesn't answer with a transaction, or the transaction can't
+    ///   be moved onto the gas tank
+    /// - `Signer(MissingSignature)` if the transaction needs a signature from someone else
+    pub async fn sponsor_solana_pay(
+        &self,
+        uri: &str,
+        account: &dyn Signer,
+    ) -> Result<SolanaPayPayment, AethokitError> {
+        let link = transaction_request_link(uri)?;
+        let body =
+            serde_json::to_vec(&serde_json::json!({ "account": account.pubkey().to_string() }))?;
+        let mut request = Request::new(Method::POST, link.clone());
+        let headers = request.headers_mut();
+        headers.insert(
+            CONTENT_TYPE,
+            "application/json".parse().expect("valid header value"),
+        );
+        headers.insert(
+            ACCEPT,
+            "application/json".parse().expect("valid header value"),
+        );
+        *request.body_mut() = Some(body.into());
+        let response = self.transport.0.execute(request).await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(AethokitError::SolanaPay(format!(
+                "{link} answered {status}: {text}"
+            )));
+        }
+        let response: TransactionResponse = serde_json::from_str(&text).map_err(|err| {
+            AethokitError::SolanaPay(format!("{link} answered with an invalid body: {err}"))
+        })?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(response.transaction.trim())
+            .map_err(|err| AethokitError::SolanaPay(format!("transaction is not base64: {err}")))?;
+        let tx: VersionedTransaction = bincode::deserialize(&bytes)?;
+        tx.sanitize()
+            .map_err(|err| AethokitError::SolanaPay(format!("malformed transaction: {err}")))?;
+
+        let encoded = self.sign_for_gas_tank(tx, account).await?;
+        let signature = self.sponsor_signature(encoded).await?;
+        Ok(SolanaPayPayment {
+            signature,
+            message: response.message,
+        })
+    }
+
+    /// Sign a merchant's transaction with `account`, first moving the fee onto the gas tank
+    /// if nobody has signed yet. Returns it encoded for sponsoring.
+    async fn sign_for_gas_tank(
+        &self,
+        tx: VersionedTransaction,
+        account: &dyn Signer,
+    ) -> Result<String, AethokitError> {
+        let gas = self.gas_pubkey().await?;
+        let keys = tx.message.static_account_keys();
+        if keys.first() == Some(&gas) {
+            let VersionedTransaction {
+                mut signatures,
+                message,
+            } = tx;
+            let required = usize::from(message.header().num_required_signatures);
+            let keys = &message.static_account_keys()[..required];
+            sign_slots(keys, &mut signatures, &message.serialize(), &[account]).await?;
+            if let Some((key, _)) = keys
+                .iter()
+                .zip(&signatures)
+                .skip(1)
+                .find(|(_, sig)| **sig == Signature::default())
+            {
+                return Err(SignerError::MissingSignature(*key).into());
+            }
+            return encode_versioned_transaction(&VersionedTransaction {
+                signatures,
+                message,
+            });
+        }
+
+        let VersionedMessage::Legacy(message) = &tx.message else {
+            return Err(AethokitError::SolanaPay(
+                "versioned transactions must already use the gas tank as fee payer".to_string(),
+            ));
+        };
+        if tx
+            .signatures
+            .iter()
+            .any(|signature| *signature != Signature::default())
+        {
+            return Err(AethokitError::SolanaPay(
+                "the merchant signed the transaction with another fee payer".to_string(),
+            ));
+        }
+        let mut tx = Transaction::new_unsigned(Message::new_with_blockhash(
+            &decompile(message),
+            Some(&gas),
+            &message.recent_blockhash,
+        ));
+        partial_sign(&mut tx, &[account]).await?;
+        if let Some(&missing) = missing_signers(&tx)?.first() {
+            return Err(SignerError::MissingSignature(missing).into());
+        }
+        encode_transaction(&tx)
+    }
+}
+
+/// The instructions of `message`, with each account's signer and writable flags.
+fn decompile(message: &Message) -> Vec<Instruction> {
+    let meta = |index: u8| {
+        let index = usize::from(index);
+        let pubkey: Pubkey = message.account_keys[index];
+        match message.is_maybe_writable(index, None) {
+            true => AccountMeta::new(pubkey, message.is_signer(index)),
+            false => AccountMeta::new_readonly(pubkey, message.is_signer(index)),
+        }
+    };
+    message
+        .instructions
+        .iter()
+        .map(|ix| Instruction {
+            program_id: message.account_keys[usize::from(ix.program_id_index)],
+            accounts: ix.accounts.iter().copied().map(meta).collect(),
+            data: ix.data.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::Keypair;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Links are decoded from both URI forms; other `solana:` URIs are refused.
+    #[test]
+    fn parses_transaction_request_links() {
+        let link =
+            transaction_request_link("solana:https%3A%2F%2Fshop.example.com%2Fpay%3Forder%3D42")
+                .unwrap();
+        assert_eq!(link.as_str(), "https://shop.example.com/pay?order=42");
+        assert!(transaction_request_link("solana:https://shop.example.com/pay").is_ok());
+        assert!(transaction_request_link(
+            "solana:mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN?amount=1"
+        )
+        .is_err());
+    }
+
+    /// The merchant's unsigned transaction is moved onto the gas tank and signed by the
+    /// customer before it is sponsored.
+    #[tokio::test]
+    async fn sponsors_transaction_request() {
+        let customer = Keypair::new();
+        let merchant = Pubkey::new_unique();
+        let gas = Signer::pubkey(&Keypair::new());
+        let transfer = solana_system_interface::instruction::transfer(
+            &Signer::pubkey(&customer),
+            &merchant,
+            1_000,
+        );
+        let tx = Transaction::new_unsigned(Message::new_with_blockhash(
+            &[transfer],
+            Some(&Signer::pubkey(&customer)),
+            &Hash::new_unique(),
+        ));
+        let body = json!({ "transaction": encode_transaction(&tx).unwrap(), "message": "Thanks!" })
+            .to_string();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("solana:http://{}/pay", listener.local_addr().unwrap());
+        let merchant_server = std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0; 8192];
+            let read = stream.read(&mut request).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+        let backend = FakeBackend::new();
+        backend
+            .on(
+                "GET",
+                "get-gas-address",
+                FakeResponse::json(200, json!({ "gasAddress": gas.to_string() })),
+            )
+            .on(
+                "POST",
+                "sponsor-tx",
+                FakeResponse::json(200, json!({ "hash": "sig" })),
+            );
+        let client = backend.client().build().unwrap();
+
+        let payment = client.sponsor_solana_pay(&uri, &customer).await.unwrap();
+        assert_eq!(
+            payment,
+            SolanaPayPayment {
+                signature: "sig".to_string(),
+                message: Some("Thanks!".to_string())
+            }
+        );
+        let request = merchant_server.join().unwrap();
+        assert!(request.contains(&Signer::pubkey(&customer).to_string()));
+        assert!(!request.to_ascii_lowercase().contains("x-gas-key"));
+
+        let sponsored = backend.requests()[1].body.as_ref().unwrap()["transaction"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(sponsored)
+            .unwrap();
+        let sponsored: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(sponsored.message.account_keys[0], gas);
+        assert!(missing_signers(&sponsored).unwrap().is_empty());
+    }
+}
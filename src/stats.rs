@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::endpoint::endpoint;
+use crate::{Aethokit, AethokitError, UsageRange};
+
+/// Width of the buckets of [`Aethokit::get_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Hourly,
+    Daily,
+}
+
+/// Sponsorship activity over a range, bucketed for charting.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SponsorshipStats {
+    pub granularity: Granularity,
+    /// Buckets in time order, including empty ones
+    pub buckets: Vec<StatsBucket>,
+}
+
+/// Sponsorships in one hour or day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct StatsBucket {
+    /// Start of the bucket
+    pub start: DateTime<Utc>,
+    /// Sponsored transactions
+    pub count: u64,
+    /// Fees paid by the gas tank, in lamports
+    #[serde(rename = "lamportsSpent", deserialize_with = "crate::lenient::u64")]
+    pub lamports_spent: u64,
+}
+
+impl SponsorshipStats {
+    /// Sponsored transactions over the whole range.
+    pub fn total_count(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.count).sum()
+    }
+
+    /// Fees paid over the whole range, in lamports.
+    pub fn total_lamports_spent(&self) -> u64 {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.lamports_spent)
+            .sum()
+    }
+}
+
+impl Aethokit {
+    /// Sponsorship counts and fees spent in `range`, per hour or day, for dashboards that
+    /// would otherwise aggregate the raw history.
+    pub async fn get_stats(
+        &self,
+        granularity: Granularity,
+        range: UsageRange,
+    ) -> Result<SponsorshipStats, AethokitError> {
+        let query = json!({ "granularity": granularity, "from": range.from, "to": range.to });
+        let path = endpoint("stats", &[], &query)?;
+        self.make_request::<(), _>(&path, Method::GET, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Buckets parse into the typed series and add up to the totals.
+    #[tokio::test]
+    async fn fetches_bucketed_stats() {
+        let backend = FakeBackend::new();
+        let body = json!({
+            "granularity": "daily",
+            "buckets": [
+                { "start": "2024-05-01T00:00:00Z", "count": 3, "lamportsSpent": 15000 },
+                { "start": "2024-05-02T00:00:00Z", "count": 0, "lamportsSpent": 0 },
+            ],
+        });
+        backend.on("GET", "stats", FakeResponse::json(200, body));
+        let client = backend.client().build().unwrap();
+
+        let range = UsageRange {
+            from: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            to: Utc.with_ymd_and_hms(2024, 5, 3, 0, 0, 0).unwrap(),
+        };
+        let stats = client.get_stats(Granularity::Daily, range).await.unwrap();
+        assert_eq!(
+            stats.buckets[1].start,
+            Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            (stats.total_count(), stats.total_lamports_spent()),
+            (3, 15000)
+        );
+        assert!(backend.requests()[0]
+            .path
+            .starts_with("stats?from=2024-05-01T00%3A00%3A00Z&granularity=daily&to="));
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::endpoint::endpoint;
+use crate::{Aethokit, AethokitError};
+
+/// Where a sponsored transaction is, as tracked by the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Submitted but not confirmed yet
+    Pending,
+    Confirmed { slot: u64 },
+    Finalized,
+    Failed {
+        error: String,
+        /// The RPC node's `TransactionError`, as JSON, if the backend passed it on; see
+        /// [`TxStatus::transaction_error`] with the `solana` feature
+        transaction_error: Option<Value>,
+    },
+    /// The backend has no record of the signature
+    NotFound,
+}
+
+impl fmt::Display for TxStatus {
+    /// One-line summary, e.g. `confirmed at slot 1234` or `failed: blockhash not found`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxStatus::Pending => f.write_str("pending"),
+            TxStatus::Confirmed { slot } => write!(f, "confirmed at slot {slot}"),
+            TxStatus::Finalized => f.write_str("finalized"),
+            TxStatus::Failed { error, .. } => write!(f, "failed: {error}"),
+            TxStatus::NotFound => f.write_str("not found"),
+        }
+    }
+}
+
+#[cfg(feature = "solana")]
+impl TxStatus {
+    /// Why the transaction failed on-chain, decoded into Solana's error type, e.g. to retry
+    /// a swap that hit its slippage limit. `None` unless the transaction failed and the
+    /// backend reported the error in a form Solana's type knows.
+    pub fn transaction_error(&self) -> Option<solana_sdk::transaction::TransactionError> {
+        match self {
+            TxStatus::Failed { transaction_error: Some(err), .. } => serde_json::from_value(err.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Index of the failing instruction and the program's custom error code, for failures
+    /// raised by a program with `ProgramError::Custom` (e.g. Anchor error codes).
+    pub fn custom_error(&self) -> Option<(u8, u32)> {
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+
+        match self.transaction_error()? {
+            TransactionError::InstructionError(index, InstructionError::Custom(code)) => Some((index, code)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum StatusResponse {
+    Pending,
+    Confirmed { slot: u64 },
+    Finalized,
+    Failed {
+        #[serde(default)]
+        error: String,
+        #[serde(default, rename = "transactionError", alias = "err")]
+        transaction_error: Option<Value>,
+    },
+}
+
+impl Aethokit {
+    /// Ask the backend whether a sponsored transaction landed, without an RPC endpoint of
+    /// your own.
+    ///
+    /// # Errors
+    /// - `UnexpectedStatus` for errors other than `404`, which maps to [`TxStatus::NotFound`]
+    pub async fn get_tx_status(&self, signature: &str) -> Result<TxStatus, AethokitError> {
+        let path = endpoint("tx-status", &[], &json!({ "signature": signature }))?;
+        match self.make_request::<(), StatusResponse>(&path, Method::GET, None).await {
+            Ok(StatusResponse::Pending) => Ok(TxStatus::Pending),
+            Ok(StatusResponse::Confirmed { slot }) => Ok(TxStatus::Confirmed { slot }),
+            Ok(StatusResponse::Finalized) => Ok(TxStatus::Finalized),
+            Ok(StatusResponse::Failed { error, transaction_error }) => Ok(TxStatus::Failed {
+                error,
+                transaction_error: transaction_error.filter(|err| !err.is_null()),
+            }),
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(TxStatus::NotFound),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+
+    /// Statuses map to the typed enum and unknown signatures come back as `NotFound`.
+    #[tokio::test]
+    async fn looks_up_status() {
+        let backend = FakeBackend::new();
+        let confirmed = json!({ "status": "confirmed", "slot": 42 });
+        backend.on("GET", "tx-status", FakeResponse::json(200, confirmed));
+        backend.on("GET", "tx-status", FakeResponse::json(404, json!({ "error": "not found" })));
+        let client = backend.client().build().unwrap();
+
+        assert_eq!(client.get_tx_status("abc").await.unwrap(), TxStatus::Confirmed { slot: 42 });
+        assert_eq!(client.get_tx_status("missing").await.unwrap(), TxStatus::NotFound);
+        assert_eq!(backend.requests()[0].path, "tx-status?signature=abc");
+    }
+
+    /// Statuses render as a one-line summary, failures with the backend's reason.
+    #[test]
+    fn displays_tx_status() {
+        assert_eq!(TxStatus::Confirmed { slot: 1234 }.to_string(), "confirmed at slot 1234");
+        assert_eq!(TxStatus::NotFound.to_string(), "not found");
+        let failed = TxStatus::Failed {
+            error: "blockhash not found".to_string(),
+            transaction_error: None,
+        };
+        assert_eq!(failed.to_string(), "failed: blockhash not found");
+    }
+
+    /// A program's custom error is decoded with the index of the failing instruction.
+    #[cfg(feature = "solana")]
+    #[tokio::test]
+    async fn decodes_transaction_error() {
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+
+        let backend = FakeBackend::new();
+        let failed = json!({
+            "status": "failed",
+            "error": "custom program error: 0x1771",
+            "transactionError": { "InstructionError": [2, { "Custom": 6001 }] },
+        });
+        backend.on("GET", "tx-status", FakeResponse::json(200, failed));
+        let client = backend.client().build().unwrap();
+
+        let status = client.get_tx_status("abc").await.unwrap();
+        assert_eq!(
+            status.transaction_error(),
+            Some(TransactionError::InstructionError(2, InstructionError::Custom(6001)))
+        );
+        assert_eq!(status.custom_error(), Some((2, 6001)));
+        assert_eq!(TxStatus::Pending.custom_error(), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_util::{Sink, Stream, StreamExt};
+
+use crate::{Aethokit, AethokitError};
+
+/// A transaction to sponsor through a [`SponsorSink`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SponsorJob {
+    /// Caller-chosen identifier, echoed back in the [`JobResult`]
+    pub id: String,
+    /// Serialized transaction string
+    pub transaction: String,
+}
+
+/// Outcome of a [`SponsorJob`].
+#[derive(Debug)]
+pub struct JobResult {
+    pub id: String,
+    /// Transaction hash, or why sponsoring failed
+    pub result: Result<String, AethokitError>,
+}
+
+/// Submission side of [`Aethokit::sponsor_sink`].
+///
+/// Sending waits while `concurrency` jobs are queued, so a producer feeding the
+/// sink slows down to the rate the API accepts transactions.
+#[derive(Debug, Clone)]
+pub struct SponsorSink {
+    jobs: mpsc::Sender<SponsorJob>,
+}
+
+impl Sink<SponsorJob> for SponsorSink {
+    type Error = AethokitError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.jobs.poll_ready(cx).map_err(|_| AethokitError::SinkClosed)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, job: SponsorJob) -> Result<(), Self::Error> {
+        self.jobs.start_send(job).map_err(|_| AethokitError::SinkClosed)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.jobs)
+            .poll_flush(cx)
+            .map_err(|_| AethokitError::SinkClosed)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.jobs)
+            .poll_close(cx)
+            .map_err(|_| AethokitError::SinkClosed)
+    }
+}
+
+impl Aethokit {
+    /// Sponsor transactions pushed into a [`Sink`], up to `concurrency` at a time.
+    ///
+    /// Jobs are submitted as the returned stream is polled, which yields one
+    /// [`JobResult`] per job in completion order. The stream ends once every sink
+    /// handle is closed or dropped and the remaining jobs have completed.
+    ///
+    /// ```no_run
+    /// # use futures_util::{SinkExt, StreamExt};
+    /// # async fn run(client: &aethokit::Aethokit, txs: Vec<String>) -> Result<(), aethokit::AethokitError> {
+    /// use aethokit::SponsorJob;
+    ///
+    /// let (mut sink, results) = client.sponsor_sink(8);
+    /// let producer = async move {
+    ///     for (i, transaction) in txs.into_iter().enumerate() {
+    ///         sink.send(SponsorJob { id: i.to_string(), transaction }).await?;
+    ///     }
+    ///     Ok::<_, aethokit::AethokitError>(())
+    /// };
+    /// let consumer = results.for_each(|job| async move { println!("{}: {:?}", job.id, job.result) });
+    /// let (sent, ()) = futures_util::join!(producer, consumer);
+    /// sent?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// If `concurrency` is zero.
+    pub fn sponsor_sink(&self, concurrency: usize) -> (SponsorSink, impl Stream<Item = JobResult>) {
+        assert!(concurrency > 0, "concurrency must be at least 1");
+        let (jobs, queue) = mpsc::channel(concurrency);
+        let client = self.clone();
+        let results = queue
+            .map(move |job: SponsorJob| {
+                let client = client.clone();
+                async move {
+                    JobResult {
+                        result: client.sponsor_signature(job.transaction).await,
+                        id: job.id,
+                    }
+                }
+            })
+            .buffer_unordered(concurrency);
+        (SponsorSink { jobs }, results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures_util::SinkExt;
+
+    /// Every job sent into the sink comes out of the stream with its id, which ends once the sink closes.
+    #[tokio::test]
+    async fn reports_every_job() {
+        // nothing listens on port 1, so each submission fails fast
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .base_url("http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let (mut sink, results) = client.sponsor_sink(2);
+        let producer = async move {
+            for id in ["a", "b", "c"] {
+                let job = SponsorJob {
+                    id: id.to_string(),
+                    transaction: "tx".to_string(),
+                };
+                sink.send(job).await.unwrap();
+            }
+        };
+        let (_, results) = futures_util::join!(producer, results.collect::<Vec<_>>());
+
+        let mut ids: Vec<_> = results.iter().map(|job| job.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!(results.iter().all(|job| job.result.is_err()));
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use reqwest::Method;
+
+use crate::{AethokitError, Responded};
+
+/// Which requests are recorded as `tracing` events (target `aethokit`).
+///
+/// The decision is made once the outcome is known, so failures and slow calls can be
+/// kept in full while only a fraction of the fast, successful ones is recorded.
+///
+/// ```
+/// # use std::time::Duration;
+/// let sampling = aethokit::TraceSampling {
+///     rate: 0.01,
+///     slow: Some(Duration::from_millis(500)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceSampling {
+    /// Fraction of fast, successful requests to record, from `0.0` to `1.0`
+    pub rate: f64,
+    /// Record every failed request regardless of `rate`
+    pub errors: bool,
+    /// Record every request slower than this regardless of `rate`
+    pub slow: Option<Duration>,
+}
+
+impl Default for TraceSampling {
+    /// Record everything.
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            errors: true,
+            slow: None,
+        }
+    }
+}
+
+/// Applies a [`TraceSampling`] to the requests of a client and its clones.
+#[derive(Debug, Default)]
+pub(crate) struct Sampler {
+    sampling: TraceSampling,
+    /// Fast successful requests seen so far, used to sample exactly `rate` of them
+    seen: AtomicU64,
+}
+
+impl Sampler {
+    pub(crate) fn new(sampling: TraceSampling) -> Self {
+        Self {
+            sampling,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a finished request if the sampling configuration keeps it.
+    pub(crate) fn record<T>(
+        &self,
+        method: &Method,
+        path: &str,
+        latency: Duration,
+        result: &Result<Responded<T>, AethokitError>,
+        class: &str,
+    ) {
+        let latency_ms = latency.as_millis() as u64;
+        match result {
+            Err(err) if self.sampling.errors => tracing::warn!(
+                target: "aethokit",
+                %method,
+                path,
+                latency_ms,
+                status = err.status().map(|status| status.as_u16()),
+                class,
+                error = %err,
+                "request failed"
+            ),
+            Ok(resp) if self.is_slow(latency) || self.sample() => tracing::info!(
+                target: "aethokit",
+                %method,
+                path,
+                latency_ms,
+                status = resp.meta.status.as_u16(),
+                class,
+                request_id = resp.meta.request_id.as_deref(),
+                "request completed"
+            ),
+            _ => {}
+        }
+    }
+
+    fn is_slow(&self, latency: Duration) -> bool {
+        self.sampling.slow.is_some_and(|slow| latency >= slow)
+    }
+
+    /// Keep `rate` of the calls, spread evenly: the n-th call is kept when
+    /// `floor(n * rate)` advances.
+    fn sample(&self) -> bool {
+        let rate = self.sampling.rate.clamp(0.0, 1.0);
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A rate keeps that exact fraction of calls, spread across the sequence.
+    #[test]
+    fn samples_at_rate() {
+        let sampler = Sampler::new(TraceSampling {
+            rate: 0.25,
+            ..Default::default()
+        });
+        let kept: Vec<bool> = (0..8).map(|_| sampler.sample()).collect();
+        assert_eq!(kept, [false, false, false, true, false, false, false, true]);
+
+        let none = Sampler::new(TraceSampling {
+            rate: 0.0,
+            ..Default::default()
+        });
+        assert!((0..100).all(|_| !none.sample()));
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::{Aethokit, AethokitError, RetryPolicy};
+
+/// Spending limits enforced by the client before anything is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Guardrails {
+    /// Sponsorships the tenant may make over the lifetime of the client; failed
+    /// submissions are not counted
+    pub max_sponsorships: Option<u64>,
+}
+
+/// A tenant's settings in a [`Tenants`] registry. Unset options fall back to the base client's.
+#[derive(Debug, Clone, Default)]
+pub struct TenantConfig {
+    pub gas_key: String,
+    pub rpc_or_network: Option<String>,
+    pub retry: Option<RetryPolicy>,
+    pub guardrails: Guardrails,
+}
+
+/// Clients for many tenants sharing one connection pool, each with its own gas key,
+/// network, retry policy and guardrails.
+///
+/// ```no_run
+/// # fn run(base: aethokit::Aethokit) -> Result<(), aethokit::AethokitError> {
+/// use aethokit::{Guardrails, TenantConfig, Tenants};
+///
+/// let mut tenants = Tenants::new(base);
+/// tenants.insert("acme", TenantConfig {
+///     gas_key: "acme-gas-key".to_string(),
+///     rpc_or_network: Some("mainnet".to_string()),
+///     guardrails: Guardrails { max_sponsorships: Some(10_000) },
+///     ..Default::default()
+/// })?;
+/// let client = tenants.get("acme").expect("registered above");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tenants {
+    base: Aethokit,
+    clients: HashMap<String, Aethokit>,
+}
+
+impl Tenants {
+    /// Registry whose tenants inherit `base`'s HTTP settings, headers and base URL.
+    pub fn new(base: Aethokit) -> Self {
+        Self {
+            base,
+            clients: HashMap::new(),
+        }
+    }
+
+    /// Register or replace a tenant.
+    ///
+    /// # Errors
+    /// - `MissingGasKey` if the tenant's gas key is empty
+    pub fn insert(&mut self, tenant: impl Into<String>, config: TenantConfig) -> Result<(), AethokitError> {
+        if config.gas_key.trim().is_empty() {
+            return Err(AethokitError::MissingGasKey);
+        }
+        let mut client = self.base.with_gas_key(config.gas_key);
+        client.fallback_keys = Arc::default();
+        if config.rpc_or_network.is_some() {
+            client.rpc_or_network = config.rpc_or_network;
+        }
+        if config.retry.is_some() {
+            client.retry = config.retry;
+        }
+        client.budget = config
+            .guardrails
+            .max_sponsorships
+            .map(|max| Arc::new(Budget::new(max)));
+        self.clients.insert(tenant.into(), client);
+        Ok(())
+    }
+
+    /// The client for `tenant`, if registered.
+    pub fn get(&self, tenant: &str) -> Option<&Aethokit> {
+        self.clients.get(tenant)
+    }
+
+    /// Unregister a tenant, returning its client.
+    pub fn remove(&mut self, tenant: &str) -> Option<Aethokit> {
+        self.clients.remove(tenant)
+    }
+}
+
+/// Remaining sponsorships under [`Guardrails::max_sponsorships`], shared between clones.
+#[derive(Debug)]
+pub(crate) struct Budget {
+    remaining: AtomicU64,
+}
+
+impl Budget {
+    fn new(max: u64) -> Self {
+        Self {
+            remaining: AtomicU64::new(max),
+        }
+    }
+
+    /// Reserve one sponsorship, `false` if none are left.
+    pub(crate) fn try_take(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| left.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Return a reservation whose submission failed.
+    pub(crate) fn refund(&self) {
+        self.remaining.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tenant overrides apply on top of the base client, and the budget stops at zero.
+    #[test]
+    fn applies_tenant_overrides() {
+        let base = Aethokit::builder()
+            .gas_key("base")
+            .rpc_or_network("devnet")
+            .build()
+            .unwrap();
+        let mut tenants = Tenants::new(base);
+        let config = TenantConfig {
+            gas_key: "acme".to_string(),
+            rpc_or_network: Some("mainnet".to_string()),
+            guardrails: Guardrails {
+                max_sponsorships: Some(1),
+            },
+            ..Default::default()
+        };
+        tenants.insert("acme", config).unwrap();
+
+        let acme = tenants.get("acme").unwrap();
+        assert_eq!(&*acme.gas_key.get(), "acme");
+        assert_eq!(acme.rpc_or_network.as_deref(), Some("mainnet"));
+        let budget = acme.budget.as_ref().unwrap();
+        assert!(budget.try_take());
+        assert!(!budget.try_take());
+        budget.refund();
+        assert!(budget.try_take());
+        assert!(tenants.get("globex").is_none());
+    }
+}
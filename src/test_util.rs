@@ -0,0 +1,477 @@
+//! In-process fake of the Aethokit API, for running end-to-end tests offline.
+//!
+//! [`FakeBackend`] answers the client's requests from canned responses instead of the
+//! network, so tests exercise the real request, retry and error handling paths.
+//!
+//! ```
+//! # async fn run() -> Result<(), aethokit::AethokitError> {
+//! use aethokit::test_util::{FakeBackend, FakeResponse};
+//! use serde_json::json;
+//!
+//! let backend = FakeBackend::new();
+//! backend.on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "Gas111" })));
+//! let client = backend.client().build()?;
+//! assert_eq!(client.get_gas_address().await?, "Gas111");
+//! assert_eq!(backend.requests()[0].path, "get-gas-address");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Responses can also come from a [`Cassette`] of real interactions captured once with a
+//! [`Recorder`], so sponsorship flows can be tested in CI without live keys or network:
+//!
+//! ```no_run
+//! # async fn run(tx: String) -> Result<(), Box<dyn std::error::Error>> {
+//! use std::sync::Arc;
+//! use aethokit::test_util::{Cassette, FakeBackend, Recorder};
+//!
+//! // once, with a real key
+//! let recorder = Arc::new(Recorder::new());
+//! let client = aethokit::Aethokit::builder()
+//!     .gas_key(std::env::var("AETHOKIT_GAS_KEY")?)
+//!     .middleware(recorder.clone())
+//!     .build()?;
+//! client.sponsor_tx(tx.clone()).await?;
+//! recorder.cassette().save("tests/cassettes/sponsor.json")?;
+//!
+//! // in CI
+//! let backend = FakeBackend::replay(&Cassette::load("tests/cassettes/sponsor.json")?);
+//! backend.client().build()?.sponsor_tx(tx).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures_util::future::BoxFuture;
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Aethokit, AethokitBuilder, AethokitError, Clock, Middleware, Next, Sleeper};
+
+/// Gas key of clients built with [`FakeBackend::client`].
+pub const FAKE_GAS_KEY: &str = "test-gas-key";
+
+/// A canned response for [`FakeBackend::on`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FakeResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl FakeResponse {
+    /// A response with a JSON body.
+    pub fn json(status: u16, body: Value) -> Self {
+        Self::text(status, body.to_string()).header("content-type", "application/json")
+    }
+
+    /// A response with a raw body, e.g. malformed JSON.
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Add a response header, e.g. `retry-after`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn into_response(self) -> Result<Response, AethokitError> {
+        let mut response = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            response = response.header(name, value);
+        }
+        let response = response
+            .body(self.body)
+            .map_err(|err| AethokitError::InvalidHeader(err.to_string()))?;
+        Ok(Response::from(response))
+    }
+}
+
+/// A request received by a [`FakeBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path relative to the client's base URL, with the query string
+    pub path: String,
+    /// The `x-gas-key` header, if any
+    pub gas_key: Option<String>,
+    /// The [`IDEMPOTENCY_KEY_HEADER`](crate::IDEMPOTENCY_KEY_HEADER), if any
+    pub idempotency_key: Option<String>,
+    /// JSON body, if the request had one
+    pub body: Option<Value>,
+}
+
+#[derive(Debug)]
+struct Route {
+    method: String,
+    path: String,
+    /// Served in order; the last one repeats
+    responses: VecDeque<FakeResponse>,
+}
+
+/// Fake Aethokit backend, plugged into a client as [`Middleware`].
+///
+/// Requests with no matching route get a `404` JSON error.
+#[derive(Debug, Default)]
+pub struct FakeBackend {
+    routes: Mutex<Vec<Route>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl FakeBackend {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Answer `method path` (path relative to the base URL, query string ignored) with
+    /// `response`. Responses added for the same route are served in order, the last one
+    /// for every request after that.
+    pub fn on(&self, method: &str, path: &str, response: FakeResponse) -> &Self {
+        let mut routes = self.routes.lock().unwrap();
+        match routes
+            .iter_mut()
+            .find(|route| route.method.eq_ignore_ascii_case(method) && route.path == path)
+        {
+            Some(route) => route.responses.push_back(response),
+            None => routes.push(Route {
+                method: method.to_ascii_uppercase(),
+                path: path.to_string(),
+                responses: VecDeque::from([response]),
+            }),
+        }
+        self
+    }
+
+    /// Requests received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Backend answering each request of `cassette` with its recorded response, in order.
+    ///
+    /// Paths are matched as recorded, so replay with the base URL the cassette was recorded
+    /// against.
+    pub fn replay(cassette: &Cassette) -> Arc<Self> {
+        let backend = Self::new();
+        for interaction in &cassette.interactions {
+            let path = interaction.path.split('?').next().unwrap_or_default();
+            backend.on(&interaction.method, path, interaction.response.clone());
+        }
+        backend
+    }
+
+    /// Builder for a client talking to this backend, with [`FAKE_GAS_KEY`] as gas key.
+    pub fn client(self: &Arc<Self>) -> AethokitBuilder {
+        Aethokit::builder()
+            .gas_key(FAKE_GAS_KEY)
+            .middleware(self.clone())
+    }
+
+    fn respond(&self, request: &Request) -> FakeResponse {
+        let url = request.url();
+        let path = url.path().trim_start_matches('/');
+        // strip the base URL's path, e.g. `api/`, by matching routes as suffixes
+        let mut routes = self.routes.lock().unwrap();
+        let route = routes.iter_mut().find(|route| {
+            route.method == request.method().as_str()
+                && (path == route.path || path.ends_with(&format!("/{}", route.path)))
+        });
+        let relative = match &route {
+            Some(route) => route.path.clone(),
+            None => path.to_string(),
+        };
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice(bytes).ok());
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: request.method().to_string(),
+            path: match url.query() {
+                Some(query) => format!("{relative}?{query}"),
+                None => relative,
+            },
+            gas_key: request
+                .headers()
+                .get("x-gas-key")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            idempotency_key: request
+                .headers()
+                .get(crate::IDEMPOTENCY_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            body,
+        });
+        match route {
+            Some(route) if route.responses.len() > 1 => route.responses.pop_front().unwrap(),
+            Some(route) => route.responses[0].clone(),
+            None => FakeResponse::json(
+                404,
+                serde_json::json!({ "error": format!("no fake response for {} {path}", request.method()) }),
+            ),
+        }
+    }
+}
+
+/// A request and the response it got, as stored in a [`Cassette`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// URL path without the leading slash, with the query string
+    pub path: String,
+    /// JSON body of the request, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    pub response: FakeResponse,
+}
+
+/// Recorded HTTP interactions, stored as JSON, see [`Recorder`] and [`FakeBackend::replay`].
+///
+/// Request headers, and with them the gas key, are not recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+}
+
+/// [`Middleware`] passing requests on and recording each with its response into a
+/// [`Cassette`]. Add it before any middleware that should be replayed.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        Cassette {
+            interactions: self.interactions.lock().unwrap().clone(),
+        }
+    }
+}
+
+impl Middleware for Recorder {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+        let url = request.url();
+        let path = url.path().trim_start_matches('/');
+        let path = match url.query() {
+            Some(query) => format!("{path}?{query}"),
+            None => path.to_string(),
+        };
+        let method = request.method().to_string();
+        let request_body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice(bytes).ok());
+        Box::pin(async move {
+            let response = next.run(request).await?;
+            let mut fake = FakeResponse::text(response.status().as_u16(), "");
+            for (name, value) in response.headers() {
+                if let Ok(value) = value.to_str() {
+                    fake = fake.header(name.as_str(), value);
+                }
+            }
+            fake.body = response.text().await?;
+            self.interactions.lock().unwrap().push(Interaction {
+                method,
+                path,
+                request_body,
+                response: fake.clone(),
+            });
+            fake.into_response()
+        })
+    }
+}
+
+impl Middleware for FakeBackend {
+    fn handle<'a>(&'a self, request: Request, _next: Next<'a>) -> BoxFuture<'a, Result<Response, AethokitError>> {
+        let fake = self.respond(&request);
+        Box::pin(async move { fake.into_response() })
+    }
+}
+
+/// [`Clock`] and [`Sleeper`] whose time only moves when slept on or advanced, so retries,
+/// polling and cache expiry can be tested without waiting. Clones share the same time.
+///
+/// Sleeps complete immediately, moving the clock forward by their duration.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::default(),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// Time passed since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+}
+
+impl Sleeper for MockClock {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.advance(duration);
+        Box::pin(futures_util::future::ready(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::RetryPolicy;
+
+    /// Success, error envelopes, rate limiting and malformed bodies surface as documented.
+    #[tokio::test]
+    async fn covers_response_kinds() {
+        let backend = FakeBackend::new();
+        backend
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })))
+            .on("GET", "get-gas-address", FakeResponse::text(200, "{not json"))
+            .on(
+                "GET",
+                "get-sponsored-tx",
+                FakeResponse::json(429, json!({ "error": "slow down" })).header("retry-after", "2"),
+            );
+        let client = backend.client().build().unwrap();
+
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        let sent = &backend.requests()[0];
+        assert_eq!(sent.path, "sponsor-tx");
+        assert_eq!(sent.gas_key.as_deref(), Some(FAKE_GAS_KEY));
+        assert_eq!(sent.body.as_ref().unwrap()["transaction"], "tx");
+
+        assert!(matches!(client.get_gas_address().await, Err(AethokitError::Serde(_))));
+
+        let err = client.list_sponsored_txs(Default::default()).await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        assert!(err.to_string().contains("no fake response for GET"));
+
+        match client.get_sponsored_tx("sig", crate::Consistency::Eventual).await {
+            Err(AethokitError::RateLimited { retry_after, body, .. }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(2)));
+                assert!(body.contains("slow down"));
+            }
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+    }
+
+    /// Queued responses are served in order, so retries can be exercised.
+    #[tokio::test(start_paused = true)]
+    async fn serves_queued_responses() {
+        let backend = FakeBackend::new();
+        backend
+            .on("GET", "get-gas-address", FakeResponse::json(503, json!({ "error": "down" })))
+            .on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "Gas111" })));
+        let client = backend
+            .client()
+            .retry_policy(RetryPolicy::default())
+            .build()
+            .unwrap();
+        assert_eq!(client.get_gas_address().await.unwrap(), "Gas111");
+        assert_eq!(backend.requests().len(), 2);
+    }
+
+    /// With a mock clock, retry backoff and cache expiry move its time instead of waiting.
+    #[tokio::test]
+    async fn runs_on_mock_clock() {
+        let backend = FakeBackend::new();
+        backend
+            .on("GET", "get-gas-address", FakeResponse::json(503, json!({ "error": "down" })))
+            .on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "Gas111" })));
+        let clock = MockClock::new();
+        let client = backend
+            .client()
+            .retry_policy(RetryPolicy::default())
+            .clock(Arc::new(clock.clone()))
+            .sleeper(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        assert_eq!(client.get_gas_address().await.unwrap(), "Gas111");
+        assert_eq!(clock.elapsed(), RetryPolicy::default().initial_backoff);
+        assert!(started.elapsed() < clock.elapsed());
+        assert_eq!(client.get_gas_address().await.unwrap(), "Gas111");
+        assert_eq!(backend.requests().len(), 2);
+        clock.advance(Duration::from_secs(5 * 60));
+        client.get_gas_address().await.unwrap();
+        assert_eq!(backend.requests().len(), 3);
+    }
+
+    /// A recorded session replays the same answers, through a JSON cassette, without the
+    /// original backend.
+    #[tokio::test]
+    async fn records_and_replays_cassettes() {
+        let live = FakeBackend::new();
+        live.on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig" })))
+            .on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": "Gas111" })));
+        let recorder = Arc::new(Recorder::new());
+        let client = Aethokit::builder()
+            .gas_key("live-key")
+            .middleware(recorder.clone())
+            .middleware(live)
+            .build()
+            .unwrap();
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        assert_eq!(client.get_gas_address().await.unwrap(), "Gas111");
+
+        let path = std::env::temp_dir().join(format!("aethokit-cassette-{}.json", std::process::id()));
+        recorder.cassette().save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(cassette.interactions[0].request_body, Some(json!({ "transaction": "tx" })));
+        assert!(!serde_json::to_string(&cassette).unwrap().contains("live-key"));
+
+        let client = FakeBackend::replay(&cassette).client().build().unwrap();
+        assert_eq!(client.sponsor_tx("tx".to_string()).await.unwrap(), "sig");
+        assert_eq!(client.get_gas_address().await.unwrap(), "Gas111");
+    }
+}
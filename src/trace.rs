@@ -0,0 +1,117 @@
+use reqwest::Request;
+
+use crate::signing::REQUEST_SIGNATURE_HEADER;
+
+/// Placeholder sent in place of the gas key in traced requests.
+pub(crate) const REDACTED_KEY: &str = "<redacted>";
+
+/// Headers whose values are secrets, or derived from one, and left out of traces.
+const REDACTED_HEADERS: [&str; 2] = ["x-gas-key", REQUEST_SIGNATURE_HEADER];
+
+/// Render a cURL command that resends `request` as the SDK built it, with its headers and
+/// body but the gas key and request signature redacted.
+///
+/// Bodies that aren't UTF-8, such as CBOR, are piped in through `printf` so they're sent
+/// byte for byte.
+pub(crate) fn curl_command(request: &Request) -> String {
+    let body = request.body().and_then(|body| body.as_bytes());
+    let text = body.map(std::str::from_utf8);
+    let mut cmd = match body {
+        Some(bytes) if !matches!(text, Some(Ok(_))) => format!("printf {} | curl", printf_escape(bytes)),
+        _ => "curl".to_string(),
+    };
+    cmd.push_str(&format!(" -X {} {}", request.method(), shell_quote(request.url().as_str())));
+    for (name, value) in request.headers() {
+        let value = match REDACTED_HEADERS.contains(&name.as_str()) {
+            true => REDACTED_KEY.into(),
+            false => String::from_utf8_lossy(value.as_bytes()),
+        };
+        cmd.push_str(&format!(" -H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+    match text {
+        Some(Ok(text)) => cmd.push_str(&format!(" --data {}", shell_quote(text))),
+        Some(Err(_)) => cmd.push_str(" --data-binary @-"),
+        None => {}
+    }
+    cmd
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Quote `bytes` as a `printf` format string, with every byte as an octal escape.
+fn printf_escape(bytes: &[u8]) -> String {
+    let escaped: String = bytes.iter().map(|byte| format!("\\{byte:03o}")).collect();
+    format!("'{escaped}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Method;
+    use url::Url;
+
+    use super::*;
+    use crate::test_util::{FakeBackend, FakeResponse, FAKE_GAS_KEY};
+    use crate::REQUEST_TIMESTAMP_HEADER;
+
+    /// The generated command never contains the key and escapes single quotes in the body.
+    #[test]
+    fn renders_redacted_curl() {
+        let url = Url::parse("https://aethokit.onrender.com/api/sponsor-tx").unwrap();
+        let request = reqwest::Client::new()
+            .request(Method::POST, url)
+            .header("x-gas-key", "secret")
+            .header("content-type", "application/json")
+            .body(r#"{"transaction":"it's"}"#)
+            .build()
+            .unwrap();
+        assert_eq!(
+            curl_command(&request),
+            "curl -X POST 'https://aethokit.onrender.com/api/sponsor-tx' \
+             -H 'x-gas-key: <redacted>' \
+             -H 'content-type: application/json' --data '{\"transaction\":\"it'\\''s\"}'"
+        );
+    }
+
+    /// Binary bodies are piped in unchanged rather than sent as text.
+    #[test]
+    fn pipes_binary_bodies() {
+        let url = Url::parse("https://aethokit.onrender.com/api/sponsor-tx").unwrap();
+        let request = reqwest::Client::new()
+            .request(Method::POST, url)
+            .header("content-type", "application/cbor")
+            .body(vec![0xa1, 0x00, 0xff])
+            .build()
+            .unwrap();
+        assert_eq!(
+            curl_command(&request),
+            "printf '\\241\\000\\377' | curl -X POST 'https://aethokit.onrender.com/api/sponsor-tx' \
+             -H 'content-type: application/cbor' --data-binary @-"
+        );
+    }
+
+    /// A traced failure reproduces the headers the client sent, including default headers
+    /// and request signing, without leaking the key or signature.
+    #[tokio::test]
+    async fn traces_sent_headers() {
+        let backend = FakeBackend::new();
+        backend.on("GET", "get-gas-address", FakeResponse::json(500, serde_json::json!({})));
+        let client = backend
+            .client()
+            .trace(true)
+            .default_header("x-tenant", "acme")
+            .signing_secret("secret")
+            .build()
+            .unwrap();
+
+        let err = client.get_gas_address().await.unwrap_err();
+        let curl = err.curl().unwrap();
+        assert!(curl.contains("-H 'x-tenant: acme'"), "{curl}");
+        assert!(curl.contains(&format!("-H '{REQUEST_SIGNATURE_HEADER}: <redacted>'")), "{curl}");
+        assert!(curl.contains(&format!("-H '{REQUEST_TIMESTAMP_HEADER}: ")), "{curl}");
+        assert!(curl.contains(&format!("-H '{}: ", crate::compat::SDK_VERSION_HEADER)), "{curl}");
+        assert!(curl.contains("-H 'x-gas-key: <redacted>'"), "{curl}");
+        assert!(!curl.contains(FAKE_GAS_KEY), "{curl}");
+    }
+}
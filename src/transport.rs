@@ -0,0 +1,86 @@
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use reqwest::{Client, Request, Response};
+
+use crate::AethokitError;
+
+/// Sends the client's HTTP requests, to the Aethokit API and the Solana RPC, see
+/// [`AethokitBuilder::transport`](crate::AethokitBuilder::transport).
+///
+/// Implement it to send requests with another HTTP stack, e.g. `ureq` on a blocking thread
+/// or a corporate HTTP library. Requests and responses keep reqwest's types: read the
+/// request's method, URL, headers and buffered body, and build the response with
+/// `Response::from` an `http::Response`. The default is a [`reqwest::Client`].
+///
+/// ```
+/// use aethokit::{AethokitError, HttpTransport};
+/// use futures_util::future::BoxFuture;
+///
+/// struct Canned;
+///
+/// impl HttpTransport for Canned {
+///     fn execute(&self, request: reqwest::Request) -> BoxFuture<'_, Result<reqwest::Response, AethokitError>> {
+///         let body = format!(r#"{{"gasAddress":"{}"}}"#, request.url().host_str().unwrap_or_default());
+///         Box::pin(async move { Ok(http::Response::new(body).into()) })
+///     }
+/// }
+/// ```
+pub trait HttpTransport: Send + Sync {
+    fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response, AethokitError>>;
+}
+
+impl HttpTransport for Client {
+    fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response, AethokitError>> {
+        let response = Client::execute(self, request);
+        Box::pin(async move { Ok(response.await?) })
+    }
+}
+
+/// Transport of a client, shareable between clones.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(pub(crate) Arc<dyn HttpTransport>);
+
+impl fmt::Debug for SharedTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedTransport")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use crate::Aethokit;
+
+    /// Requests reach the custom transport with the gas key and user agent set.
+    #[tokio::test]
+    async fn sends_through_custom_transport() {
+        #[derive(Default)]
+        struct Recording(Mutex<Vec<Request>>);
+        impl HttpTransport for Recording {
+            fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response, AethokitError>> {
+                self.0.lock().unwrap().push(request);
+                let response = http::Response::new(r#"{"gasAddress":"tank"}"#);
+                Box::pin(async { Ok(Response::from(response)) })
+            }
+        }
+
+        let transport = Arc::new(Recording::default());
+        let client = Aethokit::builder()
+            .gas_key("key")
+            .default_header("x-team", "payments")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.get_gas_address().await.unwrap(), "tank");
+        let sent = transport.0.lock().unwrap();
+        let headers = sent[0].headers();
+        assert_eq!(headers["x-gas-key"], "key");
+        assert_eq!(headers["x-team"], "payments");
+        assert!(headers["user-agent"].to_str().unwrap().starts_with("aethokit-rust/"));
+    }
+}
@@ -0,0 +1,422 @@
+//! Fluent builders for the most common sponsored transaction flows.
+
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, AddressLookupTableAccount, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use crate::signer::{missing_signers, sign_slots, Signer, SignerError};
+use crate::{
+    encode_transaction, encode_versioned_transaction, offline, Aethokit, AethokitError,
+    CommitmentLevel, ConfirmedSponsorship, EscalationSchedule, PendingTx,
+};
+
+/// Program ID of the SPL Memo program (v2).
+pub const MEMO_PROGRAM_ID: Pubkey = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Priority fee to attach through a compute-budget instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    /// Explicit compute-unit price, in micro-lamports
+    MicroLamports(u64),
+}
+
+impl Priority {
+    /// Compute-unit price for this priority, in micro-lamports.
+    pub fn micro_lamports_per_cu(self) -> u64 {
+        match self {
+            Priority::Low => 1_000,
+            Priority::Medium => 10_000,
+            Priority::High => 100_000,
+            Priority::MicroLamports(price) => price,
+        }
+    }
+}
+
+/// Builds, signs and sponsors a transaction with the gas tank as fee payer.
+///
+/// ```no_run
+/// # use aethokit::{Aethokit, tx::{Priority, SponsoredTxBuilder}};
+/// # use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signer}};
+/// # async fn run(client: &Aethokit, sender: &Keypair, to: Pubkey) -> Result<(), aethokit::AethokitError> {
+/// let hash = SponsoredTxBuilder::new(client)
+///     .transfer(&sender.pubkey(), &to, 1_000_000)
+///     .memo("order-1")
+///     .priority(Priority::High)
+///     .build_and_sponsor(&[sender])
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SponsoredTxBuilder<'a> {
+    client: &'a Aethokit,
+    instructions: Vec<Instruction>,
+    priority: Option<Priority>,
+    compute_unit_limit: Option<u32>,
+    recent_blockhash: Option<Hash>,
+}
+
+impl<'a> SponsoredTxBuilder<'a> {
+    pub fn new(client: &'a Aethokit) -> Self {
+        Self {
+            client,
+            instructions: Vec::new(),
+            priority: None,
+            compute_unit_limit: None,
+            recent_blockhash: None,
+        }
+    }
+
+    /// Append an arbitrary instruction.
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append a SOL transfer of `lamports` from `from` to `to`.
+    pub fn transfer(self, from: &Pubkey, to: &Pubkey, lamports: u64) -> Self {
+        self.instruction(solana_system_interface::instruction::transfer(from, to, lamports))
+    }
+
+    /// Append an SPL memo.
+    pub fn memo(self, memo: impl AsRef<str>) -> Self {
+        let instruction = Instruction::new_with_bytes(MEMO_PROGRAM_ID, memo.as_ref().as_bytes(), vec![]);
+        self.instruction(instruction)
+    }
+
+    /// Attach a priority fee.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Cap the compute units the transaction may consume.
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Use this blockhash instead of fetching one from the client's RPC endpoint.
+    pub fn recent_blockhash(mut self, blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(blockhash);
+        self
+    }
+
+    /// Build the transaction and sign it with `signers`, in any order.
+    ///
+    /// Accounts a program signs for, such as a PDA sender moved by the program after the
+    /// user signs an approval instruction, must be non-signer accounts of the instruction;
+    /// only the user is passed in `signers`.
+    ///
+    /// # Errors
+    /// - `Signer(MissingSignature)` if a required signer other than the gas tank was not provided
+    /// - `Signer(ProgramDerivedSigner)` if a PDA is marked as a signer
+    /// - `Signer(DuplicateSigner)` if a key is passed twice
+    pub async fn build(self, signers: &[&dyn Signer]) -> Result<Transaction, AethokitError> {
+        let tx = self.build_partial(signers).await?;
+        if let Some(&missing) = missing_signers(&tx)?.first() {
+            return Err(SignerError::MissingSignature(missing).into());
+        }
+        Ok(tx)
+    }
+
+    /// [`build`](Self::build), leaving the slots of signers not in `signers` empty to be
+    /// filled elsewhere, e.g. by the user's wallet approving the transaction. Check the
+    /// result with [`check_signatures`](crate::signer::check_signatures) before submitting it.
+    pub async fn build_partial(self, signers: &[&dyn Signer]) -> Result<Transaction, AethokitError> {
+        let instructions = offline::with_compute_budget(self.instructions, self.priority, self.compute_unit_limit);
+
+        let blockhash = match self.recent_blockhash {
+            Some(blockhash) => blockhash,
+            None => self.client.get_latest_blockhash().await?,
+        };
+        self.client
+            .build_sponsored_transaction(&instructions, signers, blockhash)
+            .await
+    }
+
+    /// Sponsor the transaction and wait for `commitment`, re-signing and resubmitting it with
+    /// a higher priority fee each time an attempt is not confirmed within the schedule's
+    /// `confirm_within`; see [`Aethokit::rebroadcast_with_escalation`].
+    ///
+    /// The first attempt uses the builder's [`priority`](Self::priority) (`Low` if unset),
+    /// the later ones the schedule's prices. Every attempt uses the same blockhash, so all
+    /// of them expire together, but an earlier attempt can still land alongside a later one:
+    /// only use this for instructions that are safe to execute twice or that conflict with
+    /// each other (e.g. a durable nonce). Whichever attempt lands first is returned.
+    ///
+    /// # Errors
+    /// - `NotConfirmed` if no attempt, up to the one at the price cap, is confirmed in time
+    pub async fn sponsor_with_fee_bump(
+        self,
+        signers: &[&dyn Signer],
+        commitment: CommitmentLevel,
+        schedule: EscalationSchedule,
+    ) -> Result<ConfirmedSponsorship, AethokitError> {
+        let client = self.client;
+        let blockhash = match self.recent_blockhash {
+            Some(blockhash) => blockhash,
+            None => client.get_latest_blockhash().await?,
+        };
+        let first_priority = self.priority.unwrap_or(Priority::Low);
+        let builder = self.recent_blockhash(blockhash);
+        let attempt = |priority| {
+            let builder = builder.clone().priority(priority);
+            async move { encode_transaction(&builder.build(signers).await?) }
+        };
+        let first = attempt(first_priority).await?;
+        client
+            .rebroadcast_with_escalation(PendingTx::Transaction(first), commitment, schedule, |price| {
+                attempt(Priority::MicroLamports(price))
+            })
+            .await
+    }
+
+    /// Build, sign and submit the transaction for sponsorship. Returns the transaction hash.
+    ///
+    /// If a tank is depleted the transaction is rebuilt with the next fallback gas key's
+    /// tank as fee payer.
+    pub async fn build_and_sponsor(self, signers: &[&dyn Signer]) -> Result<String, AethokitError> {
+        let resp = self
+            .client
+            .with_failover(|client| {
+                let builder = self.clone();
+                async move {
+                    let tx = SponsoredTxBuilder {
+                        client: &client,
+                        ..builder
+                    }
+                    .build(signers)
+                    .await?;
+                    client.sponsor_signature_with_meta(encode_transaction(&tx)?).await
+                }
+            })
+            .await?;
+        Ok(resp.value)
+    }
+}
+
+/// Builds a partially signed transaction from arbitrary instructions, with the gas tank as
+/// fee payer and its signature slot left for the backend.
+///
+/// Without lookup tables the result is a legacy transaction; with any, a v0 transaction
+/// whose accounts are loaded from them where possible.
+///
+/// ```no_run
+/// # use aethokit::{Aethokit, tx::{Priority, TransactionBuilder}};
+/// # use solana_sdk::{instruction::Instruction, signature::Keypair};
+/// # async fn run(client: &Aethokit, ix: Instruction, signer: &Keypair) -> Result<(), aethokit::AethokitError> {
+/// let payload = TransactionBuilder::for_client(client)
+///     .await?
+///     .instruction(ix)
+///     .priority(Priority::Medium)
+///     .build_base64(&[signer])
+///     .await?;
+/// let hash = client.sponsor_tx(payload).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    fee_payer: Pubkey,
+    recent_blockhash: Hash,
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    priority: Option<Priority>,
+    compute_unit_limit: Option<u32>,
+}
+
+impl TransactionBuilder {
+    pub fn new(fee_payer: Pubkey, recent_blockhash: Hash) -> Self {
+        Self {
+            fee_payer,
+            recent_blockhash,
+            instructions: Vec::new(),
+            lookup_tables: Vec::new(),
+            priority: None,
+            compute_unit_limit: None,
+        }
+    }
+
+    /// Start with the client's gas tank as fee payer and the latest blockhash from its RPC
+    /// endpoint.
+    pub async fn for_client(client: &Aethokit) -> Result<Self, AethokitError> {
+        let fee_payer = client.gas_pubkey().await?;
+        Ok(Self::new(fee_payer, client.get_latest_blockhash().await?))
+    }
+
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Load accounts from `table`, which makes the transaction a v0 transaction.
+    pub fn lookup_table(mut self, table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    /// Attach a priority fee.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Cap the compute units the transaction may consume.
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Compile the transaction and sign it with `signers`, in any order.
+    ///
+    /// # Errors
+    /// - `Compile` if the instructions don't fit the lookup tables' constraints
+    /// - `Signer(MissingSignature)` if a required signer other than the gas tank was not provided
+    pub async fn build(self, signers: &[&dyn Signer]) -> Result<VersionedTransaction, AethokitError> {
+        let instructions = offline::with_compute_budget(self.instructions, self.priority, self.compute_unit_limit);
+
+        let message = if self.lookup_tables.is_empty() {
+            VersionedMessage::Legacy(offline::sponsored_message(
+                &self.fee_payer,
+                &instructions,
+                self.recent_blockhash,
+            ))
+        } else {
+            VersionedMessage::V0(v0::Message::try_compile(
+                &self.fee_payer,
+                &instructions,
+                &self.lookup_tables,
+                self.recent_blockhash,
+            )?)
+        };
+        let required = usize::from(message.header().num_required_signatures);
+        let mut signatures = vec![Signature::default(); required];
+        let keys = &message.static_account_keys()[..required];
+        sign_slots(keys, &mut signatures, &message.serialize(), signers).await?;
+        if let Some((key, _)) = keys
+            .iter()
+            .zip(&signatures)
+            .skip(1)
+            .find(|(_, signature)| **signature == Signature::default())
+        {
+            return Err(SignerError::MissingSignature(*key).into());
+        }
+        Ok(VersionedTransaction { signatures, message })
+    }
+
+    /// [`build`](Self::build) the transaction and encode it for [`Aethokit::sponsor_tx`].
+    pub async fn build_base64(self, signers: &[&dyn Signer]) -> Result<String, AethokitError> {
+        encode_versioned_transaction(&self.build(signers).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An earlier, cheaper attempt that lands while a bumped one is pending is returned
+    /// instead of bumping again.
+    #[tokio::test(start_paused = true)]
+    async fn returns_earlier_attempt_that_landed() {
+        use std::time::Duration;
+
+        use serde_json::json;
+        use solana_sdk::signature::Keypair;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let record = |hash: &str, status: &str| {
+            FakeResponse::json(
+                200,
+                json!({ "hash": hash, "feePaid": 5000, "timestamp": "2024-05-01T12:00:00Z", "status": status }),
+            )
+        };
+        let gas = Pubkey::new_unique().to_string();
+        let backend = FakeBackend::new();
+        backend
+            .on("GET", "get-gas-address", FakeResponse::json(200, json!({ "gasAddress": gas })))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig1" })))
+            .on("POST", "sponsor-tx", FakeResponse::json(200, json!({ "hash": "sig2" })))
+            .on("GET", "get-sponsored-tx", record("sig1", "pending"))
+            .on("GET", "get-sponsored-tx", record("sig2", "pending"))
+            .on("GET", "get-sponsored-tx", record("sig1", "confirmed"));
+        let client = backend.client().build().unwrap();
+        let sender = Keypair::new();
+        let schedule = EscalationSchedule {
+            confirm_within: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        let confirmed = SponsoredTxBuilder::new(&client)
+            .transfer(&Signer::pubkey(&sender), &Pubkey::new_unique(), 1)
+            .recent_blockhash(Hash::new_unique())
+            .sponsor_with_fee_bump(&[&sender], CommitmentLevel::Confirmed, schedule)
+            .await
+            .unwrap();
+        assert_eq!(confirmed.signature, "sig1");
+        let posted: Vec<_> = backend
+            .requests()
+            .into_iter()
+            .filter(|req| req.method == "POST")
+            .map(|req| req.body.unwrap()["transaction"].clone())
+            .collect();
+        assert_eq!(posted.len(), 2);
+        assert_ne!(posted[0], posted[1]);
+    }
+
+    /// Lookup tables produce a v0 message, and only the fee payer's slot is left empty.
+    #[tokio::test]
+    async fn builds_v0_with_lookup_tables() {
+        use solana_sdk::instruction::AccountMeta;
+        use solana_sdk::signature::Keypair;
+
+        let fee_payer = Pubkey::new_unique();
+        let sender = Keypair::new();
+        let looked_up = Pubkey::new_unique();
+        let ix = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![
+                AccountMeta::new(Signer::pubkey(&sender), true),
+                AccountMeta::new(looked_up, false),
+            ],
+        );
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![looked_up],
+        };
+        let builder = TransactionBuilder::new(fee_payer, Hash::new_unique())
+            .instruction(ix)
+            .lookup_table(table)
+            .priority(Priority::Low);
+
+        let tx = builder.clone().build(&[&sender]).await.unwrap();
+        let VersionedMessage::V0(message) = &tx.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert!(!message.account_keys.contains(&looked_up));
+        assert_eq!(tx.signatures[0], Signature::default());
+        assert_ne!(tx.signatures[1], Signature::default());
+
+        let encoded = builder.clone().build_base64(&[&sender]).await.unwrap();
+        let raw = crate::codec::RawTransaction::from_base64(&encoded).unwrap();
+        assert_eq!(raw.unsigned_slots(), vec![0]);
+        assert!(matches!(
+            builder.build(&[]).await,
+            Err(AethokitError::Signer(SignerError::MissingSignature(_)))
+        ));
+    }
+}
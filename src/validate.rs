@@ -0,0 +1,112 @@
+use crate::codec::{RawTransaction, PACKET_DATA_SIZE};
+use crate::{Aethokit, AethokitError};
+
+/// Most signatures a transaction can carry and still fit in a packet: each signer costs a
+/// 64-byte signature plus its 32-byte account key.
+pub const MAX_SIGNATURES: usize = 12;
+
+/// A transaction that would be rejected for its size, caught before it is sent.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+    #[error(
+        "transaction is {size} bytes, over the {max} byte limit; move accounts into address \
+         lookup tables with a v0 message, or split the instructions across transactions"
+    )]
+    TooLarge { size: usize, max: usize },
+    #[error(
+        "transaction needs {count} signatures, over the limit of {max}; split it into \
+         transactions with fewer signers"
+    )]
+    TooManySignatures { count: usize, max: usize },
+}
+
+/// Check `tx` fits in a packet and doesn't need more signatures than one can carry.
+///
+/// # Errors
+/// - `TooManySignatures` if the message requires more than [`MAX_SIGNATURES`] signers
+/// - `TooLarge` if the serialized transaction is over [`PACKET_DATA_SIZE`] bytes
+pub fn precheck_transaction(tx: &RawTransaction) -> Result<(), ValidationError> {
+    let count = tx
+        .signatures
+        .len()
+        .max(usize::from(tx.message.header.num_required_signatures));
+    if count > MAX_SIGNATURES {
+        return Err(ValidationError::TooManySignatures {
+            count,
+            max: MAX_SIGNATURES,
+        });
+    }
+    let size = tx.encode().len();
+    if size > PACKET_DATA_SIZE {
+        return Err(ValidationError::TooLarge {
+            size,
+            max: PACKET_DATA_SIZE,
+        });
+    }
+    Ok(())
+}
+
+impl Aethokit {
+    /// Run [`precheck_transaction`] on a base64 transaction if the client was built with
+    /// [`precheck`](crate::AethokitBuilder::precheck).
+    pub(crate) fn precheck(&self, tx: &str) -> Result<(), AethokitError> {
+        if self.precheck {
+            precheck_transaction(&RawTransaction::from_base64(tx)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::codec::{CompiledInstruction, MessageHeader, MessageVersion, RawMessage};
+    use crate::test_util::FakeBackend;
+
+    fn transaction(signers: u8, data_len: usize) -> RawTransaction {
+        RawTransaction {
+            signatures: vec![[0; 64]; usize::from(signers)],
+            message: RawMessage {
+                version: MessageVersion::Legacy,
+                header: MessageHeader {
+                    num_required_signatures: signers,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: (0..=signers).map(|i| [i; 32]).collect(),
+                recent_blockhash: [9; 32],
+                instructions: vec![CompiledInstruction {
+                    program_id_index: signers,
+                    accounts: vec![0],
+                    data: vec![0xaa; data_len],
+                }],
+                address_table_lookups: vec![],
+            },
+        }
+    }
+
+    /// Oversized transactions fail before anything is sent, and only with the check enabled.
+    #[tokio::test]
+    async fn rejects_oversized_transactions() {
+        assert_eq!(precheck_transaction(&transaction(1, 100)), Ok(()));
+        assert_eq!(
+            precheck_transaction(&transaction(13, 0)),
+            Err(ValidationError::TooManySignatures { count: 13, max: 12 })
+        );
+        let oversized = transaction(1, 1200);
+        let size = oversized.encode().len();
+        assert_eq!(
+            precheck_transaction(&oversized),
+            Err(ValidationError::TooLarge { size, max: 1232 })
+        );
+
+        let backend = FakeBackend::new();
+        let client = backend.client().precheck(true).build().unwrap();
+        match client.sponsor_tx(oversized.to_base64()).await {
+            Err(AethokitError::Validation(ValidationError::TooLarge { .. })) => {}
+            other => panic!("expected TooLarge, got {other:?}"),
+        }
+        assert!(backend.requests().is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+//! Local checks of a fully-signed transaction returned by the backend, see
+//! [`SponsorOptions::verify_signature`](crate::SponsorOptions::verify_signature), and of
+//! sponsorship receipts.
+
+use base64::Engine as _;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use thiserror::Error;
+
+use crate::{Aethokit, AethokitError, SponsoredTx};
+
+/// Why a sponsorship's signed transaction or receipt was not accepted.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VerifyError {
+    #[error("the backend did not return the signed transaction")]
+    MissingSignedTransaction,
+    #[error("signed transaction could not be decoded")]
+    Undecodable,
+    #[error("the backend changed the transaction message")]
+    MessageMutated,
+    #[error("fee payer is {found}, expected the gas tank {expected}")]
+    WrongFeePayer { expected: Pubkey, found: Pubkey },
+    #[error("the gas tank's signature does not verify")]
+    InvalidSponsorSignature,
+    #[error("the backend changed signatures other than the gas tank's")]
+    SignaturesChanged,
+    #[error("the receipt's proof does not verify")]
+    InvalidReceiptSignature,
+}
+
+/// Check that `signed` is `submitted` with only the fee payer's slot filled in, by `gas_tank`.
+pub fn verify_sponsor_signature(
+    submitted: &str,
+    signed: &str,
+    gas_tank: &Pubkey,
+) -> Result<(), VerifyError> {
+    let submitted = decode(submitted)?;
+    let signed = decode(signed)?;
+    let message = signed.message.serialize();
+    if message != submitted.message.serialize() {
+        return Err(VerifyError::MessageMutated);
+    }
+    let fee_payer = signed
+        .message
+        .static_account_keys()
+        .first()
+        .copied()
+        .unwrap_or_default();
+    if fee_payer != *gas_tank {
+        return Err(VerifyError::WrongFeePayer {
+            expected: *gas_tank,
+            found: fee_payer,
+        });
+    }
+    let Some((sponsor, others)) = signed.signatures.split_first() else {
+        return Err(VerifyError::InvalidSponsorSignature);
+    };
+    if !sponsor.verify(gas_tank.as_ref(), &message) {
+        return Err(VerifyError::InvalidSponsorSignature);
+    }
+    if others != submitted.signatures.get(1..).unwrap_or_default() {
+        return Err(VerifyError::SignaturesChanged);
+    }
+    Ok(())
+}
+
+fn decode(tx: &str) -> Result<VersionedTransaction, VerifyError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(tx.trim())
+        .map_err(|_| VerifyError::Undecodable)?;
+    bincode::deserialize(&bytes).map_err(|_| VerifyError::Undecodable)
+}
+
+impl Aethokit {
+    /// Verify `sponsored` against the `submitted` transaction, with this client's gas tank.
+    pub(crate) async fn verify_sponsorship(
+        &self,
+        submitted: &str,
+        sponsored: &SponsoredTx,
+    ) -> Result<(), AethokitError> {
+        let signed = sponsored
+            .signed_transaction
+            .as_deref()
+            .ok_or(VerifyError::MissingSignedTransaction)?;
+        let gas_tank = self.gas_pubkey().await?;
+        Ok(verify_sponsor_signature(submitted, signed, &gas_tank)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_util::{FakeBackend, FakeResponse};
+    use crate::{encode_transaction, offline, SponsorOptions};
+
+    /// A correctly co-signed transaction passes; a tampered message or a foreign fee payer
+    /// signature fails the sponsorship.
+    #[tokio::test]
+    async fn verifies_returned_transaction() {
+        let gas_tank = Keypair::new();
+        let sender = Keypair::new();
+        let transfer = solana_system_interface::instruction::transfer(
+            &sender.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        );
+        let message =
+            offline::sponsored_message(&gas_tank.pubkey(), &[transfer], Hash::new_unique());
+        let mut tx = Transaction::new_unsigned(message);
+        offline::partial_sign(&mut tx, &[&sender]).unwrap();
+        let submitted = encode_transaction(&tx).unwrap();
+        let mut signed = tx.clone();
+        offline::partial_sign(&mut signed, &[&gas_tank]).unwrap();
+        let mut forged = tx.clone();
+        forged.signatures[0] = Keypair::new().sign_message(&tx.message_data());
+
+        let mut mutated = signed.clone();
+        mutated.message.recent_blockhash = Hash::new_unique();
+        assert_eq!(
+            verify_sponsor_signature(
+                &submitted,
+                &encode_transaction(&mutated).unwrap(),
+                &gas_tank.pubkey()
+            ),
+            Err(VerifyError::MessageMutated)
+        );
+
+        let backend = FakeBackend::new();
+        let address = json!({ "gasAddress": gas_tank.pubkey().to_string() });
+        backend.on("GET", "get-gas-address", FakeResponse::json(200, address));
+        for tx in [&signed, &forged] {
+            let body =
+                json!({ "hash": "sig", "signedTransaction": encode_transaction(tx).unwrap() });
+            backend.on("POST", "sponsor-tx", FakeResponse::json(200, body));
+        }
+        let client = backend.client().build().unwrap();
+        let options = SponsorOptions {
+            verify_signature: true,
+            ..Default::default()
+        };
+
+        let sponsored = client
+            .sponsor_tx_with_options(submitted.clone(), options.clone())
+            .await
+            .unwrap();
+        assert_eq!(sponsored.signature, "sig");
+        assert_eq!(
+            backend.requests()[0].body.as_ref().unwrap()["returnSigned"],
+            true
+        );
+        assert!(matches!(
+            client.sponsor_tx_with_options(submitted, options).await,
+            Err(AethokitError::Verify(VerifyError::InvalidSponsorSignature))
+        ));
+    }
+}
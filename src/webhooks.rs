@@ -0,0 +1,343 @@
+//! Types and signature verification for webhooks sent by Aethokit.
+//!
+//! Each delivery carries an `x-aethokit-timestamp` header and an `x-aethokit-signature`
+//! header holding the hex-encoded HMAC-SHA256 of `"{timestamp}.{body}"`, keyed with the
+//! webhook secret. Deliveries signed more than [`DEFAULT_TOLERANCE`] away from the current
+//! time are rejected, so a captured delivery can't be replayed later.
+//!
+//! Webhooks are registered and removed with [`Aethokit::register_webhook`] and
+//! [`Aethokit::delete_webhook`].
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::{Aethokit, AethokitError, WebhookId};
+
+/// Header carrying the hex-encoded HMAC-SHA256 signature of the delivery.
+pub const SIGNATURE_HEADER: &str = "x-aethokit-signature";
+/// Header carrying the Unix timestamp (seconds) the delivery was signed at.
+pub const TIMESTAMP_HEADER: &str = "x-aethokit-timestamp";
+/// How far the delivery's timestamp may be from the current time, either way, in
+/// [`verify_signature`] and [`verify_and_parse`].
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("missing or non-ascii `{0}` header")]
+    MissingHeader(&'static str),
+    #[error("webhook signature does not match")]
+    InvalidSignature,
+    /// The signature matches but the timestamp is not a Unix time within the tolerance of
+    /// now, so the delivery may be a replay
+    #[error("webhook timestamp `{0}` is outside the allowed tolerance")]
+    TimestampOutOfTolerance(String),
+    #[error("invalid webhook payload: {0}")]
+    Payload(#[from] serde_json::Error),
+}
+
+/// A webhook delivery, tagged by its `type` field.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type")]
+pub enum WebhookEvent {
+    #[serde(rename = "tx.confirmed")]
+    TxConfirmed(TxConfirmed),
+    #[serde(rename = "tx.failed")]
+    TxFailed(TxFailed),
+    #[serde(rename = "balance.low")]
+    LowBalance(LowBalance),
+}
+
+/// A sponsored transaction was confirmed on-chain.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TxConfirmed {
+    pub hash: String,
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub slot: u64,
+    /// Fee paid by the gas tank, in lamports
+    #[serde(rename = "feePaid", deserialize_with = "crate::lenient::u64")]
+    pub fee_paid: u64,
+    /// Time the event was emitted
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A sponsored transaction failed on-chain or was dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TxFailed {
+    pub hash: String,
+    /// Error reported by the backend
+    pub error: String,
+    /// Time the event was emitted
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The gas tank balance dropped below its alert threshold.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LowBalance {
+    #[serde(rename = "gasAddress")]
+    pub gas_address: String,
+    /// Current balance, in lamports
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub balance: u64,
+    /// Configured alert threshold, in lamports
+    #[serde(deserialize_with = "crate::lenient::u64")]
+    pub threshold: u64,
+    /// Time the event was emitted
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Kind of [`WebhookEvent`] a webhook subscribes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "tx.confirmed")]
+    TxConfirmed,
+    #[serde(rename = "tx.failed")]
+    TxFailed,
+    #[serde(rename = "balance.low")]
+    LowBalance,
+}
+
+/// A webhook registered for the gas tank, see [`Aethokit::register_webhook`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    /// Endpoint deliveries are posted to
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+    /// Time the webhook was registered
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct RegisterRequest<'a> {
+    url: &'a str,
+    events: &'a [WebhookEventType],
+    secret: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookList {
+    webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Serialize)]
+struct DeleteRequest<'a> {
+    id: &'a WebhookId,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteResponse {}
+
+impl Aethokit {
+    /// Register `url` to receive the gas tank's `events`, signed with `secret`.
+    ///
+    /// Verify deliveries with the same secret using [`verify_and_parse`]. The backend only
+    /// keeps a hash of the secret, so store it where the receiving service can read it.
+    pub async fn register_webhook(
+        &self,
+        url: &str,
+        events: &[WebhookEventType],
+        secret: &str,
+    ) -> Result<Webhook, AethokitError> {
+        let body = RegisterRequest { url, events, secret };
+        self.make_request("register-webhook", Method::POST, Some(&body))
+            .await
+    }
+
+    /// Every webhook registered for the gas tank.
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>, AethokitError> {
+        let list: WebhookList = self
+            .make_request::<(), _>("get-webhooks", Method::GET, None)
+            .await?;
+        Ok(list.webhooks)
+    }
+
+    /// Stop deliveries to a webhook and remove it.
+    pub async fn delete_webhook(&self, id: &WebhookId) -> Result<(), AethokitError> {
+        self.make_request::<_, DeleteResponse>("delete-webhook", Method::POST, Some(&DeleteRequest { id }))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Verify that a webhook delivery was signed with `secret` within [`DEFAULT_TOLERANCE`] of
+/// now.
+///
+/// `body` must be the raw request body, exactly as received.
+///
+/// # Errors
+/// - `MissingHeader` if the signature or timestamp header is absent
+/// - `InvalidSignature` if the signature is malformed or does not match
+/// - `TimestampOutOfTolerance` if the delivery was signed too long ago or in the future
+pub fn verify_signature(secret: &[u8], headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookError> {
+    verify_signature_with_tolerance(secret, headers, body, DEFAULT_TOLERANCE)
+}
+
+/// Like [`verify_signature`], accepting timestamps up to `tolerance` away from now, e.g. to
+/// allow for clock skew or deliveries queued by a proxy.
+pub fn verify_signature_with_tolerance(
+    secret: &[u8],
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+) -> Result<(), WebhookError> {
+    verify_signature_at(secret, headers, body, tolerance, unix_now())
+}
+
+fn unix_now() -> i64 {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    since_epoch.as_secs() as i64
+}
+
+fn verify_signature_at(
+    secret: &[u8],
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance: Duration,
+    now: i64,
+) -> Result<(), WebhookError> {
+    let signature = header_str(headers, SIGNATURE_HEADER)?;
+    let timestamp = header_str(headers, TIMESTAMP_HEADER)?;
+    let expected = hex::decode(signature.trim()).map_err(|_| WebhookError::InvalidSignature)?;
+
+    // verify_slice compares in constant time
+    signing_mac(secret, timestamp, body)
+        .verify_slice(&expected)
+        .map_err(|_| WebhookError::InvalidSignature)?;
+    match timestamp.trim().parse::<i64>() {
+        Ok(signed_at) if signed_at.abs_diff(now) <= tolerance.as_secs() => Ok(()),
+        _ => Err(WebhookError::TimestampOutOfTolerance(timestamp.to_string())),
+    }
+}
+
+/// Parse a webhook payload without verifying it.
+pub fn parse_event(body: &[u8]) -> Result<WebhookEvent, WebhookError> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// Verify a webhook delivery, then parse its payload.
+pub fn verify_and_parse(
+    secret: &[u8],
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<WebhookEvent, WebhookError> {
+    verify_signature(secret, headers, body)?;
+    parse_event(body)
+}
+
+fn signing_mac(secret: &[u8], timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, WebhookError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(WebhookError::MissingHeader(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_headers(secret: &[u8], timestamp: &str, body: &[u8]) -> HeaderMap {
+        let signature = hex::encode(signing_mac(secret, timestamp, body).finalize().into_bytes());
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers.insert(TIMESTAMP_HEADER, timestamp.parse().unwrap());
+        headers
+    }
+
+    /// A correctly signed delivery parses into its typed event; a tampered body is rejected.
+    #[test]
+    fn verifies_and_parses_delivery() {
+        let body = br#"{"type":"balance.low","gasAddress":"Gas111","balance":1000,"threshold":5000,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let headers = signed_headers(b"whsec", &unix_now().to_string(), body);
+
+        match verify_and_parse(b"whsec", &headers, body).unwrap() {
+            WebhookEvent::LowBalance(event) => assert_eq!(event.balance, 1000),
+            other => panic!("expected LowBalance, got {other:?}"),
+        }
+
+        let tampered = String::from_utf8_lossy(body).replace("1000", "9000");
+        match verify_signature(b"whsec", &headers, tampered.as_bytes()) {
+            Err(WebhookError::InvalidSignature) => {}
+            other => panic!("expected InvalidSignature, got {other:?}"),
+        }
+    }
+
+    /// Authentic deliveries signed outside the tolerance, in the past or the future, are
+    /// rejected as possible replays.
+    #[test]
+    fn rejects_timestamps_outside_tolerance() {
+        let body = br#"{"type":"tx.failed"}"#;
+        let now = 1_704_067_200;
+        let verify = |signed_at: i64| {
+            let headers = signed_headers(b"whsec", &signed_at.to_string(), body);
+            verify_signature_at(b"whsec", &headers, body, DEFAULT_TOLERANCE, now)
+        };
+        assert!(verify(now - 299).is_ok());
+        assert!(verify(now + 60).is_ok());
+        match verify(now - 301) {
+            Err(WebhookError::TimestampOutOfTolerance(timestamp)) => assert_eq!(timestamp, "1704066899"),
+            other => panic!("expected TimestampOutOfTolerance, got {other:?}"),
+        }
+        assert!(matches!(verify(now + 301), Err(WebhookError::TimestampOutOfTolerance(_))));
+
+        let stale = signed_headers(b"whsec", "1704067200", body);
+        assert!(matches!(
+            verify_signature(b"whsec", &stale, body),
+            Err(WebhookError::TimestampOutOfTolerance(_))
+        ));
+        let a_year = Duration::from_secs(366 * 24 * 60 * 60);
+        assert!(verify_signature_at(b"whsec", &stale, body, a_year, now + 86_400).is_ok());
+    }
+
+    /// Webhooks are registered with their events and secret, listed and deleted by ID.
+    #[tokio::test]
+    async fn manages_webhooks() {
+        use serde_json::json;
+
+        use crate::test_util::{FakeBackend, FakeResponse};
+
+        let webhook = json!({
+            "id": "wh_1",
+            "url": "https://ops.example.com/hooks",
+            "events": ["balance.low", "tx.failed"],
+            "createdAt": "2024-05-01T12:00:00Z",
+        });
+        let backend = FakeBackend::new();
+        backend
+            .on("POST", "register-webhook", FakeResponse::json(200, webhook.clone()))
+            .on("GET", "get-webhooks", FakeResponse::json(200, json!({ "webhooks": [webhook] })))
+            .on("POST", "delete-webhook", FakeResponse::json(200, json!({})));
+        let client = backend.client().build().unwrap();
+
+        let events = [WebhookEventType::LowBalance, WebhookEventType::TxFailed];
+        let registered = client
+            .register_webhook("https://ops.example.com/hooks", &events, "whsec")
+            .await
+            .unwrap();
+        assert_eq!(registered.events, events);
+        assert_eq!(client.list_webhooks().await.unwrap(), std::slice::from_ref(&registered));
+        client.delete_webhook(&registered.id).await.unwrap();
+
+        let requests = backend.requests();
+        assert_eq!(
+            requests[0].body,
+            Some(json!({ "url": "https://ops.example.com/hooks", "events": ["balance.low", "tx.failed"], "secret": "whsec" }))
+        );
+        assert_eq!(requests[2].body, Some(json!({ "id": "wh_1" })));
+    }
+}
@@ -0,0 +1,158 @@
+//! End-to-end tests against the live Aethokit API and Solana devnet, run before releases:
+//!
+//! ```sh
+//! AETHOKIT_GAS_KEY=... AETHOKIT_IT_SENDER=... cargo test --features it -- --ignored
+//! ```
+//!
+//! - `AETHOKIT_GAS_KEY`: gas key of a funded devnet tank (`AETHOKIT_NETWORK` defaults to devnet)
+//! - `AETHOKIT_IT_SENDER`: base58 keypair of a funded devnet account, the sender of the transfers
+//! - `AETHOKIT_IT_TOKEN_ACCOUNT`: token account owned by the sender holding at least one base
+//!   unit, for the SPL transfer
+//!
+//! The tests are ignored by default so `cargo test` stays offline.
+
+use std::time::Duration;
+
+use aethokit::tx::MEMO_PROGRAM_ID;
+use aethokit::{codes, Aethokit, AethokitBuilder, AethokitError, CommitmentLevel, SponsoredTxBuilder};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::transaction::VersionedTransaction;
+
+const SPL_TOKEN_PROGRAM_ID: Pubkey = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn client() -> Aethokit {
+    Aethokit::from_env().expect("AETHOKIT_GAS_KEY must be set")
+}
+
+fn env(var: &str) -> String {
+    std::env::var(var).unwrap_or_else(|_| panic!("{var} must be set"))
+}
+
+fn sender() -> Keypair {
+    Keypair::from_base58_string(&env("AETHOKIT_IT_SENDER"))
+}
+
+fn pubkey(keypair: &Keypair) -> Pubkey {
+    solana_sdk::signer::Signer::pubkey(keypair)
+}
+
+/// A system transfer is sponsored and confirmed, with the tank paying the fee.
+#[tokio::test]
+#[ignore = "needs a devnet gas key and funded sender"]
+async fn sponsors_transfer() {
+    let client = client();
+    let sender = sender();
+    let tx = SponsoredTxBuilder::new(&client)
+        .transfer(&pubkey(&sender), &pubkey(&sender), 1)
+        .memo("aethokit-it")
+        .build(&[&sender])
+        .await
+        .unwrap();
+
+    let confirmed = client
+        .sponsor_and_confirm(
+            aethokit::encode_transaction(&tx).unwrap(),
+            CommitmentLevel::Confirmed,
+            CONFIRM_TIMEOUT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(confirmed.signature, tx.signatures[0].to_string());
+    assert!(confirmed.fee > 0);
+}
+
+/// An SPL token transfer is sponsored; the sender moves one base unit to itself.
+#[tokio::test]
+#[ignore = "needs a devnet gas key, funded sender and token account"]
+async fn sponsors_spl_transfer() {
+    let client = client();
+    let sender = sender();
+    let account: Pubkey = env("AETHOKIT_IT_TOKEN_ACCOUNT").parse().unwrap();
+    // SPL token `Transfer`: tag 3, then the amount
+    let mut data = vec![3];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    let transfer = Instruction::new_with_bytes(
+        SPL_TOKEN_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(account, false),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(pubkey(&sender), true),
+        ],
+    );
+
+    let hash = SponsoredTxBuilder::new(&client)
+        .instruction(transfer)
+        .build_and_sponsor(&[&sender])
+        .await
+        .unwrap();
+    assert!(hash.parse::<Signature>().is_ok());
+}
+
+/// A v0 transaction is sponsored like a legacy one.
+#[tokio::test]
+#[ignore = "needs a devnet gas key and funded sender"]
+async fn sponsors_versioned_transaction() {
+    let client = client();
+    let sender = sender();
+    let gas_address: Pubkey = client.get_gas_address().await.unwrap().parse().unwrap();
+    let memo = Instruction::new_with_bytes(
+        MEMO_PROGRAM_ID,
+        b"aethokit-it v0",
+        vec![AccountMeta::new_readonly(pubkey(&sender), true)],
+    );
+    let blockhash = client.get_latest_blockhash().await.unwrap();
+    let message = v0::Message::try_compile(&gas_address, &[memo], &[], blockhash).unwrap();
+    let message = VersionedMessage::V0(message);
+    let mut signatures = vec![Signature::default(); message.header().num_required_signatures.into()];
+    let slot = message
+        .static_account_keys()
+        .iter()
+        .position(|key| *key == pubkey(&sender))
+        .unwrap();
+    signatures[slot] = solana_sdk::signer::Signer::sign_message(&sender, &message.serialize());
+    let tx = VersionedTransaction { signatures, message };
+
+    let confirmed = client
+        .sponsor_and_confirm(
+            aethokit::encode_versioned_transaction(&tx).unwrap(),
+            CommitmentLevel::Confirmed,
+            CONFIRM_TIMEOUT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(confirmed.signature, tx.signatures[slot].to_string());
+}
+
+/// Rejections surface as typed errors: an unknown gas key, a malformed transaction and a
+/// blockhash the cluster has never seen.
+#[tokio::test]
+#[ignore = "needs a devnet gas key and funded sender"]
+async fn reports_failures() {
+    let client = client();
+    let sender = sender();
+
+    let unknown = AethokitBuilder::from_env()
+        .unwrap()
+        .gas_key("not-a-real-key")
+        .build()
+        .unwrap();
+    let err = unknown.sponsor_tx("AQ==".to_string()).await.unwrap_err();
+    assert_eq!(err.code(), Some(codes::INVALID_GAS_KEY));
+
+    let err = client.sponsor_tx("not base64".to_string()).await.unwrap_err();
+    assert!(err.is_client_error(), "{err}");
+
+    let err = SponsoredTxBuilder::new(&client)
+        .transfer(&pubkey(&sender), &pubkey(&sender), 1)
+        .recent_blockhash(Hash::new_unique())
+        .build_and_sponsor(&[&sender])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AethokitError::BlockhashExpired), "{err}");
+}